@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS memory_reports (
+    id TEXT PRIMARY KEY NOT NULL,
+    server_id TEXT NOT NULL,
+    pattern TEXT NOT NULL,
+    separator TEXT NOT NULL,
+    prefix_depth INTEGER NOT NULL,
+    key_count INTEGER NOT NULL,
+    total_memory INTEGER NOT NULL,
+    cancelled INTEGER NOT NULL,
+    analysis TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS memory_reports_server_id_created_at ON memory_reports (server_id, created_at);
@@ -0,0 +1 @@
+ALTER TABLE servers ADD COLUMN sentinel TEXT DEFAULT '' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE servers ADD COLUMN cluster INTEGER DEFAULT 0 NOT NULL;
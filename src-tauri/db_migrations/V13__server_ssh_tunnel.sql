@@ -0,0 +1 @@
+ALTER TABLE servers ADD COLUMN ssh_tunnel TEXT DEFAULT '' NOT NULL;
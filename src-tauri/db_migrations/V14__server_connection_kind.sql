@@ -0,0 +1,2 @@
+ALTER TABLE servers ADD COLUMN connection_kind TEXT DEFAULT 'tcp' NOT NULL;
+ALTER TABLE servers ADD COLUMN socket_path TEXT DEFAULT '' NOT NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE servers ADD COLUMN username TEXT DEFAULT '' NOT NULL;
+ALTER TABLE servers ADD COLUMN password TEXT DEFAULT '' NOT NULL;
+ALTER TABLE servers ADD COLUMN database INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE servers ADD COLUMN tls INTEGER DEFAULT 0 NOT NULL;
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS command_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    server_id TEXT NOT NULL,
+    command TEXT NOT NULL,
+    duration_us INTEGER NOT NULL,
+    success INTEGER NOT NULL,
+    executed_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS command_history_server_id ON command_history (server_id);
+
+CREATE TABLE IF NOT EXISTS snippets (
+    id TEXT PRIMARY KEY NOT NULL,
+    server_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    command TEXT NOT NULL,
+    parameters TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS command_docs_cache (
+    version TEXT PRIMARY KEY NOT NULL,
+    commands TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE servers ADD COLUMN read_only INTEGER DEFAULT 0 NOT NULL;
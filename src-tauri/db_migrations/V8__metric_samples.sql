@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS metric_samples (
+    id TEXT PRIMARY KEY NOT NULL,
+    server_id TEXT NOT NULL,
+    ops_per_sec REAL NOT NULL,
+    used_memory INTEGER NOT NULL,
+    connected_clients INTEGER NOT NULL,
+    hit_ratio REAL,
+    input_bytes_per_sec REAL NOT NULL,
+    output_bytes_per_sec REAL NOT NULL,
+    sampled_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS metric_samples_server_id_sampled_at ON metric_samples (server_id, sampled_at);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS slowlog_entries (
+    id TEXT PRIMARY KEY NOT NULL,
+    server_id TEXT NOT NULL,
+    entry_id INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    duration_us INTEGER NOT NULL,
+    args TEXT NOT NULL,
+    client_addr TEXT NOT NULL,
+    client_name TEXT NOT NULL,
+    captured_at DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+-- Entry ids restart with the server, so the timestamp is part of what identifies an entry.
+CREATE UNIQUE INDEX IF NOT EXISTS slowlog_entries_server_entry ON slowlog_entries (server_id, entry_id, timestamp);
@@ -0,0 +1,127 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+    utils::split_command_args,
+};
+use redis::Value as RedisValue;
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AclDryRun {
+    allowed: bool,
+    /// Why the server would deny the command, e.g. a key outside the user's patterns.
+    reason: Option<String>,
+}
+
+/// Checks whether `username` could run a command line without running it. Needs Redis 7.
+async fn _acl_dry_run(
+    state: &Mutex<AppState>,
+    username: String,
+    command: String,
+) -> Result<AclDryRun, AppError> {
+    let args = split_command_args(&command)
+        .filter(|args| !args.is_empty())
+        .ok_or_else(|| {
+            log::warn!("Invalid command to dry run: {}", command);
+            AppError::InvalidCommand
+        })?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let reply: RedisValue = redis::cmd("ACL")
+        .arg("DRYRUN")
+        .arg(&username)
+        .arg(&args)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to dry run {} as {}: {}", command, username, e);
+            AppError::RedisFailed
+        })?;
+
+    // The server replies OK when allowed and the denial reason as a bulk string otherwise.
+    Ok(match reply {
+        RedisValue::Okay => AclDryRun {
+            allowed: true,
+            reason: None,
+        },
+        RedisValue::SimpleString(s) if s == "OK" => AclDryRun {
+            allowed: true,
+            reason: None,
+        },
+        RedisValue::BulkString(bytes) => AclDryRun {
+            allowed: false,
+            reason: Some(String::from_utf8_lossy(&bytes).into_owned()),
+        },
+        other => {
+            log::error!("Unexpected ACL DRYRUN reply: {:?}", other);
+            return Err(AppError::RedisFailed);
+        }
+    })
+}
+
+#[tauri::command]
+pub async fn acl_dry_run(
+    state: State<'_, Mutex<AppState>>,
+    username: String,
+    command: String,
+) -> Result<AclDryRun, AppError> {
+    _acl_dry_run(state.inner(), username, command).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_acl_dry_run_invalid_command() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _acl_dry_run(&app_state, "default".to_string(), "  ".to_string()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+
+    #[tokio::test]
+    async fn test_acl_dry_run() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: () = redis::cmd("ACL")
+                .arg("SETUSER")
+                .arg("reader")
+                .arg(&["on", "nopass", "~user:*", "+@read"])
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let allowed = _acl_dry_run(&app_state, "reader".to_string(), "GET user:1".to_string())
+            .await
+            .unwrap();
+        assert!(allowed.allowed);
+
+        let denied = _acl_dry_run(&app_state, "reader".to_string(), "SET user:1 x".to_string())
+            .await
+            .unwrap();
+        assert!(!denied.allowed);
+        assert!(denied.reason.unwrap().contains("set"));
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Returns the user the app is authenticated as.
+async fn _acl_whoami(state: &Mutex<AppState>) -> Result<String, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("ACL")
+        .arg("WHOAMI")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get the current ACL user: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn acl_whoami(state: State<'_, Mutex<AppState>>) -> Result<String, AppError> {
+    _acl_whoami(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acl_whoami_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _acl_whoami(&app_state).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
@@ -1,19 +1,36 @@
 use crate::{
     core::{AppError, AppState},
-    models::{Model, Server},
-    services::test_connection,
+    models::{ConnectionKind, Model, SentinelSettings, Server, SshTunnelSettings},
+    services::{test_connection, SshTunnel},
 };
 use tauri::State;
 use tokio::sync::Mutex;
 
+#[allow(clippy::too_many_arguments)]
 async fn _add_server(
     state: &Mutex<AppState>,
     name: String,
     address: String,
     port: u16,
+    read_only: Option<bool>,
+    sentinel: Option<SentinelSettings>,
+    cluster: Option<bool>,
+    ssh_tunnel: Option<SshTunnelSettings>,
+    connection_kind: Option<ConnectionKind>,
 ) -> Result<Server, AppError> {
-    let server = Server::from_payload(name, address, port);
-    test_connection(&server).await?;
+    let server = Server {
+        read_only: read_only.unwrap_or(false),
+        sentinel,
+        cluster: cluster.unwrap_or(false),
+        ssh_tunnel,
+        connection_kind: connection_kind.unwrap_or_default(),
+        ..Server::from_payload(name, address, port)
+    };
+    // Checked through a temporary tunnel, closed once the check is done.
+    match SshTunnel::open_for(&server).await? {
+        Some(tunnel) => test_connection(&tunnel.local_server(&server)).await?,
+        None => test_connection(&server).await?,
+    };
 
     let app_state = state.lock().await;
     let db_connection = app_state.get_db_connection().ok_or_else(|| {
@@ -23,14 +40,31 @@ async fn _add_server(
     server.create(db_connection)
 }
 
-#[tauri::command]
+#[tauri::command(rename_all = "snake_case")]
+#[allow(clippy::too_many_arguments)]
 pub async fn add_server(
     state: State<'_, Mutex<AppState>>,
     name: String,
     address: String,
     port: u16,
+    read_only: Option<bool>,
+    sentinel: Option<SentinelSettings>,
+    cluster: Option<bool>,
+    ssh_tunnel: Option<SshTunnelSettings>,
+    connection_kind: Option<ConnectionKind>,
 ) -> Result<Server, AppError> {
-    _add_server(state.inner(), name, address, port).await
+    _add_server(
+        state.inner(),
+        name,
+        address,
+        port,
+        read_only,
+        sentinel,
+        cluster,
+        ssh_tunnel,
+        connection_kind,
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -60,6 +94,11 @@ mod tests {
             test_server.name.clone(),
             test_server.address.clone(),
             test_server.port,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
         )
         .await;
 
@@ -68,6 +107,7 @@ mod tests {
         assert_eq!(server.name, test_server.name);
         assert_eq!(server.address, test_server.address);
         assert_eq!(server.port, test_server.port);
+        assert!(server.read_only);
         assert_ne!(server, test_server);
 
         container.rm().await.unwrap();
@@ -86,6 +126,11 @@ mod tests {
             "Bad Server".into(),
             "256.256.256.256".into(),
             6379,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .await;
 
@@ -93,14 +138,80 @@ mod tests {
         assert_eq!(result.err().unwrap(), AppError::RedisFailed);
     }
 
+    #[tokio::test]
+    async fn test_add_sentinel_server_without_sentinels() {
+        let app_state = Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(Database::new_in_memory().unwrap()));
+            s
+        });
+
+        let sentinel = SentinelSettings {
+            master_name: "mymaster".to_string(),
+            sentinels: vec![],
+            ..SentinelSettings::default()
+        };
+        let result = _add_server(
+            &app_state,
+            "Sentinel".into(),
+            String::new(),
+            0,
+            None,
+            Some(sentinel),
+            None,
+            None,
+            None,
+        )
+        .await;
+
+        assert_eq!(result.err().unwrap(), AppError::RedisFailed);
+    }
+
     #[tokio::test]
     async fn test_add_server_no_db_connection() {
         let (host, port, container) = run_redis_container(6379).await;
         let app_state = Mutex::new(AppState::new());
-        let server = _add_server(&app_state, "Test Server".into(), host, port).await;
+        let server = _add_server(
+            &app_state,
+            "Test Server".into(),
+            host,
+            port,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
 
         assert!(server.is_err());
         assert_eq!(server.err().unwrap(), AppError::DbNotReady);
         container.rm().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_add_server_behind_unreachable_bastion() {
+        let app_state = Mutex::new(AppState::new());
+        let ssh_tunnel = SshTunnelSettings {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            user: "deploy".to_string(),
+            password: Some("secret".to_string()),
+            ..SshTunnelSettings::default()
+        };
+        let result = _add_server(
+            &app_state,
+            "Behind Bastion".into(),
+            "10.0.0.5".into(),
+            6379,
+            None,
+            None,
+            None,
+            Some(ssh_tunnel),
+            None,
+        )
+        .await;
+
+        assert_eq!(result.err().unwrap(), AppError::SshFailed);
+    }
 }
@@ -0,0 +1,41 @@
+use crate::core::AppState;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Flags a running job for cancellation. Returns `false` if it already finished.
+async fn _cancel_job(state: &Mutex<AppState>, id: Uuid) -> Result<bool, String> {
+    let app_state = state.lock().await;
+    Ok(app_state.cancel_job(&id))
+}
+
+#[tauri::command]
+pub async fn cancel_job(state: State<'_, Mutex<AppState>>, id: Uuid) -> Result<bool, String> {
+    _cancel_job(state.inner(), id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    #[tokio::test]
+    async fn test_cancel_running_job() {
+        let id = Uuid::new_v4();
+        let app_state = Mutex::new(AppState::new());
+        let flag = app_state.lock().await.register_job(id);
+
+        let result = _cancel_job(&app_state, id).await;
+
+        assert_eq!(result, Ok(true));
+        assert!(flag.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unknown_job() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _cancel_job(&app_state, Uuid::new_v4()).await;
+
+        assert_eq!(result, Ok(false));
+    }
+}
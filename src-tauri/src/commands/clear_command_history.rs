@@ -0,0 +1,53 @@
+use crate::{
+    core::{AppError, AppState},
+    models::CommandHistoryEntry,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _clear_command_history(state: &Mutex<AppState>, server_id: Uuid) -> Result<(), AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    CommandHistoryEntry::clear(&server_id, db_connection)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_command_history(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+) -> Result<(), AppError> {
+    _clear_command_history(state.inner(), server_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, models::Model};
+
+    #[tokio::test]
+    async fn test_clear_command_history_only_clears_one_server() {
+        let db = Database::new_in_memory().unwrap();
+        let (cleared, kept) = (Uuid::now_v7(), Uuid::now_v7());
+        for server_id in [cleared, kept] {
+            CommandHistoryEntry::from_payload(server_id, "PING".to_string(), 10, true)
+                .record(&db)
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        assert!(_clear_command_history(&app_state, cleared).await.is_ok());
+
+        let state = app_state.lock().await;
+        let remaining = CommandHistoryEntry::get(state.get_db_connection().unwrap()).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].server_id, kept);
+    }
+}
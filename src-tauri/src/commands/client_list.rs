@@ -0,0 +1,87 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_client_list, ClientInfo, CLIENT_NAME},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Lists the server's connections, optionally leaving out the ones opened by this app.
+async fn _client_list(
+    state: &Mutex<AppState>,
+    exclude_own: bool,
+) -> Result<Vec<ClientInfo>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let reply: String = redis::cmd("CLIENT")
+        .arg("LIST")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to list clients: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_client_list(&reply)
+        .into_iter()
+        .filter(|client| !exclude_own || client.name != *CLIENT_NAME)
+        .collect())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn client_list(
+    state: State<'_, Mutex<AppState>>,
+    exclude_own: bool,
+) -> Result<Vec<ClientInfo>, AppError> {
+    _client_list(state.inner(), exclude_own).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_client_list() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let mut other = client.get_multiplexed_async_connection().await.unwrap();
+        let _: () = redis::cmd("CLIENT")
+            .arg("SETNAME")
+            .arg("worker")
+            .query_async(&mut other)
+            .await
+            .unwrap();
+        // Another instance of the app, e.g. run by a colleague.
+        let mut gui = client.get_multiplexed_async_connection().await.unwrap();
+        let _: () = redis::cmd("CLIENT")
+            .arg("SETNAME")
+            .arg("redis-gui-0badc0de")
+            .query_async(&mut gui)
+            .await
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let clients = _client_list(&app_state, false).await.unwrap();
+        assert!(clients.iter().any(|c| c.name == *CLIENT_NAME));
+        let worker = clients.iter().find(|c| c.name == "worker").unwrap();
+        assert_eq!(worker.cmd, "client|setname");
+
+        let clients = _client_list(&app_state, true).await.unwrap();
+        assert!(clients.iter().all(|c| c.name != *CLIENT_NAME));
+        assert!(clients.iter().any(|c| c.name == "worker"));
+        assert!(clients.iter().any(|c| c.name == "redis-gui-0badc0de"));
+
+        container.rm().await.unwrap();
+    }
+}
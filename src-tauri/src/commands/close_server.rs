@@ -8,6 +8,17 @@ async fn _close_server(state: &Mutex<AppState>) -> Result<(), String> {
     if app_state.get_redis_client().is_some() {
         app_state.set_redis_client(None);
     }
+    app_state.set_server(None);
+    app_state.set_cluster(None);
+    app_state.set_command_docs(None);
+    app_state.set_metrics_sampler(None);
+    app_state.set_slowlog_collector(None);
+    app_state.set_monitor(None);
+    app_state.set_pubsub(None);
+    app_state.set_keyspace_watcher(None);
+    app_state.set_console_connection(None);
+    app_state.set_sentinel_watcher(None);
+    app_state.set_ssh_tunnel(None);
 
     Ok(())
 }
@@ -42,5 +53,7 @@ mod tests {
 
         let locked_state = app_state.lock().await;
         assert!(locked_state.get_redis_client().is_none());
+        assert!(locked_state.get_server().is_none());
+        assert!(locked_state.get_metrics_sampler().is_none());
     }
 }
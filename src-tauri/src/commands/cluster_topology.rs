@@ -0,0 +1,44 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{cluster_topology as read_cluster_topology, get_connection, ClusterTopology},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reads the nodes, slot ranges, health and replication of the opened cluster.
+async fn _cluster_topology(state: &Mutex<AppState>) -> Result<ClusterTopology, AppError> {
+    let client = {
+        let state = state.lock().await;
+        if state.get_cluster().is_none() {
+            log::warn!("The opened server is not a cluster");
+            return Err(AppError::InvalidCommand);
+        }
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+
+    let mut connection = get_connection(&client).await?;
+    read_cluster_topology(&mut connection).await
+}
+
+#[tauri::command]
+pub async fn cluster_topology(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<ClusterTopology, AppError> {
+    _cluster_topology(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cluster_topology_without_cluster() {
+        let app_state = Mutex::new(AppState::new());
+
+        let result = _cluster_topology(&app_state).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
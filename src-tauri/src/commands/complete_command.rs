@@ -0,0 +1,327 @@
+use super::get_command_docs::load_command_docs;
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, CommandArgument, CommandSpec},
+    utils::split_command_args,
+};
+use redis::aio::MultiplexedConnection;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Upper bound of the key names suggested while typing.
+const MAX_KEY_SUGGESTIONS: usize = 20;
+/// Number of SCAN round trips spent looking for matching key names.
+const MAX_SCAN_ROUNDS: usize = 10;
+const SCAN_COUNT: usize = 200;
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionKind {
+    Command,
+    Argument,
+    Key,
+    None,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct CompletionItem {
+    /// Replaces the word being typed.
+    value: String,
+    summary: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Completions {
+    kind: CompletionKind,
+    items: Vec<CompletionItem>,
+    /// Usage line of the command being typed.
+    syntax: Option<String>,
+}
+
+/// Splits the input before the cursor into the finished arguments and the word being typed.
+fn split_input(input: &str) -> (Vec<String>, String) {
+    let (done, current) = match input.rfind(char::is_whitespace) {
+        Some(index) => {
+            let whitespace = input[index..].chars().next().unwrap().len_utf8();
+            (&input[..index], &input[index + whitespace..])
+        }
+        None => ("", input),
+    };
+
+    let args = match split_command_args(done) {
+        Some(args) => args
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect(),
+        None => done.split_whitespace().map(str::to_string).collect(),
+    };
+
+    (args, current.to_string())
+}
+
+fn find_command<'a>(commands: &'a [CommandSpec], name: &str) -> Option<&'a CommandSpec> {
+    let name = name.to_lowercase();
+    commands.iter().find(|command| command.name == name)
+}
+
+/// Collects the literal tokens (`EX`, `NX`, `WITHSCORES`...) an argument list accepts.
+fn collect_tokens(arguments: &[CommandArgument], tokens: &mut BTreeSet<String>) {
+    for argument in arguments {
+        if let Some(token) = &argument.token {
+            tokens.insert(token.to_uppercase());
+        }
+        collect_tokens(&argument.arguments, tokens);
+    }
+}
+
+/// Escapes the glob characters of a prefix so it is matched literally by `SCAN MATCH`.
+fn escape_glob(prefix: &str) -> String {
+    let mut escaped = String::with_capacity(prefix.len());
+    for c in prefix.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Looks for key names starting with `prefix`, giving up after a fixed number of SCAN
+/// round trips so completion stays fast on large keyspaces.
+async fn scan_key_names(
+    connection: &mut MultiplexedConnection,
+    prefix: &str,
+) -> Result<Vec<String>, AppError> {
+    let pattern = format!("{}*", escape_glob(prefix));
+    let mut keys = BTreeSet::new();
+    let mut cursor: u64 = 0;
+
+    for _ in 0..MAX_SCAN_ROUNDS {
+        let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(SCAN_COUNT)
+            .query_async(connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to scan key names for completion: {}", e);
+                AppError::RedisFailed
+            })?;
+
+        keys.extend(batch);
+        cursor = next;
+        if cursor == 0 || keys.len() >= MAX_KEY_SUGGESTIONS {
+            break;
+        }
+    }
+
+    Ok(keys.into_iter().take(MAX_KEY_SUGGESTIONS).collect())
+}
+
+async fn _complete_command(
+    state: &Mutex<AppState>,
+    input: String,
+) -> Result<Completions, AppError> {
+    let commands = load_command_docs(state, false).await?;
+    let (args, current) = split_input(&input);
+    let upper = current.to_uppercase();
+
+    let Some(name) = args.first() else {
+        let items = commands
+            .iter()
+            .filter(|command| command.display_name().starts_with(&upper))
+            .map(|command| CompletionItem {
+                value: command.display_name(),
+                summary: command.summary.clone(),
+            })
+            .collect();
+
+        return Ok(Completions {
+            kind: CompletionKind::Command,
+            items,
+            syntax: None,
+        });
+    };
+
+    let Some(mut command) = find_command(&commands, name) else {
+        return Ok(Completions {
+            kind: CompletionKind::None,
+            items: vec![],
+            syntax: None,
+        });
+    };
+
+    if !command.subcommands.is_empty() {
+        match args.get(1) {
+            None => {
+                let items = command
+                    .subcommands
+                    .iter()
+                    .filter_map(|sub| {
+                        let value = sub.display_name().rsplit(' ').next()?.to_string();
+                        value.starts_with(&upper).then(|| CompletionItem {
+                            value,
+                            summary: sub.summary.clone(),
+                        })
+                    })
+                    .collect();
+
+                return Ok(Completions {
+                    kind: CompletionKind::Command,
+                    items,
+                    syntax: Some(command.syntax()),
+                });
+            }
+            Some(sub) => {
+                let full_name = format!("{}|{}", command.name, sub.to_lowercase());
+                if let Some(sub) = find_command(&command.subcommands, &full_name) {
+                    command = sub;
+                }
+            }
+        }
+    }
+
+    let position = args.len() as i64;
+    if command.is_key_position(position, position + 1) {
+        let client = {
+            let state = state.lock().await;
+            state.get_redis_client().cloned().ok_or_else(|| {
+                log::error!("Redis client is not ready");
+                AppError::RedisFailed
+            })?
+        };
+        let mut connection = get_connection(&client).await?;
+        let items = scan_key_names(&mut connection, &current)
+            .await?
+            .into_iter()
+            .map(|value| CompletionItem {
+                value,
+                summary: None,
+            })
+            .collect();
+
+        return Ok(Completions {
+            kind: CompletionKind::Key,
+            items,
+            syntax: Some(command.syntax()),
+        });
+    }
+
+    let mut tokens = BTreeSet::new();
+    if !current.is_empty() {
+        collect_tokens(&command.arguments, &mut tokens);
+    }
+
+    Ok(Completions {
+        kind: CompletionKind::Argument,
+        items: tokens
+            .into_iter()
+            .filter(|token| token.starts_with(&upper))
+            .map(|value| CompletionItem {
+                value,
+                summary: None,
+            })
+            .collect(),
+        syntax: Some(command.syntax()),
+    })
+}
+
+#[tauri::command]
+pub async fn complete_command(
+    state: State<'_, Mutex<AppState>>,
+    input: String,
+) -> Result<Completions, AppError> {
+    _complete_command(state.inner(), input).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+    use redis::AsyncTypedCommands;
+    const PORT: u16 = 6379;
+
+    async fn setup_state(host: String, port: u16) -> Mutex<AppState> {
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        Mutex::new(app_state)
+    }
+
+    #[test]
+    fn test_split_input() {
+        assert_eq!(split_input("GE"), (vec![], "GE".to_string()));
+        assert_eq!(
+            split_input("SET \"a b\" "),
+            (vec!["SET".to_string(), "a b".to_string()], String::new())
+        );
+        assert_eq!(
+            split_input("GET user:"),
+            (vec!["GET".to_string()], "user:".to_string())
+        );
+    }
+
+    #[test]
+    fn test_escape_glob() {
+        assert_eq!(escape_glob("user:[1]*?"), "user:\\[1\\]\\*\\?");
+        assert_eq!(escape_glob("plain"), "plain");
+    }
+
+    #[tokio::test]
+    async fn test_complete_command_names_and_keys() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        {
+            let state = app_state.lock().await;
+            let client = state.get_redis_client().unwrap();
+            let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+            conn.set("user:1", "a").await.unwrap();
+            conn.set("user:2", "b").await.unwrap();
+            conn.set("order:1", "c").await.unwrap();
+        }
+
+        let names = _complete_command(&app_state, "hgeta".to_string())
+            .await
+            .unwrap();
+        assert_eq!(names.kind, CompletionKind::Command);
+        assert_eq!(
+            names
+                .items
+                .iter()
+                .map(|i| i.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["HGETALL"]
+        );
+
+        let subcommands = _complete_command(&app_state, "CONFIG G".to_string())
+            .await
+            .unwrap();
+        assert!(subcommands.items.iter().any(|i| i.value == "GET"));
+
+        let keys = _complete_command(&app_state, "GET user:".to_string())
+            .await
+            .unwrap();
+        assert_eq!(keys.kind, CompletionKind::Key);
+        assert_eq!(keys.syntax.as_deref(), Some("GET key"));
+        assert_eq!(
+            keys.items
+                .iter()
+                .map(|i| i.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["user:1", "user:2"]
+        );
+
+        let tokens = _complete_command(&app_state, "SET user:1 a N".to_string())
+            .await
+            .unwrap();
+        assert_eq!(tokens.kind, CompletionKind::Argument);
+        assert!(tokens.items.iter().any(|i| i.value == "NX"));
+
+        container.rm().await.unwrap();
+    }
+}
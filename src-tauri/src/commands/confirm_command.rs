@@ -0,0 +1,182 @@
+use super::get_command_docs::load_command_docs;
+use crate::{
+    core::{AppError, AppState},
+    models::{CommandGuard, Setting, COMMAND_GUARD_SETTING},
+    services::is_write_command,
+    utils::split_command_args,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Returns the stored guard list, or the default one when it was never changed.
+pub(super) fn load_command_guard(state: &AppState) -> Result<CommandGuard, AppError> {
+    match state.get_db_connection() {
+        Some(db) => Ok(Setting::get_value(COMMAND_GUARD_SETTING, db)?.unwrap_or_default()),
+        None => Ok(CommandGuard::default()),
+    }
+}
+
+/// Checks an argument list before it is sent to the server.
+///
+/// # Errors
+/// * `WriteBlocked` if the opened server is read-only and the command writes.
+/// * `ConfirmationRequired` if the command is guarded and `confirmation` is not a valid token.
+pub(super) async fn guard_command(
+    state: &Mutex<AppState>,
+    args: &[Vec<u8>],
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let name = args
+        .first()
+        .map(|name| String::from_utf8_lossy(name).to_uppercase())
+        .unwrap_or_default();
+
+    let read_only = state.lock().await.get_server().is_some_and(|s| s.read_only);
+    if read_only {
+        let commands = load_command_docs(state, false).await?;
+        if is_write_command(&commands, args) {
+            log::warn!("Blocked {} on a read-only server", name);
+            return Err(AppError::WriteBlocked);
+        }
+    }
+
+    let mut state = state.lock().await;
+    let guard = load_command_guard(&state)?;
+    if let Some(guarded) = guard.matches(args) {
+        let guarded = guarded.trim().to_uppercase();
+        if !confirmation.is_some_and(|token| state.take_confirmation(&token, &guarded)) {
+            log::warn!("{} requires a confirmation token", guarded);
+            return Err(AppError::ConfirmationRequired);
+        }
+    }
+
+    Ok(())
+}
+
+/// Issues a single-use token for the guarded command a command line starts with.
+/// Snippets can be confirmed with their template as long as the command name is not a parameter.
+async fn _confirm_command(state: &Mutex<AppState>, command: String) -> Result<Uuid, AppError> {
+    let args = split_command_args(&command).ok_or_else(|| {
+        log::warn!("Unbalanced quotes in command: {}", command);
+        AppError::InvalidCommand
+    })?;
+
+    let mut state = state.lock().await;
+    let guard = load_command_guard(&state)?;
+    let guarded = guard.matches(&args).ok_or_else(|| {
+        log::warn!("Command does not need a confirmation: {}", command);
+        AppError::InvalidCommand
+    })?;
+
+    Ok(state.issue_confirmation(guarded.trim().to_uppercase()))
+}
+
+#[tauri::command]
+pub async fn confirm_command(
+    state: State<'_, Mutex<AppState>>,
+    command: String,
+) -> Result<Uuid, AppError> {
+    _confirm_command(state.inner(), command).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, models::Server, services::CommandSpec};
+    use std::sync::Arc;
+
+    fn args(line: &str) -> Vec<Vec<u8>> {
+        split_command_args(line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_guarded_command_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+
+        let result = guard_command(&app_state, &args("flushdb"), None).await;
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+
+        let token = _confirm_command(&app_state, "FLUSHDB ASYNC".to_string())
+            .await
+            .unwrap();
+        let result = guard_command(&app_state, &args("FLUSHALL"), Some(token)).await;
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+
+        let token = _confirm_command(&app_state, "FLUSHDB".to_string())
+            .await
+            .unwrap();
+        assert!(guard_command(&app_state, &args("flushdb"), Some(token))
+            .await
+            .is_ok());
+        // Tokens are single-use.
+        let result = guard_command(&app_state, &args("flushdb"), Some(token)).await;
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+
+        assert!(guard_command(&app_state, &args("GET k"), None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_unguarded_command() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _confirm_command(&app_state, "GET k".to_string()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+
+    #[tokio::test]
+    async fn test_guard_list_is_configurable() {
+        let db = Database::new_in_memory().unwrap();
+        let guard = CommandGuard {
+            commands: vec!["DEL".to_string()],
+        };
+        Setting::set_value(COMMAND_GUARD_SETTING, &guard, &db).unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        let result = guard_command(&app_state, &args("DEL k"), None).await;
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+        assert!(guard_command(&app_state, &args("FLUSHALL"), None)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_read_only_server_blocks_writes() {
+        let mut app_state = AppState::new();
+        app_state.set_server(Some(Server {
+            read_only: true,
+            ..Server::default()
+        }));
+        app_state.set_command_docs(Some(Arc::new(vec![
+            CommandSpec {
+                name: "get".to_string(),
+                flags: vec!["readonly".to_string()],
+                ..CommandSpec::default()
+            },
+            CommandSpec {
+                name: "set".to_string(),
+                flags: vec!["write".to_string()],
+                ..CommandSpec::default()
+            },
+        ])));
+        let app_state = Mutex::new(app_state);
+
+        assert!(guard_command(&app_state, &args("GET k"), None)
+            .await
+            .is_ok());
+        let result = guard_command(&app_state, &args("SET k v"), None).await;
+        assert_eq!(result.unwrap_err(), AppError::WriteBlocked);
+
+        // Confirming a guarded command does not lift the read-only flag.
+        let token = _confirm_command(&app_state, "FLUSHALL".to_string())
+            .await
+            .unwrap();
+        let result = guard_command(&app_state, &args("FLUSHALL"), Some(token)).await;
+        assert_eq!(result.unwrap_err(), AppError::WriteBlocked);
+    }
+}
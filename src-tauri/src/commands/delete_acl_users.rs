@@ -0,0 +1,97 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Deletes users and closes their connections, returns how many existed. `ACL DELUSER`
+/// is guarded by default, so a token from `confirm_command` is usually required.
+async fn _delete_acl_users(
+    state: &Mutex<AppState>,
+    usernames: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<u64, AppError> {
+    if usernames.is_empty() {
+        return Err(AppError::InvalidCommand);
+    }
+    let mut args = vec![b"ACL".to_vec(), b"DELUSER".to_vec()];
+    args.extend(usernames.iter().map(|name| name.as_bytes().to_vec()));
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("ACL")
+        .arg("DELUSER")
+        .arg(&usernames)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to delete ACL users {:?}: {}", usernames, e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn delete_acl_users(
+    state: State<'_, Mutex<AppState>>,
+    usernames: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<u64, AppError> {
+    _delete_acl_users(state.inner(), usernames, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_delete_acl_users_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _delete_acl_users(&app_state, vec!["reader".to_string()], None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_delete_acl_users() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: () = redis::cmd("ACL")
+                .arg("SETUSER")
+                .arg("reader")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(client));
+        let token = app_state.issue_confirmation("ACL DELUSER".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let usernames = vec!["reader".to_string(), "nobody".to_string()];
+        let deleted = _delete_acl_users(&app_state, usernames, Some(token))
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+use super::execute_command::{run_command, CommandReply};
+use crate::core::{AppError, AppState};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _delete_function(
+    state: &Mutex<AppState>,
+    library_name: String,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    let args = vec![
+        b"FUNCTION".to_vec(),
+        b"DELETE".to_vec(),
+        library_name.into_bytes(),
+    ];
+
+    run_command(state, &args, confirmation).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_function(
+    state: State<'_, Mutex<AppState>>,
+    library_name: String,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _delete_function(state.inner(), library_name, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::Server, services::test_connection, tests::run_redis_container, utils::RespValue,
+    };
+
+    #[tokio::test]
+    async fn test_delete_function() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: String = redis::cmd("FUNCTION")
+                .arg("LOAD")
+                .arg("#!lua name=mylib\nredis.register_function('hello', function() return 'hi' end)")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let result = _delete_function(&app_state, "mylib".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::SimpleString("OK".to_string()));
+
+        let result = _delete_function(&app_state, "mylib".to_string(), None)
+            .await
+            .unwrap();
+        assert!(matches!(result.reply, RespValue::Error(_)));
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Snippet},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _delete_snippet(state: &Mutex<AppState>, id: Uuid) -> Result<bool, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Snippet::find_by_id(&id.to_string(), db_connection)?.delete(db_connection)
+}
+
+#[tauri::command]
+pub async fn delete_snippet(state: State<'_, Mutex<AppState>>, id: Uuid) -> Result<bool, AppError> {
+    _delete_snippet(state.inner(), id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_delete_snippet() {
+        let db = Database::new_in_memory().unwrap();
+        let snippet = Snippet::from_payload(Uuid::now_v7(), "Ping".into(), "PING".into())
+            .create(&db)
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        assert_eq!(_delete_snippet(&app_state, snippet.id).await, Ok(true));
+        assert_eq!(
+            _delete_snippet(&app_state, snippet.id).await,
+            Err(AppError::DbQueryFailed)
+        );
+    }
+}
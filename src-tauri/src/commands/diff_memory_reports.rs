@@ -0,0 +1,101 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{MemoryReport, Model},
+    services::{diff_prefixes, PrefixDiff},
+};
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryReportDiff {
+    key_count_delta: i64,
+    total_memory_delta: i64,
+    /// Prefixes that changed, biggest memory change first.
+    prefixes: Vec<PrefixDiff>,
+}
+
+/// Compares two stored reports, deltas are `after` minus `before`.
+/// Reports taken with a different prefix depth or separator give meaningless prefix deltas.
+async fn _diff_memory_reports(
+    state: &Mutex<AppState>,
+    before_id: Uuid,
+    after_id: Uuid,
+) -> Result<MemoryReportDiff, AppError> {
+    let state = state.lock().await;
+    let db = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    let before = MemoryReport::find_by_id(&before_id.to_string(), db)?;
+    let after = MemoryReport::find_by_id(&after_id.to_string(), db)?;
+    if (before.prefix_depth, &before.separator) != (after.prefix_depth, &after.separator) {
+        log::warn!(
+            "Comparing reports grouped differently: {} and {}",
+            before,
+            after
+        );
+    }
+
+    Ok(MemoryReportDiff {
+        key_count_delta: after.analysis.key_count as i64 - before.analysis.key_count as i64,
+        total_memory_delta: after.analysis.total_memory as i64
+            - before.analysis.total_memory as i64,
+        prefixes: diff_prefixes(&before.analysis, &after.analysis),
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_memory_reports(
+    state: State<'_, Mutex<AppState>>,
+    before_id: Uuid,
+    after_id: Uuid,
+) -> Result<MemoryReportDiff, AppError> {
+    _diff_memory_reports(state.inner(), before_id, after_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database,
+        services::{memory_analysis::PrefixStats, MemoryAnalysis},
+    };
+
+    fn report(keys: u64, memory: u64) -> MemoryReport {
+        MemoryReport {
+            analysis: MemoryAnalysis {
+                key_count: keys,
+                total_memory: memory,
+                prefixes: vec![PrefixStats {
+                    prefix: "user".to_string(),
+                    keys,
+                    memory,
+                }],
+                ..MemoryAnalysis::default()
+            },
+            ..MemoryReport::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_diff_memory_reports() {
+        let db = Database::new_in_memory().unwrap();
+        let before = report(10, 1000).create(&db).unwrap();
+        let after = report(8, 1200).create(&db).unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        let diff = _diff_memory_reports(&app_state, before.id, after.id)
+            .await
+            .unwrap();
+        assert_eq!(diff.key_count_delta, -2);
+        assert_eq!(diff.total_memory_delta, 200);
+        assert_eq!(diff.prefixes.len(), 1);
+        assert_eq!(diff.prefixes[0].memory_delta(), 200);
+    }
+}
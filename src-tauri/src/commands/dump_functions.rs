@@ -0,0 +1,74 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use std::path::PathBuf;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Writes the serialized payload of every function library to a file.
+/// Returns the number of bytes written.
+async fn _dump_functions(state: &Mutex<AppState>, path: PathBuf) -> Result<usize, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let payload: Vec<u8> = redis::cmd("FUNCTION")
+        .arg("DUMP")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to dump functions: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    std::fs::write(&path, &payload).map_err(|e| {
+        log::error!("Failed to write functions to {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+
+    Ok(payload.len())
+}
+
+#[tauri::command]
+pub async fn dump_functions(
+    state: State<'_, Mutex<AppState>>,
+    path: PathBuf,
+) -> Result<usize, AppError> {
+    _dump_functions(state.inner(), path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_dump_functions_to_file() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let path = std::env::temp_dir().join(format!("functions-{}.rdb", uuid::Uuid::new_v4()));
+        let written = _dump_functions(&app_state, path.clone()).await.unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len() as usize, written);
+
+        std::fs::remove_file(path).unwrap();
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dump_functions_no_redis_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _dump_functions(&app_state, PathBuf::from("functions.rdb")).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
@@ -0,0 +1,218 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    models::Script,
+    services::{resp3_client, ServerConnection},
+    utils::{redis_error_to_resp, redis_to_resp, RespValue},
+};
+use redis::{Cmd, Value as RedisValue};
+use serde::Serialize;
+use std::time::Instant;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct ScriptReply {
+    sha1: String,
+    reply: RespValue,
+    duration_us: u64,
+    /// Line of the script an error reply points at, when the server reports one.
+    error_line: Option<u32>,
+}
+
+/// Extracts the line number from script errors such as
+/// `ERR user_script:3: Script attempted to access nonexistent global variable 'x'`.
+fn script_error_line(message: &str) -> Option<u32> {
+    let (_, rest) = message.split_once("user_script:")?;
+    let digits: String = rest
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Runs a script with `EVALSHA`, sending the body with `EVAL` only when the server does not
+/// have it cached yet. Read-only servers use the `_RO` variants so the script cannot write.
+async fn _eval_script(
+    state: &Mutex<AppState>,
+    body: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<ScriptReply, AppError> {
+    if body.trim().is_empty() {
+        log::warn!("Refusing to run an empty script");
+        return Err(AppError::InvalidCommand);
+    }
+
+    let sha1 = Script::sha1_of(&body);
+    let read_only = state.lock().await.get_server().is_some_and(|s| s.read_only);
+    let (evalsha, eval) = match read_only {
+        true => ("EVALSHA_RO", "EVAL_RO"),
+        false => ("EVALSHA", "EVAL"),
+    };
+
+    let mut command_args: Vec<Vec<u8>> = vec![
+        evalsha.as_bytes().to_vec(),
+        sha1.as_bytes().to_vec(),
+        keys.len().to_string().into_bytes(),
+    ];
+    command_args.extend(keys.into_iter().map(String::into_bytes));
+    command_args.extend(args.into_iter().map(String::into_bytes));
+
+    guard_command(state, &command_args, confirmation).await?;
+
+    let (client, cluster) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.get_cluster().cloned())
+    };
+    // Keyed scripts run on the node serving their keys in cluster mode.
+    let mut connection = ServerConnection::open(&resp3_client(&client)?, cluster.as_ref()).await?;
+
+    let to_cmd = |command_args: &[Vec<u8>]| {
+        let mut cmd = Cmd::new();
+        for arg in command_args {
+            cmd.arg(arg.as_slice());
+        }
+        cmd
+    };
+
+    let started_at = Instant::now();
+    let mut result = to_cmd(&command_args)
+        .query_async::<RedisValue>(&mut connection)
+        .await;
+    if result.as_ref().is_err_and(|e| e.code() == Some("NOSCRIPT")) {
+        log::debug!("Script {} is not cached, sending its body", sha1);
+        command_args[0] = eval.as_bytes().to_vec();
+        command_args[1] = body.into_bytes();
+        result = to_cmd(&command_args)
+            .query_async::<RedisValue>(&mut connection)
+            .await;
+    }
+    let duration_us = started_at.elapsed().as_micros() as u64;
+
+    let reply = match result {
+        Ok(value) => redis_to_resp(value),
+        Err(e) => redis_error_to_resp(&e).ok_or_else(|| {
+            log::error!("Failed to run script {}: {}", sha1, e);
+            AppError::RedisFailed
+        })?,
+    };
+    let error_line = match &reply {
+        RespValue::Error(message) => script_error_line(message),
+        _ => None,
+    };
+
+    Ok(ScriptReply {
+        sha1,
+        reply,
+        duration_us,
+        error_line,
+    })
+}
+
+#[tauri::command]
+pub async fn eval_script(
+    state: State<'_, Mutex<AppState>>,
+    body: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<ScriptReply, AppError> {
+    _eval_script(state.inner(), body, keys, args, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+    const PORT: u16 = 6379;
+
+    async fn setup_state(host: String, port: u16) -> Mutex<AppState> {
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        Mutex::new(app_state)
+    }
+
+    #[test]
+    fn test_script_error_line() {
+        assert_eq!(
+            script_error_line(
+                "ERR user_script:3: Script attempted to access nonexistent global variable 'x' \
+                script: 0a1b, on @user_script:3."
+            ),
+            Some(3)
+        );
+        assert_eq!(
+            script_error_line(
+                "ERR Error compiling script (new function): user_script:1: '=' expected near 'end'"
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            script_error_line("ERR Error running script (call to f_0a1b): @user_script: 12: boom"),
+            Some(12)
+        );
+        assert_eq!(script_error_line("ERR custom failure"), None);
+    }
+
+    #[tokio::test]
+    async fn test_eval_script_with_keys_and_args() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+        let body = "redis.call('SET', KEYS[1], ARGV[1]) return redis.call('GET', KEYS[1])";
+
+        // The first run falls back to EVAL, the second one hits the script cache.
+        for _ in 0..2 {
+            let result = _eval_script(
+                &app_state,
+                body.to_string(),
+                vec!["greeting".to_string()],
+                vec!["hello".to_string()],
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(result.reply, RespValue::BulkString("hello".to_string()));
+            assert_eq!(result.sha1, Script::sha1_of(body));
+            assert_eq!(result.error_line, None);
+        }
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_eval_script_error_line() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        let result = _eval_script(
+            &app_state,
+            "local a = 1\nreturn undefined_global".to_string(),
+            vec![],
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(result.reply, RespValue::Error(_)));
+        assert_eq!(result.error_line, Some(2));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_eval_empty_script() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _eval_script(&app_state, " ".to_string(), vec![], vec![], None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
@@ -0,0 +1,479 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    models::CommandHistoryEntry,
+    services::{get_connection, get_console_connection, resp3_client, set_no_evict},
+    utils::{redis_error_to_resp, redis_to_resp, split_command_args, RespValue},
+};
+use redis::{Cmd, Value as RedisValue};
+use serde::Serialize;
+use std::{sync::atomic::Ordering, time::Instant};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Commands that take over the connection and need a dedicated view instead of the console.
+const CONSOLE_UNSUPPORTED_COMMANDS: &[&str] = &[
+    "SUBSCRIBE",
+    "PSUBSCRIBE",
+    "SSUBSCRIBE",
+    "MONITOR",
+    "SYNC",
+    "PSYNC",
+];
+
+/// Commands bound to a single connection, a cluster connection spreads commands over nodes.
+const CLUSTER_UNSUPPORTED_COMMANDS: &[&str] =
+    &["MULTI", "EXEC", "DISCARD", "WATCH", "UNWATCH", "SELECT"];
+
+/// Rejects commands a cluster connection cannot run, since it may send consecutive commands
+/// to different nodes.
+fn check_cluster_command(args: &[Vec<u8>]) -> Result<(), AppError> {
+    let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+    if CLUSTER_UNSUPPORTED_COMMANDS.contains(&name.as_str()) {
+        log::warn!("Command {} is not supported on a cluster", name);
+        return Err(AppError::InvalidCommand);
+    }
+    Ok(())
+}
+
+/// `CONFIG SET` parameters holding a password.
+const SECRET_CONFIG_PARAMETERS: &[&str] = &[
+    "REQUIREPASS",
+    "MASTERAUTH",
+    "TLS-KEY-FILE-PASS",
+    "TLS-CLIENT-KEY-FILE-PASS",
+];
+
+/// Whether a command line carries a password, such lines are left out of the history
+/// like redis-cli does, e.g. `AUTH`, `HELLO 3 AUTH` or `ACL SETUSER u >pw`.
+fn contains_secret(args: &[Vec<u8>]) -> bool {
+    let args: Vec<String> = args
+        .iter()
+        .map(|arg| String::from_utf8_lossy(arg).to_uppercase())
+        .collect();
+    let has = |token: &str| args.iter().skip(1).any(|arg| arg == token);
+
+    match args.first().map(String::as_str) {
+        Some("AUTH") => true,
+        Some("HELLO") | Some("MIGRATE") => has("AUTH") || has("AUTH2"),
+        Some("ACL") => args.get(1).is_some_and(|sub| sub == "SETUSER"),
+        Some("CONFIG") => {
+            args.get(1).is_some_and(|sub| sub == "SET")
+                && SECRET_CONFIG_PARAMETERS.iter().any(|name| has(name))
+        }
+        Some("SENTINEL") => has("AUTH-PASS"),
+        _ => false,
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommandReply {
+    pub(super) reply: RespValue,
+    pub(super) duration_us: u64,
+}
+
+/// Checks an argument list against the read-only flag and the guard list, then runs it over
+/// RESP3 on a fresh connection. On a cluster, commands go through a cluster connection so
+/// they reach the node serving their keys. Server errors are returned as `RespValue::Error`
+/// so their text reaches the user.
+pub(super) async fn run_command(
+    state: &Mutex<AppState>,
+    args: &[Vec<u8>],
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    run(state, args, confirmation, false).await
+}
+
+/// Runs a command like `run_command`, on the console connection when `console` is set. It is
+/// opened on first use and kept until the server is closed so `MULTI`, `SELECT` or a
+/// blocking call carry over between console lines without reaching other commands.
+async fn run(
+    state: &Mutex<AppState>,
+    args: &[Vec<u8>],
+    confirmation: Option<Uuid>,
+    console: bool,
+) -> Result<CommandReply, AppError> {
+    guard_command(state, args, confirmation).await?;
+
+    // Blocking commands may take a while, so the state is not held while the command runs.
+    let (client, cluster, connection, no_evict) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        let no_evict = state.client_no_evict().load(Ordering::Relaxed);
+        (
+            client,
+            state.get_cluster().cloned(),
+            state.get_console_connection().cloned(),
+            no_evict,
+        )
+    };
+
+    let mut cmd = Cmd::new();
+    for arg in args {
+        cmd.arg(arg.as_slice());
+    }
+
+    let (result, duration_us) = match cluster {
+        Some(cluster) => {
+            check_cluster_command(args)?;
+            let mut connection = cluster.connection();
+            let started_at = Instant::now();
+            let result = cmd.query_async::<RedisValue>(&mut connection).await;
+            (result, started_at.elapsed().as_micros() as u64)
+        }
+        None => {
+            let mut connection = match connection.filter(|_| console) {
+                Some(connection) => connection,
+                None if !console => get_connection(&resp3_client(&client)?).await?,
+                None => {
+                    let mut connection = get_console_connection(&resp3_client(&client)?).await?;
+                    if no_evict {
+                        if let Err(e) = set_no_evict(&mut connection, true).await {
+                            log::warn!("Failed to set NO-EVICT on the console connection: {}", e);
+                        }
+                    }
+                    state
+                        .lock()
+                        .await
+                        .set_console_connection(Some(connection.clone()));
+                    connection
+                }
+            };
+            let started_at = Instant::now();
+            let result = cmd.query_async::<RedisValue>(&mut connection).await;
+            (result, started_at.elapsed().as_micros() as u64)
+        }
+    };
+
+    let reply = match result {
+        Ok(value) => redis_to_resp(value),
+        Err(e) => match redis_error_to_resp(&e) {
+            Some(reply) => reply,
+            None => {
+                log::error!(
+                    "Failed to execute {}: {}",
+                    String::from_utf8_lossy(&args[0]),
+                    e
+                );
+                // The next line reconnects, the connection state is lost anyway.
+                if console {
+                    state.lock().await.set_console_connection(None);
+                }
+                return Err(AppError::RedisFailed);
+            }
+        },
+    };
+
+    Ok(CommandReply { reply, duration_us })
+}
+
+pub(super) async fn _execute_command(
+    state: &Mutex<AppState>,
+    command: String,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    let args = split_command_args(&command).ok_or_else(|| {
+        log::warn!("Unbalanced quotes in command: {}", command);
+        AppError::InvalidCommand
+    })?;
+
+    let name = match args.first() {
+        Some(name) => String::from_utf8_lossy(name).to_uppercase(),
+        None => return Err(AppError::InvalidCommand),
+    };
+
+    if CONSOLE_UNSUPPORTED_COMMANDS.contains(&name.as_str()) {
+        log::warn!("Command {} is not supported from the console", name);
+        return Err(AppError::InvalidCommand);
+    }
+
+    log::debug!("Executing console command: {}", name);
+    let CommandReply { reply, duration_us } = run(state, &args, confirmation, true).await?;
+
+    let state = state.lock().await;
+    if contains_secret(&args) {
+        log::debug!("{} carries a password and is not recorded in history", name);
+    } else if let (Some(server), Some(db)) = (state.get_server(), state.get_db_connection()) {
+        let success = !matches!(reply, RespValue::Error(_));
+        let entry =
+            CommandHistoryEntry::from_payload(server.id, command, duration_us as i64, success);
+        if let Err(e) = entry.record(db) {
+            log::error!("Failed to record {} in command history: {}", entry, e);
+        }
+    }
+
+    Ok(CommandReply { reply, duration_us })
+}
+
+/// Guarded commands need a token from `confirm_command` in `confirmation`.
+#[tauri::command]
+pub async fn execute_command(
+    state: State<'_, Mutex<AppState>>,
+    command: String,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _execute_command(state.inner(), command, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database,
+        models::{Model, Server},
+        services::{test_connection, RedisCluster},
+        tests::{run_redis_cluster_container, run_redis_container},
+    };
+    const PORT: u16 = 6379;
+
+    async fn setup_state(host: String, port: u16) -> Mutex<AppState> {
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        Mutex::new(app_state)
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_records_history() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host.clone(), port).await;
+        let server = {
+            let mut state = app_state.lock().await;
+            let db = Database::new_in_memory().unwrap();
+            let server = Server::from_payload("Local Server".to_string(), host, port)
+                .create(&db)
+                .unwrap();
+            state.set_db_connection(Some(db));
+            state.set_server(Some(server.clone()));
+            server
+        };
+
+        let _ = _execute_command(&app_state, "PING".to_string(), None)
+            .await
+            .unwrap();
+        let _ = _execute_command(&app_state, "NOTACOMMAND".to_string(), None)
+            .await
+            .unwrap();
+        let _ = _execute_command(&app_state, "AUTH default hunter2".to_string(), None)
+            .await
+            .unwrap();
+
+        let state = app_state.lock().await;
+        let (entries, total) =
+            CommandHistoryEntry::search(&server.id, "", 10, 0, state.get_db_connection().unwrap())
+                .unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(entries[0].command, "NOTACOMMAND");
+        assert!(!entries[0].success);
+        assert_eq!(entries[1].command, "PING");
+        assert!(entries[1].success);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_typed_replies() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        let result = _execute_command(
+            &app_state,
+            r#"SET greeting "hello world""#.to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.reply, RespValue::SimpleString("OK".to_string()));
+
+        let result = _execute_command(&app_state, "GET greeting".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.reply,
+            RespValue::BulkString("hello world".to_string())
+        );
+
+        let result = _execute_command(&app_state, "HSET h f v".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::Integer(1));
+
+        let result = _execute_command(&app_state, "HGETALL h".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.reply,
+            RespValue::Map(vec![(
+                RespValue::BulkString("f".to_string()),
+                RespValue::BulkString("v".to_string())
+            )])
+        );
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_error_reply() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        let _ = _execute_command(&app_state, "SET k v".to_string(), None).await;
+        let result = _execute_command(&app_state, "LPUSH k item".to_string(), None)
+            .await
+            .unwrap();
+
+        match result.reply {
+            RespValue::Error(message) => assert!(message.starts_with("WRONGTYPE")),
+            other => panic!("Expected an error reply, got {:?}", other),
+        }
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_blocking_command() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        // Blocks longer than the default response timeout of the client.
+        let result = _execute_command(&app_state, "BLPOP missing 1".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::Nil);
+        assert!(result.duration_us >= 1_000_000);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_keeps_connection_state() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        for line in ["SELECT 2", "MULTI", "SET k v"] {
+            let _ = _execute_command(&app_state, line.to_string(), None)
+                .await
+                .unwrap();
+        }
+        let result = _execute_command(&app_state, "EXEC".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.reply,
+            RespValue::Array(vec![RespValue::SimpleString("OK".to_string())])
+        );
+        let result = _execute_command(&app_state, "GET k".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("v".to_string()));
+
+        // A new console connection starts over on database 0.
+        app_state.lock().await.set_console_connection(None);
+        let result = _execute_command(&app_state, "GET k".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::Nil);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_command_ignores_console_state() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        for line in ["SET k v", "SELECT 2", "MULTI"] {
+            let _ = _execute_command(&app_state, line.to_string(), None)
+                .await
+                .unwrap();
+        }
+        let result = run_command(&app_state, &[b"GET".to_vec(), b"k".to_vec()], None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("v".to_string()));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_command_line() {
+        let app_state = Mutex::new(AppState::new());
+
+        let result = _execute_command(&app_state, r#"SET k "unterminated"#.to_string(), None).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+
+        let result = _execute_command(&app_state, "   ".to_string(), None).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+
+        let result = _execute_command(&app_state, "monitor".to_string(), None).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+
+    #[test]
+    fn test_contains_secret() {
+        let secret = |line: &str| contains_secret(&split_command_args(line).unwrap());
+
+        assert!(secret("auth hunter2"));
+        assert!(secret("HELLO 3 AUTH app hunter2"));
+        assert!(secret("ACL SETUSER app on >hunter2"));
+        assert!(secret("CONFIG SET maxmemory 1gb requirepass hunter2"));
+        assert!(secret("MIGRATE 10.0.0.2 6379 k 0 5000 AUTH hunter2"));
+        assert!(secret("SENTINEL SET mymaster auth-pass hunter2"));
+
+        assert!(!secret("HELLO 3"));
+        assert!(!secret("ACL GETUSER app"));
+        assert!(!secret("CONFIG SET maxmemory 1gb"));
+        assert!(!secret("GET auth"));
+    }
+
+    #[test]
+    fn test_check_cluster_command() {
+        let check = |line: &str| check_cluster_command(&split_command_args(line).unwrap());
+
+        assert_eq!(check("multi"), Err(AppError::InvalidCommand));
+        assert_eq!(check("WATCH k"), Err(AppError::InvalidCommand));
+        assert_eq!(check("SELECT 1"), Err(AppError::InvalidCommand));
+        assert_eq!(check("GET k"), Ok(()));
+        assert_eq!(check("EVAL \"return 1\" 0"), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_on_cluster() {
+        let (host, port, container) = run_redis_cluster_container().await;
+        let mut server = Server::from_payload("Local Cluster".to_string(), host, port);
+        server.cluster = true;
+        let app_state = setup_state(server.address.clone(), port).await;
+        app_state
+            .lock()
+            .await
+            .set_cluster(Some(RedisCluster::connect(&server).await.unwrap()));
+
+        let result = _execute_command(&app_state, "SET {user}:1 v".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::SimpleString("OK".to_string()));
+        let result = run_command(&app_state, &[b"GET".to_vec(), b"{user}:1".to_vec()], None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("v".to_string()));
+
+        let result = _execute_command(&app_state, "MULTI".to_string(), None).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+        // Console state is never opened on a cluster.
+        assert!(app_state.lock().await.get_console_connection().is_none());
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_no_redis_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _execute_command(&app_state, "PING".to_string(), None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
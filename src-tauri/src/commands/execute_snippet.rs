@@ -0,0 +1,80 @@
+use super::execute_command::{_execute_command, CommandReply};
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Snippet},
+};
+use std::collections::HashMap;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _execute_snippet(
+    state: &Mutex<AppState>,
+    id: Uuid,
+    values: HashMap<String, String>,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    let snippet = {
+        let state = state.lock().await;
+        let db_connection = state.get_db_connection().ok_or_else(|| {
+            log::error!("Database connection is not ready");
+            AppError::DbNotReady
+        })?;
+        Snippet::find_by_id(&id.to_string(), db_connection)?
+    };
+
+    let command = snippet.render(&values).ok_or_else(|| {
+        log::warn!("Missing parameter values for {}", snippet);
+        AppError::InvalidCommand
+    })?;
+
+    _execute_command(state, command, confirmation).await
+}
+
+#[tauri::command]
+pub async fn execute_snippet(
+    state: State<'_, Mutex<AppState>>,
+    id: Uuid,
+    values: HashMap<String, String>,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _execute_snippet(state.inner(), id, values, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+        utils::RespValue,
+    };
+
+    #[tokio::test]
+    async fn test_execute_snippet_with_parameters() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let db = Database::new_in_memory().unwrap();
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let snippet = Snippet::from_payload(server.id, "Echo".into(), "ECHO {{message}}".into())
+            .create(&db)
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        let values = HashMap::from([("message".to_string(), "hello world".to_string())]);
+        let result = _execute_snippet(&app_state, snippet.id, values, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            result.reply,
+            RespValue::BulkString("hello world".to_string())
+        );
+
+        let result = _execute_snippet(&app_state, snippet.id, HashMap::new(), None).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Server, ServerBundle},
+};
+use std::path::PathBuf;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Writes the saved servers, or only `ids`, to a versioned JSON bundle. Passwords and
+/// passphrases are left out unless `include_secrets` is set.
+/// Returns the number of servers written.
+async fn _export_servers(
+    state: &Mutex<AppState>,
+    path: PathBuf,
+    ids: Option<Vec<Uuid>>,
+    include_secrets: bool,
+) -> Result<usize, AppError> {
+    let servers = {
+        let state = state.lock().await;
+        let db_connection = state.get_db_connection().ok_or_else(|| {
+            log::error!("Database connection is not ready");
+            AppError::DbNotReady
+        })?;
+        Server::get(db_connection)?
+    };
+
+    let servers: Vec<Server> = servers
+        .into_iter()
+        .filter(|server| ids.as_ref().is_none_or(|ids| ids.contains(&server.id)))
+        .map(|server| match include_secrets {
+            true => server,
+            false => server.without_secrets(),
+        })
+        .collect();
+    let count = servers.len();
+
+    let contents = serde_json::to_string_pretty(&ServerBundle::new(servers)).map_err(|e| {
+        log::error!("Failed to serialize servers: {}", e);
+        AppError::FileFailed
+    })?;
+    std::fs::write(&path, contents).map_err(|e| {
+        log::error!("Failed to write servers to {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+
+    Ok(count)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_servers(
+    state: State<'_, Mutex<AppState>>,
+    path: PathBuf,
+    ids: Option<Vec<Uuid>>,
+    include_secrets: bool,
+) -> Result<usize, AppError> {
+    _export_servers(state.inner(), path, ids, include_secrets).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_export_servers_without_secrets() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server {
+            password: Some("secret".to_string()),
+            ..Server::from_payload("Primary".to_string(), "10.0.0.1".to_string(), 6379)
+        }
+        .create(&db)
+        .unwrap();
+        Server::from_payload("Other".to_string(), "10.0.0.2".to_string(), 6379)
+            .create(&db)
+            .unwrap();
+        let app_state = Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(db));
+            s
+        });
+
+        let path = std::env::temp_dir().join(format!("servers-{}.json", Uuid::new_v4()));
+        let written = _export_servers(&app_state, path.clone(), Some(vec![server.id]), false)
+            .await
+            .unwrap();
+        assert_eq!(written, 1);
+
+        let bundle: ServerBundle =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(bundle.servers[0].name, "Primary");
+        assert_eq!(bundle.servers[0].password, None);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_servers_no_db_connection() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _export_servers(&app_state, PathBuf::from("servers.json"), None, true).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
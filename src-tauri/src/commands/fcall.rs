@@ -0,0 +1,100 @@
+use super::execute_command::{run_command, CommandReply};
+use crate::core::{AppError, AppState};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Invokes a function with separate keys and arguments. `read_only` calls it with `FCALL_RO`,
+/// which read-only servers always do so functions without the `no-writes` flag are refused.
+async fn _fcall(
+    state: &Mutex<AppState>,
+    function: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    read_only: bool,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    let read_only = read_only || state.lock().await.get_server().is_some_and(|s| s.read_only);
+    let name = if read_only { "FCALL_RO" } else { "FCALL" };
+
+    let mut command_args = vec![
+        name.as_bytes().to_vec(),
+        function.into_bytes(),
+        keys.len().to_string().into_bytes(),
+    ];
+    command_args.extend(keys.into_iter().map(String::into_bytes));
+    command_args.extend(args.into_iter().map(String::into_bytes));
+
+    run_command(state, &command_args, confirmation).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn fcall(
+    state: State<'_, Mutex<AppState>>,
+    function: String,
+    keys: Vec<String>,
+    args: Vec<String>,
+    read_only: bool,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _fcall(state.inner(), function, keys, args, read_only, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::Server, services::test_connection, tests::run_redis_container, utils::RespValue,
+    };
+
+    #[tokio::test]
+    async fn test_fcall_with_keys_and_args() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: String = redis::cmd("FUNCTION")
+                .arg("LOAD")
+                .arg(
+                    "#!lua name=mylib\n\
+                    redis.register_function('setget', function(keys, args) \
+                    redis.call('SET', keys[1], args[1]) return redis.call('GET', keys[1]) end)",
+                )
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let result = _fcall(
+            &app_state,
+            "setget".to_string(),
+            vec!["greeting".to_string()],
+            vec!["hello".to_string()],
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("hello".to_string()));
+
+        // The function writes without declaring `no-writes`, so FCALL_RO refuses it.
+        let result = _fcall(
+            &app_state,
+            "setget".to_string(),
+            vec!["greeting".to_string()],
+            vec!["hello".to_string()],
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(result.reply, RespValue::Error(_)));
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::ServerConnection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Empties the server's script cache. `SCRIPT FLUSH` is guarded by default, so a token from
+/// `confirm_command` is usually required.
+async fn _flush_scripts(
+    state: &Mutex<AppState>,
+    asynchronous: bool,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let mode = if asynchronous { "ASYNC" } else { "SYNC" };
+    let args = vec![
+        b"SCRIPT".to_vec(),
+        b"FLUSH".to_vec(),
+        mode.as_bytes().to_vec(),
+    ];
+    guard_command(state, &args, confirmation).await?;
+
+    let (client, cluster) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.get_cluster().cloned())
+    };
+    let mut connection = ServerConnection::open(&client, cluster.as_ref()).await?;
+
+    redis::cmd("SCRIPT")
+        .arg("FLUSH")
+        .arg(mode)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to flush the script cache: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn flush_scripts(
+    state: State<'_, Mutex<AppState>>,
+    asynchronous: bool,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _flush_scripts(state.inner(), asynchronous, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_flush_scripts_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _flush_scripts(&app_state, false, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+}
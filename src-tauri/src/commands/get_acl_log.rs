@@ -0,0 +1,75 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_acl_log, AclLogEntry},
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reads the latest `count` denied commands and authentications, the server's default
+/// of 10 when not given.
+async fn _get_acl_log(
+    state: &Mutex<AppState>,
+    count: Option<u32>,
+) -> Result<Vec<AclLogEntry>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let reply: RedisValue = redis::cmd("ACL")
+        .arg("LOG")
+        .arg(count)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get the ACL log: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_acl_log(&reply))
+}
+
+#[tauri::command]
+pub async fn get_acl_log(
+    state: State<'_, Mutex<AppState>>,
+    count: Option<u32>,
+) -> Result<Vec<AclLogEntry>, AppError> {
+    _get_acl_log(state.inner(), count).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_get_acl_log() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let denied: redis::RedisResult<()> = redis::cmd("AUTH")
+                .arg("nobody")
+                .arg("wrong")
+                .query_async(&mut connection)
+                .await;
+            assert!(denied.is_err());
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let entries = _get_acl_log(&app_state, Some(5)).await.unwrap();
+        assert_eq!(entries[0].reason, "auth");
+        assert_eq!(entries[0].username, "nobody");
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_acl_user, AclUser},
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reads a user's flags, password hashes and permissions, `None` when it does not exist.
+async fn _get_acl_user(
+    state: &Mutex<AppState>,
+    username: String,
+) -> Result<Option<AclUser>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let reply: RedisValue = redis::cmd("ACL")
+        .arg("GETUSER")
+        .arg(&username)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get ACL user {}: {}", username, e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_acl_user(&username, &reply))
+}
+
+#[tauri::command]
+pub async fn get_acl_user(
+    state: State<'_, Mutex<AppState>>,
+    username: String,
+) -> Result<Option<AclUser>, AppError> {
+    _get_acl_user(state.inner(), username).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_get_acl_user() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let user = _get_acl_user(&app_state, "default".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(user.enabled);
+        assert!(user.nopass);
+        assert_eq!(user.categories, vec!["+@all"]);
+
+        let missing = _get_acl_user(&app_state, "nobody".to_string())
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+
+        container.rm().await.unwrap();
+    }
+}
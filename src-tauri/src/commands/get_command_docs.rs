@@ -0,0 +1,113 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{CommandDocsCache, Model},
+    services::{fetch_command_docs, get_connection, server_version_key, CommandSpec},
+};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Returns the commands of the opened server, loading them from the SQLite cache of its
+/// version or fetching `COMMAND` and `COMMAND DOCS` when the version was never seen.
+/// `refresh` bypasses both caches, e.g. after a module was loaded.
+pub(super) async fn load_command_docs(
+    state: &Mutex<AppState>,
+    refresh: bool,
+) -> Result<Arc<Vec<CommandSpec>>, AppError> {
+    let mut state = state.lock().await;
+    if !refresh {
+        if let Some(command_docs) = state.get_command_docs() {
+            return Ok(command_docs);
+        }
+    }
+
+    let client = state.get_redis_client().ok_or_else(|| {
+        log::error!("Redis client is not ready");
+        AppError::RedisFailed
+    })?;
+    let mut connection = get_connection(client).await?;
+    let version = server_version_key(&mut connection).await?;
+
+    let cached = match (refresh, state.get_db_connection()) {
+        (false, Some(db)) => CommandDocsCache::find_by_version(&version, db)?,
+        _ => None,
+    };
+
+    let commands = match cached {
+        Some(cache) => cache.commands,
+        None => {
+            log::debug!("Fetching command docs for {}", version);
+            let commands = fetch_command_docs(&mut connection).await?;
+            if let Some(db) = state.get_db_connection() {
+                let cache = CommandDocsCache::from_payload(version, commands.clone());
+                if let Err(e) = cache.create(db) {
+                    log::error!("Failed to cache {}: {}", cache, e);
+                }
+            }
+            commands
+        }
+    };
+
+    let commands = Arc::new(commands);
+    state.set_command_docs(Some(commands.clone()));
+    Ok(commands)
+}
+
+async fn _get_command_docs(
+    state: &Mutex<AppState>,
+    refresh: bool,
+) -> Result<Vec<CommandSpec>, AppError> {
+    load_command_docs(state, refresh)
+        .await
+        .map(|commands| commands.as_ref().clone())
+}
+
+#[tauri::command]
+pub async fn get_command_docs(
+    state: State<'_, Mutex<AppState>>,
+    refresh: bool,
+) -> Result<Vec<CommandSpec>, AppError> {
+    _get_command_docs(state.inner(), refresh).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+    const PORT: u16 = 6379;
+
+    #[tokio::test]
+    async fn test_get_command_docs_is_cached_per_version() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let commands = _get_command_docs(&app_state, false).await.unwrap();
+        let get = commands.iter().find(|c| c.name == "get").unwrap();
+        assert_eq!(get.syntax(), "GET key");
+        assert!(get.summary.is_some());
+        let config = commands.iter().find(|c| c.name == "config").unwrap();
+        assert!(config.subcommands.iter().any(|c| c.name == "config|get"));
+
+        let state = app_state.lock().await;
+        let caches = CommandDocsCache::get(state.get_db_connection().unwrap()).unwrap();
+        assert_eq!(caches.len(), 1);
+        assert!(caches[0].version.starts_with("redis-8.0.2"));
+        assert_eq!(caches[0].commands, commands);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_command_docs_no_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_command_docs(&app_state, false).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
@@ -0,0 +1,35 @@
+use super::confirm_command::load_command_guard;
+use crate::{
+    core::{AppError, AppState},
+    models::CommandGuard,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn _get_command_guard(state: &Mutex<AppState>) -> Result<CommandGuard, AppError> {
+    let state = state.lock().await;
+    load_command_guard(&state)
+}
+
+#[tauri::command]
+pub async fn get_command_guard(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<CommandGuard, AppError> {
+    _get_command_guard(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_get_default_command_guard() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let guard = _get_command_guard(&app_state).await.unwrap();
+        assert_eq!(guard, CommandGuard::default());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{
+    core::{AppError, AppState},
+    models::CommandHistoryEntry,
+};
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug)]
+pub struct CommandHistoryPage {
+    entries: Vec<CommandHistoryEntry>,
+    total: u32,
+    page: u32,
+    per_page: u32,
+}
+
+async fn _get_command_history(
+    state: &Mutex<AppState>,
+    server_id: Uuid,
+    search: String,
+    page: u32,
+    per_page: u32,
+) -> Result<CommandHistoryPage, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    let page = page.max(1);
+    let per_page = per_page.clamp(1, 500);
+    let (entries, total) = CommandHistoryEntry::search(
+        &server_id,
+        search.trim(),
+        per_page,
+        (page - 1) * per_page,
+        db_connection,
+    )?;
+
+    Ok(CommandHistoryPage {
+        entries,
+        total,
+        page,
+        per_page,
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_command_history(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+    search: String,
+    page: u32,
+    per_page: u32,
+) -> Result<CommandHistoryPage, AppError> {
+    _get_command_history(state.inner(), server_id, search, page, per_page).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    fn setup_state(server_id: Uuid, commands: &[&str]) -> Mutex<AppState> {
+        let db = Database::new_in_memory().unwrap();
+        for command in commands {
+            CommandHistoryEntry::from_payload(server_id, command.to_string(), 10, true)
+                .record(&db)
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        Mutex::new(app_state)
+    }
+
+    #[tokio::test]
+    async fn test_get_command_history_paginates_newest_first() {
+        let server_id = Uuid::now_v7();
+        let app_state = setup_state(server_id, &["GET a", "GET b", "GET c"]);
+
+        let page = _get_command_history(&app_state, server_id, "".into(), 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.entries.len(), 2);
+        assert_eq!(page.entries[0].command, "GET c");
+
+        let page = _get_command_history(&app_state, server_id, "".into(), 2, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].command, "GET a");
+    }
+
+    #[tokio::test]
+    async fn test_get_command_history_search() {
+        let server_id = Uuid::now_v7();
+        let app_state = setup_state(server_id, &["HGETALL user:1", "GET a", "hget user:2 f"]);
+
+        let page = _get_command_history(&app_state, server_id, "HGET".into(), 1, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 2);
+
+        let page = _get_command_history(&app_state, Uuid::now_v7(), "".into(), 1, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_command_history_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_command_history(&app_state, Uuid::now_v7(), "".into(), 1, 10).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
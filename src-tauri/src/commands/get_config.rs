@@ -0,0 +1,90 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_config, server_major_version, ConfigParameter},
+};
+use redis::Value as RedisValue;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ServerConfig {
+    /// Major version the defaults were picked for, `0` when unknown.
+    major_version: u32,
+    parameters: BTreeMap<String, ConfigParameter>,
+}
+
+/// Reads every parameter matching `pattern`, `*` when empty.
+async fn _get_config(state: &Mutex<AppState>, pattern: String) -> Result<ServerConfig, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+    let major_version = server_major_version(&mut connection).await?;
+
+    let pattern = match pattern.is_empty() {
+        true => "*".to_string(),
+        false => pattern,
+    };
+    let reply: RedisValue = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg(&pattern)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get the config matching {}: {}", pattern, e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(ServerConfig {
+        major_version,
+        parameters: parse_config(&reply, major_version),
+    })
+}
+
+#[tauri::command]
+pub async fn get_config(
+    state: State<'_, Mutex<AppState>>,
+    pattern: String,
+) -> Result<ServerConfig, AppError> {
+    _get_config(state.inner(), pattern).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_get_config_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_config(&app_state, String::new()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_get_config() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let config = _get_config(&app_state, String::new()).await.unwrap();
+        assert_eq!(config.major_version, 8);
+        let policy = &config.parameters["maxmemory-policy"];
+        assert_eq!(policy.value, "noeviction");
+        assert!(!policy.modified);
+
+        let config = _get_config(&app_state, "hz".to_string()).await.unwrap();
+        assert_eq!(config.parameters.len(), 1);
+
+        container.rm().await.unwrap();
+    }
+}
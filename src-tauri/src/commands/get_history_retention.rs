@@ -0,0 +1,39 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{HistoryRetention, Setting, HISTORY_RETENTION_SETTING},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn _get_history_retention(state: &Mutex<AppState>) -> Result<HistoryRetention, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Ok(Setting::get_value(HISTORY_RETENTION_SETTING, db_connection)?.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn get_history_retention(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<HistoryRetention, AppError> {
+    _get_history_retention(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_get_default_history_retention() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let retention = _get_history_retention(&app_state).await.unwrap();
+        assert_eq!(retention, HistoryRetention::default());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{
+    core::{AppError, AppState},
+    models::MemoryReport,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Returns the stored memory reports of a server, newest first.
+async fn _get_memory_reports(
+    state: &Mutex<AppState>,
+    server_id: Uuid,
+) -> Result<Vec<MemoryReport>, AppError> {
+    let state = state.lock().await;
+    let db = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    MemoryReport::get_for_server(&server_id, db)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_memory_reports(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+) -> Result<Vec<MemoryReport>, AppError> {
+    _get_memory_reports(state.inner(), server_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_memory_reports_no_db_connection() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_memory_reports(&app_state, Uuid::now_v7()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
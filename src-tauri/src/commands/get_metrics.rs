@@ -0,0 +1,153 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{MetricSample, MetricsSettings, Model, Setting, METRICS_SETTINGS_SETTING},
+};
+use tauri::State;
+use time::{Duration, OffsetDateTime};
+use tokio::sync::Mutex;
+
+/// Returns the stored sampler settings, or the default ones when they were never changed.
+pub(super) fn load_metrics_settings(state: &AppState) -> Result<MetricsSettings, AppError> {
+    match state.get_db_connection() {
+        Some(db) => Ok(Setting::get_value(METRICS_SETTINGS_SETTING, db)?.unwrap_or_default()),
+        None => Ok(MetricsSettings::default()),
+    }
+}
+
+/// Writes a sample to the database when persistence is enabled.
+/// Failures are logged since the sample is still kept in memory.
+pub(super) async fn record_sample(state: &Mutex<AppState>, sample: &MetricSample) {
+    let state = state.lock().await;
+    let Some(db) = state.get_db_connection() else {
+        return;
+    };
+
+    match load_metrics_settings(&state) {
+        Ok(settings) if settings.persist => {
+            if let Err(e) = sample.save(db) {
+                log::error!("Failed to persist {}: {}", sample, e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("Failed to read metrics settings: {}", e),
+    }
+}
+
+/// Returns the samples of the opened server taken in the last `range` seconds, oldest first.
+/// Persisted samples are used when persistence is enabled so the range can reach
+/// further back than the in-memory buffer.
+async fn _get_metrics(state: &Mutex<AppState>, range: u32) -> Result<Vec<MetricSample>, AppError> {
+    let state = state.lock().await;
+    let since = OffsetDateTime::now_utc() - Duration::seconds(range as i64);
+
+    let settings = load_metrics_settings(&state)?;
+    if let (true, Some(db), Some(server)) = (
+        settings.persist,
+        state.get_db_connection(),
+        state.get_server(),
+    ) {
+        return MetricSample::find_since(&server.id, since, db);
+    }
+
+    let sampler = state.get_metrics_sampler().ok_or_else(|| {
+        log::error!("Metrics sampler is not running");
+        AppError::RedisFailed
+    })?;
+    Ok(sampler.samples_since(since).await)
+}
+
+#[tauri::command]
+pub async fn get_metrics(
+    state: State<'_, Mutex<AppState>>,
+    range: u32,
+) -> Result<Vec<MetricSample>, AppError> {
+    _get_metrics(state.inner(), range).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database,
+        models::Server,
+        services::{test_connection, MetricsSampler},
+        tests::run_redis_container,
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_get_metrics_without_sampler() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_metrics(&app_state, 60).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_from_persisted_samples() {
+        let db = Database::new_in_memory().unwrap();
+        Setting::set_value(
+            METRICS_SETTINGS_SETTING,
+            &MetricsSettings {
+                persist: true,
+                ..MetricsSettings::default()
+            },
+            &db,
+        )
+        .unwrap();
+        let server = Server::default();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        app_state.set_server(Some(server.clone()));
+        let app_state = Mutex::new(app_state);
+
+        for minutes in [1, 30] {
+            let sample = MetricSample {
+                id: uuid::Uuid::now_v7(),
+                server_id: server.id,
+                sampled_at: OffsetDateTime::now_utc() - Duration::minutes(minutes),
+                ..MetricSample::default()
+            };
+            record_sample(&app_state, &sample).await;
+        }
+
+        assert_eq!(_get_metrics(&app_state, 300).await.unwrap().len(), 1);
+        assert_eq!(_get_metrics(&app_state, 3600).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics_from_sampler() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let settings = MetricsSettings {
+            interval_secs: 1,
+            ..MetricsSettings::default()
+        };
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let sampler = MetricsSampler::start(
+            client,
+            server.id,
+            &settings,
+            Arc::default(),
+            move |sample| {
+                let _ = sender.send(sample);
+            },
+        );
+
+        let mut app_state = AppState::new();
+        app_state.set_metrics_sampler(Some(sampler));
+        let app_state = Mutex::new(app_state);
+
+        let emitted = receiver.recv().await.unwrap();
+        assert_eq!(emitted.server_id, server.id);
+        assert!(emitted.used_memory > 0);
+
+        let samples = _get_metrics(&app_state, 60).await.unwrap();
+        assert_eq!(samples.first(), Some(&emitted));
+
+        container.rm().await.unwrap();
+    }
+}
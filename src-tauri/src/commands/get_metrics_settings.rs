@@ -0,0 +1,39 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{MetricsSettings, Setting, METRICS_SETTINGS_SETTING},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn _get_metrics_settings(state: &Mutex<AppState>) -> Result<MetricsSettings, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Ok(Setting::get_value(METRICS_SETTINGS_SETTING, db_connection)?.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn get_metrics_settings(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<MetricsSettings, AppError> {
+    _get_metrics_settings(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_get_default_metrics_settings() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let settings = _get_metrics_settings(&app_state).await.unwrap();
+        assert_eq!(settings, MetricsSettings::default());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_slowlog, SlowlogEntry},
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Returns the newest `count` slow log entries, or the server's default of 10 without one.
+async fn _get_slowlog(
+    state: &Mutex<AppState>,
+    count: Option<u32>,
+) -> Result<Vec<SlowlogEntry>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut cmd = redis::cmd("SLOWLOG");
+    cmd.arg("GET");
+    if let Some(count) = count {
+        cmd.arg(count);
+    }
+
+    let reply: RedisValue = cmd.query_async(&mut connection).await.map_err(|e| {
+        log::error!("Failed to get the slow log: {}", e);
+        AppError::RedisFailed
+    })?;
+
+    Ok(parse_slowlog(&reply))
+}
+
+#[tauri::command]
+pub async fn get_slowlog(
+    state: State<'_, Mutex<AppState>>,
+    count: Option<u32>,
+) -> Result<Vec<SlowlogEntry>, AppError> {
+    _get_slowlog(state.inner(), count).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_get_slowlog() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: () = redis::cmd("CONFIG")
+                .arg("SET")
+                .arg("slowlog-log-slower-than")
+                .arg(0)
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+            let _: () = redis::cmd("CLIENT")
+                .arg("SETNAME")
+                .arg("slowlog-test")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+            let _: () = redis::cmd("SET")
+                .arg("slow")
+                .arg("value")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let entries = _get_slowlog(&app_state, Some(128)).await.unwrap();
+        let set = entries
+            .iter()
+            .find(|entry| entry.args.first().map(String::as_str) == Some("SET"))
+            .unwrap();
+        assert_eq!(set.args, vec!["SET", "slow", "value"]);
+        assert_eq!(set.client_name, "slowlog-test");
+        assert!(!set.client_addr.is_empty());
+
+        assert_eq!(_get_slowlog(&app_state, Some(1)).await.unwrap().len(), 1);
+
+        container.rm().await.unwrap();
+    }
+}
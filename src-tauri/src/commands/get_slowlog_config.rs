@@ -0,0 +1,63 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_slowlog_config, SlowlogConfig},
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn _get_slowlog_config(state: &Mutex<AppState>) -> Result<SlowlogConfig, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let reply: RedisValue = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("slowlog-*")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get the slow log config: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_slowlog_config(&reply))
+}
+
+#[tauri::command]
+pub async fn get_slowlog_config(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<SlowlogConfig, AppError> {
+    _get_slowlog_config(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_get_default_slowlog_config() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let config = _get_slowlog_config(&app_state).await.unwrap();
+        assert_eq!(
+            config,
+            SlowlogConfig {
+                log_slower_than: 10000,
+                max_len: 128
+            }
+        );
+
+        container.rm().await.unwrap();
+    }
+}
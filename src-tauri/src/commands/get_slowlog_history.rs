@@ -0,0 +1,97 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, SlowlogRecord},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Writes captured slow log entries to the database.
+/// Failures are logged since the capture runs in the background.
+pub(super) async fn record_slowlog(state: &Mutex<AppState>, records: &[SlowlogRecord]) {
+    let state = state.lock().await;
+    let Some(db) = state.get_db_connection() else {
+        log::warn!("Database connection is not ready, slow log entries were not captured");
+        return;
+    };
+
+    for record in records {
+        if let Err(e) = record.create(db) {
+            log::error!("Failed to capture {}: {}", record, e);
+        }
+    }
+}
+
+/// Returns a page of the slow log entries captured for a server, newest first.
+async fn _get_slowlog_history(
+    state: &Mutex<AppState>,
+    server_id: Uuid,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<SlowlogRecord>, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    let page = page.max(1);
+    let per_page = per_page.clamp(1, 500);
+    SlowlogRecord::search(&server_id, per_page, (page - 1) * per_page, db_connection)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_slowlog_history(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<SlowlogRecord>, AppError> {
+    _get_slowlog_history(state.inner(), server_id, page, per_page).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, services::SlowlogEntry};
+
+    #[tokio::test]
+    async fn test_recorded_slowlog_is_paginated() {
+        let server_id = Uuid::now_v7();
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let records: Vec<SlowlogRecord> = (0..3)
+            .map(|id| {
+                let entry = SlowlogEntry {
+                    id,
+                    timestamp: 1718030000 + id,
+                    ..SlowlogEntry::default()
+                };
+                SlowlogRecord::from_entry(server_id, &entry)
+            })
+            .collect();
+        record_slowlog(&app_state, &records).await;
+
+        let page = _get_slowlog_history(&app_state, server_id, 1, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.iter().map(|r| r.entry_id).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        let page = _get_slowlog_history(&app_state, server_id, 2, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_slowlog_history_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_slowlog_history(&app_state, Uuid::now_v7(), 1, 10).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{
+    core::{AppError, AppState},
+    models::Snippet,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _get_snippets(state: &Mutex<AppState>, server_id: Uuid) -> Result<Vec<Snippet>, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Snippet::get_for_server(&server_id, db_connection)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_snippets(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+) -> Result<Vec<Snippet>, AppError> {
+    _get_snippets(state.inner(), server_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, models::Model};
+
+    #[tokio::test]
+    async fn test_get_snippets_for_server() {
+        let db = Database::new_in_memory().unwrap();
+        let server_id = Uuid::now_v7();
+        let snippet = Snippet::from_payload(server_id, "Ping".into(), "PING".into())
+            .create(&db)
+            .unwrap();
+        Snippet::from_payload(Uuid::now_v7(), "Other".into(), "INFO".into())
+            .create(&db)
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        let app_state = Mutex::new(app_state);
+
+        let snippets = _get_snippets(&app_state, server_id).await.unwrap();
+        assert_eq!(snippets, vec![snippet]);
+    }
+
+    #[tokio::test]
+    async fn test_get_snippets_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _get_snippets(&app_state, Uuid::now_v7()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
@@ -0,0 +1,73 @@
+use super::import_servers::{import_into, ServerImportResult};
+use crate::{
+    core::{AppError, AppState},
+    services::{parse_connections, ConnectionSource},
+};
+use std::path::PathBuf;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Imports the connections exported by another client, e.g. RedisInsight, with a result
+/// for every entry of the file.
+async fn _import_connections(
+    state: &Mutex<AppState>,
+    path: PathBuf,
+    source: ConnectionSource,
+) -> Result<Vec<ServerImportResult>, AppError> {
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        log::error!("Failed to read connections from {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+
+    import_into(state, parse_connections(source, &contents)?).await
+}
+
+#[tauri::command]
+pub async fn import_connections(
+    state: State<'_, Mutex<AppState>>,
+    path: PathBuf,
+    source: ConnectionSource,
+) -> Result<Vec<ServerImportResult>, AppError> {
+    _import_connections(state.inner(), path, source).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_import_text_list() {
+        let app_state = Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(Database::new_in_memory().unwrap()));
+            s
+        });
+        let path = std::env::temp_dir().join(format!("servers-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "10.0.0.1 6379 secret\n10.0.0.1 6379\n10.0.0.2\n").unwrap();
+
+        let results = _import_connections(&app_state, path.clone(), ConnectionSource::TextList)
+            .await
+            .unwrap();
+        let server = results[0].server.clone().unwrap();
+        assert_eq!(server.password.as_deref(), Some("secret"));
+        assert_eq!(results[1].duplicate_of, Some(server.id));
+        assert_eq!(results[2].name, "Line 3");
+        assert!(results[2].error.is_some());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_connections_no_db_connection() {
+        let app_state = Mutex::new(AppState::new());
+        let path = std::env::temp_dir().join(format!("servers-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "10.0.0.1 6379\n").unwrap();
+
+        let result =
+            _import_connections(&app_state, path.clone(), ConnectionSource::TextList).await;
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
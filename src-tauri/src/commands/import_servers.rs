@@ -0,0 +1,215 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Server, ServerBundle, SERVER_BUNDLE_VERSION},
+    services::ConnectionImportError,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::State;
+use time::OffsetDateTime;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// The outcome of importing one entry, exactly one of `server`, `duplicate_of` and
+/// `error` is set.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ServerImportResult {
+    pub name: String,
+    /// The saved server.
+    pub server: Option<Server>,
+    /// The saved server that already connects to the same target, nothing is saved.
+    pub duplicate_of: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+/// Saves every server that does not connect to the same target as a saved one or an
+/// earlier entry, entries that failed to parse are reported as they are. The connections
+/// are not checked, imported servers may be unreachable from this machine.
+pub(super) async fn import_into(
+    state: &Mutex<AppState>,
+    entries: Vec<Result<Server, ConnectionImportError>>,
+) -> Result<Vec<ServerImportResult>, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+    let mut saved = Server::get(db_connection)?;
+
+    let results = entries
+        .into_iter()
+        .map(|entry| {
+            let server = match entry {
+                Ok(server) => server,
+                Err(e) => {
+                    return ServerImportResult {
+                        name: e.name,
+                        server: None,
+                        duplicate_of: None,
+                        error: Some(e.message),
+                    }
+                }
+            };
+            let mut result = ServerImportResult {
+                name: server.name.clone(),
+                server: None,
+                duplicate_of: None,
+                error: None,
+            };
+            if let Some(existing) = saved.iter().find(|saved| saved.same_target(&server)) {
+                result.duplicate_of = Some(existing.id);
+                return result;
+            }
+
+            let now = OffsetDateTime::now_utc();
+            let server = Server {
+                created_at: now,
+                updated_at: now,
+                ..server
+            };
+            match server.create(db_connection) {
+                Ok(server) => {
+                    saved.push(server.clone());
+                    result.server = Some(server);
+                }
+                Err(e) => result.error = Some(format!("Failed to save the server ({})", e)),
+            }
+            result
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Imports the servers of a bundle written by `export_servers`.
+async fn _import_servers(
+    state: &Mutex<AppState>,
+    path: PathBuf,
+) -> Result<Vec<ServerImportResult>, AppError> {
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        log::error!("Failed to read servers from {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+    let bundle: ServerBundle = serde_json::from_str(&contents).map_err(|e| {
+        log::error!("Invalid server bundle {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+    if bundle.version > SERVER_BUNDLE_VERSION {
+        log::error!(
+            "Server bundle {} has version {}, only {} is supported",
+            path.display(),
+            bundle.version,
+            SERVER_BUNDLE_VERSION
+        );
+        return Err(AppError::FileFailed);
+    }
+
+    import_into(state, bundle.servers.into_iter().map(Ok).collect()).await
+}
+
+#[tauri::command]
+pub async fn import_servers(
+    state: State<'_, Mutex<AppState>>,
+    path: PathBuf,
+) -> Result<Vec<ServerImportResult>, AppError> {
+    _import_servers(state.inner(), path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, models::SshTunnelSettings};
+
+    fn state_with_db() -> Mutex<AppState> {
+        Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(Database::new_in_memory().unwrap()));
+            s
+        })
+    }
+
+    fn write_bundle(bundle: &ServerBundle) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("servers-{}.json", Uuid::new_v4()));
+        std::fs::write(&path, serde_json::to_string(bundle).unwrap()).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_import_servers_skips_duplicates() {
+        let app_state = state_with_db();
+        let primary = Server {
+            password: Some("secret".to_string()),
+            ..Server::from_payload("Primary".to_string(), "10.0.0.1".to_string(), 6379)
+        };
+        let path = write_bundle(&ServerBundle::new(vec![
+            primary.clone(),
+            Server::from_payload("Same".to_string(), "10.0.0.1".to_string(), 6379),
+            Server {
+                database: 1,
+                ..Server::from_payload("Cache".to_string(), "10.0.0.1".to_string(), 6379)
+            },
+        ]));
+
+        let results = _import_servers(&app_state, path.clone()).await.unwrap();
+        let saved = results[0].server.clone().unwrap();
+        assert_eq!(saved.password, primary.password);
+        assert_eq!(results[1].duplicate_of, Some(saved.id));
+        assert!(results[2].server.is_some());
+
+        let results = _import_servers(&app_state, path.clone()).await.unwrap();
+        assert!(results.iter().all(|result| result.duplicate_of.is_some()));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_servers_behind_different_bastions() {
+        let app_state = state_with_db();
+        let behind = |host: &str| Server {
+            ssh_tunnel: Some(SshTunnelSettings {
+                host: host.to_string(),
+                port: 22,
+                user: "deploy".to_string(),
+                ..SshTunnelSettings::default()
+            }),
+            ..Server::from_payload(host.to_string(), "127.0.0.1".to_string(), 6379)
+        };
+        let path = write_bundle(&ServerBundle::new(vec![
+            behind("bastion-eu"),
+            behind("bastion-us"),
+            behind("BASTION-EU"),
+        ]));
+
+        let results = _import_servers(&app_state, path.clone()).await.unwrap();
+        assert!(results[0].server.is_some());
+        assert!(results[1].server.is_some());
+        assert_eq!(
+            results[2].duplicate_of,
+            results[0].server.as_ref().map(|server| server.id)
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_servers_from_newer_bundle() {
+        let app_state = state_with_db();
+        let path = write_bundle(&ServerBundle {
+            version: SERVER_BUNDLE_VERSION + 1,
+            ..ServerBundle::new(vec![])
+        });
+
+        let result = _import_servers(&app_state, path.clone()).await;
+        assert_eq!(result.unwrap_err(), AppError::FileFailed);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_servers_missing_file() {
+        let app_state = state_with_db();
+        let result = _import_servers(&app_state, PathBuf::from("/nonexistent/servers.json")).await;
+
+        assert_eq!(result.unwrap_err(), AppError::FileFailed);
+    }
+}
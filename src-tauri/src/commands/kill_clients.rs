@@ -0,0 +1,133 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use serde::Deserialize;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientType {
+    Normal,
+    Master,
+    Replica,
+    Pubsub,
+}
+
+/// Which connections `CLIENT KILL` closes.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "by", content = "value", rename_all = "snake_case")]
+pub enum ClientKillFilter {
+    Id(u64),
+    Addr(String),
+    User(String),
+    Type(ClientType),
+}
+
+impl ClientKillFilter {
+    fn args(&self) -> [String; 2] {
+        match self {
+            ClientKillFilter::Id(id) => ["ID".to_string(), id.to_string()],
+            ClientKillFilter::Addr(addr) => ["ADDR".to_string(), addr.clone()],
+            ClientKillFilter::User(user) => ["USER".to_string(), user.clone()],
+            ClientKillFilter::Type(client_type) => {
+                let name = match client_type {
+                    ClientType::Normal => "normal",
+                    ClientType::Master => "master",
+                    ClientType::Replica => "replica",
+                    ClientType::Pubsub => "pubsub",
+                };
+                ["TYPE".to_string(), name.to_string()]
+            }
+        }
+    }
+}
+
+/// Closes the connections matching the filter and returns how many were closed.
+/// `CLIENT KILL` is guarded by default, so a token from `confirm_command` is usually required.
+async fn _kill_clients(
+    state: &Mutex<AppState>,
+    filter: ClientKillFilter,
+    confirmation: Option<Uuid>,
+) -> Result<u32, AppError> {
+    let mut args = vec![b"CLIENT".to_vec(), b"KILL".to_vec()];
+    args.extend(filter.args().into_iter().map(String::into_bytes));
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut cmd = redis::cmd("CLIENT");
+    cmd.arg("KILL").arg(&filter.args());
+    cmd.query_async(&mut connection).await.map_err(|e| {
+        log::error!("Failed to kill clients matching {:?}: {}", filter, e);
+        AppError::RedisFailed
+    })
+}
+
+#[tauri::command]
+pub async fn kill_clients(
+    state: State<'_, Mutex<AppState>>,
+    filter: ClientKillFilter,
+    confirmation: Option<Uuid>,
+) -> Result<u32, AppError> {
+    _kill_clients(state.inner(), filter, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[test]
+    fn test_deserialize_filter() {
+        let filter: ClientKillFilter = serde_json::from_str(r#"{"by":"id","value":12}"#).unwrap();
+        assert_eq!(filter.args(), ["ID".to_string(), "12".to_string()]);
+
+        let filter: ClientKillFilter =
+            serde_json::from_str(r#"{"by":"type","value":"pubsub"}"#).unwrap();
+        assert_eq!(filter.args(), ["TYPE".to_string(), "pubsub".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_kill_clients_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _kill_clients(&app_state, ClientKillFilter::Id(1), None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_kill_client_by_id() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let mut other = client.get_multiplexed_async_connection().await.unwrap();
+        let id: u64 = redis::cmd("CLIENT")
+            .arg("ID")
+            .query_async(&mut other)
+            .await
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let token = app_state.issue_confirmation("CLIENT KILL".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let killed = _kill_clients(&app_state, ClientKillFilter::Id(id), Some(token))
+            .await
+            .unwrap();
+        assert_eq!(killed, 1);
+
+        container.rm().await.unwrap();
+    }
+}
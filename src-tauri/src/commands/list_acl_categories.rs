@@ -0,0 +1,66 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Lists the command categories, or the commands of `category` when given.
+async fn _list_acl_categories(
+    state: &Mutex<AppState>,
+    category: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut names: Vec<String> = redis::cmd("ACL")
+        .arg("CAT")
+        .arg(&category)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to list ACL categories {:?}: {}", category, e);
+            AppError::RedisFailed
+        })?;
+    names.sort();
+    Ok(names)
+}
+
+#[tauri::command]
+pub async fn list_acl_categories(
+    state: State<'_, Mutex<AppState>>,
+    category: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    _list_acl_categories(state.inner(), category).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_list_acl_categories() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let categories = _list_acl_categories(&app_state, None).await.unwrap();
+        assert!(categories.contains(&"dangerous".to_string()));
+
+        let commands = _list_acl_categories(&app_state, Some("hash".to_string()))
+            .await
+            .unwrap();
+        assert!(commands.contains(&"hset".to_string()));
+
+        container.rm().await.unwrap();
+    }
+}
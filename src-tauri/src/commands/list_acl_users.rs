@@ -0,0 +1,58 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_acl_list, AclListEntry},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Lists every ACL user with the rules recreating it.
+async fn _list_acl_users(state: &Mutex<AppState>) -> Result<Vec<AclListEntry>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let lines: Vec<String> = redis::cmd("ACL")
+        .arg("LIST")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to list ACL users: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_acl_list(&lines))
+}
+
+#[tauri::command]
+pub async fn list_acl_users(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<Vec<AclListEntry>, AppError> {
+    _list_acl_users(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_list_acl_users() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let users = _list_acl_users(&app_state).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].username, "default");
+        assert!(users[0].rules.contains(&"on".to_string()));
+
+        container.rm().await.unwrap();
+    }
+}
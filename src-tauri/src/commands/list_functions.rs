@@ -0,0 +1,97 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_function_list, FunctionLibrary},
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Lists the function libraries of the server, optionally filtered by a library name pattern.
+async fn _list_functions(
+    state: &Mutex<AppState>,
+    library_pattern: Option<String>,
+    with_code: bool,
+) -> Result<Vec<FunctionLibrary>, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut cmd = redis::cmd("FUNCTION");
+    cmd.arg("LIST");
+    if let Some(pattern) = library_pattern.filter(|pattern| !pattern.is_empty()) {
+        cmd.arg("LIBRARYNAME").arg(pattern);
+    }
+    if with_code {
+        cmd.arg("WITHCODE");
+    }
+
+    let reply: RedisValue = cmd.query_async(&mut connection).await.map_err(|e| {
+        log::error!("Failed to list functions: {}", e);
+        AppError::RedisFailed
+    })?;
+
+    Ok(parse_function_list(&reply))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_functions(
+    state: State<'_, Mutex<AppState>>,
+    library_pattern: Option<String>,
+    with_code: bool,
+) -> Result<Vec<FunctionLibrary>, AppError> {
+    _list_functions(state.inner(), library_pattern, with_code).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_list_functions() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: String = redis::cmd("FUNCTION")
+                .arg("LOAD")
+                .arg(
+                    "#!lua name=mylib\n\
+                    redis.register_function{function_name='hello', \
+                    callback=function() return 'hi' end, flags={'no-writes'}}",
+                )
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let libraries = _list_functions(&app_state, Some("my*".to_string()), true)
+            .await
+            .unwrap();
+        assert_eq!(libraries.len(), 1);
+        assert_eq!(libraries[0].library_name, "mylib");
+        assert_eq!(libraries[0].functions[0].name, "hello");
+        assert_eq!(
+            libraries[0].functions[0].flags,
+            vec!["no-writes".to_string()]
+        );
+        assert!(libraries[0].library_code.is_some());
+
+        let libraries = _list_functions(&app_state, Some("other*".to_string()), false)
+            .await
+            .unwrap();
+        assert!(libraries.is_empty());
+
+        container.rm().await.unwrap();
+    }
+}
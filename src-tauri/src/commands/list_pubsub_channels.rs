@@ -0,0 +1,117 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_numsub, PubSubChannel},
+};
+use redis::Value as RedisValue;
+use serde::Serialize;
+use tauri::State;
+use tokio::sync::Mutex;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PubSubOverview {
+    /// Active channels with their subscriber counts.
+    channels: Vec<PubSubChannel>,
+    /// Number of patterns subscribed to by all clients.
+    patterns: u64,
+}
+
+/// Lists the active channels matching `pattern` with their subscriber counts, using the
+/// shard channel variants when `sharded`.
+async fn _list_pubsub_channels(
+    state: &Mutex<AppState>,
+    pattern: Option<String>,
+    sharded: bool,
+) -> Result<PubSubOverview, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+    let (list, count) = if sharded {
+        ("SHARDCHANNELS", "SHARDNUMSUB")
+    } else {
+        ("CHANNELS", "NUMSUB")
+    };
+
+    let mut cmd = redis::cmd("PUBSUB");
+    cmd.arg(list);
+    if let Some(pattern) = pattern.filter(|p| !p.is_empty()) {
+        cmd.arg(pattern);
+    }
+    let mut names: Vec<String> = cmd.query_async(&mut connection).await.map_err(|e| {
+        log::error!("Failed to list pub/sub channels: {}", e);
+        AppError::RedisFailed
+    })?;
+    names.sort();
+
+    let mut pipe = redis::pipe();
+    pipe.cmd("PUBSUB").arg(count).arg(&names);
+    pipe.cmd("PUBSUB").arg("NUMPAT");
+    let (subscribers, patterns): (RedisValue, u64) =
+        pipe.query_async(&mut connection).await.map_err(|e| {
+            log::error!("Failed to count pub/sub subscribers: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(PubSubOverview {
+        channels: parse_numsub(&subscribers),
+        patterns,
+    })
+}
+
+#[tauri::command]
+pub async fn list_pubsub_channels(
+    state: State<'_, Mutex<AppState>>,
+    pattern: Option<String>,
+    sharded: bool,
+) -> Result<PubSubOverview, AppError> {
+    _list_pubsub_channels(state.inner(), pattern, sharded).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_list_pubsub_channels() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let mut pubsub = client.get_async_pubsub().await.unwrap();
+        pubsub.subscribe(&["orders", "events"]).await.unwrap();
+        pubsub.psubscribe("logs:*").await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let overview = _list_pubsub_channels(&app_state, None, false)
+            .await
+            .unwrap();
+        assert_eq!(overview.patterns, 1);
+        assert_eq!(
+            overview.channels,
+            vec![
+                PubSubChannel {
+                    name: "events".to_string(),
+                    subscribers: 1
+                },
+                PubSubChannel {
+                    name: "orders".to_string(),
+                    subscribers: 1
+                },
+            ]
+        );
+
+        let overview = _list_pubsub_channels(&app_state, Some("ord*".to_string()), false)
+            .await
+            .unwrap();
+        assert_eq!(overview.channels.len(), 1);
+
+        container.rm().await.unwrap();
+    }
+}
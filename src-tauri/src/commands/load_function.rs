@@ -0,0 +1,82 @@
+use super::execute_command::{run_command, CommandReply};
+use crate::core::{AppError, AppState};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Loads a library from source, replacing an existing library of the same name when asked.
+/// The reply holds the library name, or the compile error reported by the server.
+async fn _load_function(
+    state: &Mutex<AppState>,
+    code: String,
+    replace: bool,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    if code.trim().is_empty() {
+        log::warn!("Refusing to load an empty function library");
+        return Err(AppError::InvalidCommand);
+    }
+
+    let mut args = vec![b"FUNCTION".to_vec(), b"LOAD".to_vec()];
+    if replace {
+        args.push(b"REPLACE".to_vec());
+    }
+    args.push(code.into_bytes());
+
+    run_command(state, &args, confirmation).await
+}
+
+#[tauri::command]
+pub async fn load_function(
+    state: State<'_, Mutex<AppState>>,
+    code: String,
+    replace: bool,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _load_function(state.inner(), code, replace, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::Server, services::test_connection, tests::run_redis_container, utils::RespValue,
+    };
+
+    const LIBRARY: &str = "#!lua name=mylib\n\
+        redis.register_function('hello', function() return 'hi' end)";
+
+    #[tokio::test]
+    async fn test_load_function() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let result = _load_function(&app_state, LIBRARY.to_string(), false, None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("mylib".to_string()));
+
+        let result = _load_function(&app_state, LIBRARY.to_string(), false, None)
+            .await
+            .unwrap();
+        assert!(matches!(result.reply, RespValue::Error(_)));
+
+        let result = _load_function(&app_state, LIBRARY.to_string(), true, None)
+            .await
+            .unwrap();
+        assert_eq!(result.reply, RespValue::BulkString("mylib".to_string()));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_empty_function() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _load_function(&app_state, "".to_string(), false, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
@@ -0,0 +1,84 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::ServerConnection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Loads a script into the server's script cache and returns its SHA1.
+async fn _load_script(state: &Mutex<AppState>, body: String) -> Result<String, AppError> {
+    if body.trim().is_empty() {
+        log::warn!("Refusing to load an empty script");
+        return Err(AppError::InvalidCommand);
+    }
+
+    let args = vec![
+        b"SCRIPT".to_vec(),
+        b"LOAD".to_vec(),
+        body.clone().into_bytes(),
+    ];
+    guard_command(state, &args, None).await?;
+
+    let (client, cluster) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.get_cluster().cloned())
+    };
+    let mut connection = ServerConnection::open(&client, cluster.as_ref()).await?;
+
+    redis::cmd("SCRIPT")
+        .arg("LOAD")
+        .arg(&body)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load script: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn load_script(
+    state: State<'_, Mutex<AppState>>,
+    body: String,
+) -> Result<String, AppError> {
+    _load_script(state.inner(), body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Script, Server},
+        services::test_connection,
+        tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_load_script() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let sha1 = _load_script(&app_state, "return 1".to_string())
+            .await
+            .unwrap();
+        assert_eq!(sha1, Script::sha1_of("return 1"));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_script_no_redis_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _load_script(&app_state, "return 1".to_string()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
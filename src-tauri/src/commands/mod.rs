@@ -1,13 +1,167 @@
+mod acl_dry_run;
+mod acl_whoami;
 mod add_server;
+mod add_server_from_url;
+mod cancel_job;
+mod clear_command_history;
+mod client_list;
 mod close_server;
+mod cluster_topology;
+mod complete_command;
+mod confirm_command;
+mod delete_acl_users;
+mod delete_function;
+mod delete_script;
+mod delete_snippet;
+mod diff_memory_reports;
+mod dump_functions;
+mod eval_script;
+mod execute_command;
+mod execute_snippet;
+mod export_servers;
+mod fcall;
+mod flush_scripts;
+mod get_acl_log;
+mod get_acl_user;
+mod get_command_docs;
+mod get_command_guard;
+mod get_command_history;
+mod get_config;
+mod get_history_retention;
+mod get_memory_reports;
+mod get_metrics;
+mod get_metrics_settings;
+mod get_scripts;
 mod get_servers;
+mod get_slowlog;
+mod get_slowlog_config;
+mod get_slowlog_history;
+mod get_snippets;
+mod import_connections;
+mod import_servers;
+mod kill_clients;
+mod list_acl_categories;
+mod list_acl_users;
+mod list_functions;
+mod list_pubsub_channels;
+mod load_function;
+mod load_script;
 mod open_server;
+mod pause_clients;
+mod publish_message;
+mod replica_of;
+mod replication_overview;
+mod reset_config_stats;
+mod reset_slowlog;
+mod restore_functions;
 mod retrieve_key;
 mod retrieve_keys;
+mod rewrite_config;
+mod run_bulk_job;
+mod run_memory_analysis;
+mod run_ttl_analysis;
+mod save_script;
+mod save_snippet;
+mod script_exists;
+mod sentinel_overview;
+mod server_info;
+mod set_acl_user;
+mod set_client_no_evict;
+mod set_command_guard;
+mod set_config;
+mod set_history_retention;
+mod set_metrics_settings;
+mod set_server_read_only;
+mod set_slowlog_config;
+mod slowlog_len;
+mod start_monitor;
+mod stop_monitor;
+mod subscribe_channels;
+mod unpause_clients;
+mod unsubscribe_channels;
+mod unwatch_keyspace;
+mod watch_keyspace;
 
+pub use acl_dry_run::acl_dry_run;
+pub use acl_whoami::acl_whoami;
 pub use add_server::add_server;
+pub use add_server_from_url::add_server_from_url;
+pub use cancel_job::cancel_job;
+pub use clear_command_history::clear_command_history;
+pub use client_list::client_list;
 pub use close_server::close_server;
+pub use cluster_topology::cluster_topology;
+pub use complete_command::complete_command;
+pub use confirm_command::confirm_command;
+pub use delete_acl_users::delete_acl_users;
+pub use delete_function::delete_function;
+pub use delete_script::delete_script;
+pub use delete_snippet::delete_snippet;
+pub use diff_memory_reports::diff_memory_reports;
+pub use dump_functions::dump_functions;
+pub use eval_script::eval_script;
+pub use execute_command::execute_command;
+pub use execute_snippet::execute_snippet;
+pub use export_servers::export_servers;
+pub use fcall::fcall;
+pub use flush_scripts::flush_scripts;
+pub use get_acl_log::get_acl_log;
+pub use get_acl_user::get_acl_user;
+pub use get_command_docs::get_command_docs;
+pub use get_command_guard::get_command_guard;
+pub use get_command_history::get_command_history;
+pub use get_config::get_config;
+pub use get_history_retention::get_history_retention;
+pub use get_memory_reports::get_memory_reports;
+pub use get_metrics::get_metrics;
+pub use get_metrics_settings::get_metrics_settings;
+pub use get_scripts::get_scripts;
 pub use get_servers::get_servers;
+pub use get_slowlog::get_slowlog;
+pub use get_slowlog_config::get_slowlog_config;
+pub use get_slowlog_history::get_slowlog_history;
+pub use get_snippets::get_snippets;
+pub use import_connections::import_connections;
+pub use import_servers::import_servers;
+pub use kill_clients::kill_clients;
+pub use list_acl_categories::list_acl_categories;
+pub use list_acl_users::list_acl_users;
+pub use list_functions::list_functions;
+pub use list_pubsub_channels::list_pubsub_channels;
+pub use load_function::load_function;
+pub use load_script::load_script;
 pub use open_server::open_server;
+pub use pause_clients::pause_clients;
+pub use publish_message::publish_message;
+pub use replica_of::replica_of;
+pub use replication_overview::replication_overview;
+pub use reset_config_stats::reset_config_stats;
+pub use reset_slowlog::reset_slowlog;
+pub use restore_functions::restore_functions;
 pub use retrieve_key::retrieve_key;
 pub use retrieve_keys::retrieve_keys;
+pub use rewrite_config::rewrite_config;
+pub use run_bulk_job::run_bulk_job;
+pub use run_memory_analysis::run_memory_analysis;
+pub use run_ttl_analysis::run_ttl_analysis;
+pub use save_script::save_script;
+pub use save_snippet::save_snippet;
+pub use script_exists::script_exists;
+pub use sentinel_overview::sentinel_overview;
+pub use server_info::server_info;
+pub use set_acl_user::set_acl_user;
+pub use set_client_no_evict::set_client_no_evict;
+pub use set_command_guard::set_command_guard;
+pub use set_config::set_config;
+pub use set_history_retention::set_history_retention;
+pub use set_metrics_settings::set_metrics_settings;
+pub use set_server_read_only::set_server_read_only;
+pub use set_slowlog_config::set_slowlog_config;
+pub use slowlog_len::slowlog_len;
+pub use start_monitor::start_monitor;
+pub use stop_monitor::stop_monitor;
+pub use subscribe_channels::subscribe_channels;
+pub use unpause_clients::unpause_clients;
+pub use unsubscribe_channels::unsubscribe_channels;
+pub use unwatch_keyspace::unwatch_keyspace;
+pub use watch_keyspace::watch_keyspace;
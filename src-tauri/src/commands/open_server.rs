@@ -1,13 +1,62 @@
+use super::{
+    get_metrics::{load_metrics_settings, record_sample},
+    get_slowlog_history::record_slowlog,
+};
 use crate::{
     core::{AppError, AppState},
-    models::{Model, Server},
-    services::test_connection,
+    models::{MetricSample, Model, Server, SlowlogRecord},
+    services::{
+        test_connection, MetricsSampler, RedisCluster, SentinelWatcher, SlowlogCollector,
+        SshTunnel, METRICS_SAMPLE_EVENT, SENTINEL_FAILOVER_EVENT,
+    },
 };
-use tauri::State;
+use redis::Client;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-async fn _open_server(state: &Mutex<AppState>, id: Uuid) -> Result<Server, AppError> {
+#[derive(Serialize, Clone, Debug)]
+pub struct SentinelFailover {
+    server_id: Uuid,
+    address: String,
+    port: u16,
+}
+
+/// Opens the SSH tunnel of a server, then its client and, in cluster mode, its cluster
+/// connection. The forward is set up first so the connection checks go through it.
+async fn connect_server(
+    server: &Server,
+) -> Result<(Client, Option<RedisCluster>, Option<SshTunnel>), AppError> {
+    let tunnel = SshTunnel::open_for(server).await?;
+    let target = match &tunnel {
+        Some(tunnel) => tunnel.local_server(server),
+        None => server.clone(),
+    };
+    let client = test_connection(&target).await?;
+    let cluster = match target.cluster {
+        true => Some(RedisCluster::connect(&target).await?),
+        false => None,
+    };
+    Ok((client, cluster, tunnel))
+}
+
+/// Connects to a stored server and starts sampling its metrics and capturing its slow log,
+/// replacing the background tasks and closing the sessions of the previously opened
+/// server. Failovers of a Sentinel-managed server are reported to `on_failover` with the
+/// new master.
+async fn _open_server<F, G, H>(
+    state: &Mutex<AppState>,
+    id: Uuid,
+    on_sample: F,
+    on_slowlog: G,
+    on_failover: H,
+) -> Result<Server, AppError>
+where
+    F: Fn(MetricSample) + Send + 'static,
+    G: Fn(Vec<SlowlogRecord>) + Send + 'static,
+    H: Fn(String, u16) + Send + Sync + 'static,
+{
     let server = {
         let app_state = state.lock().await;
         let db = app_state.get_db_connection().ok_or_else(|| {
@@ -18,36 +67,222 @@ async fn _open_server(state: &Mutex<AppState>, id: Uuid) -> Result<Server, AppEr
     };
 
     let mut app_state = state.lock().await;
-    let client = test_connection(&server).await?;
+    let (client, cluster, tunnel) = connect_server(&server).await?;
     if app_state.get_redis_client().is_some() {
         app_state.set_redis_client(None);
     }
 
+    let settings = load_metrics_settings(&app_state)?;
+    if let Some(db) = app_state.get_db_connection() {
+        MetricSample::prune(&server.id, settings.max_age_days, db)?;
+        SlowlogRecord::prune(&server.id, db)?;
+    }
+    let sampler = MetricsSampler::start(
+        client.clone(),
+        server.id,
+        &settings,
+        app_state.client_no_evict(),
+        on_sample,
+    );
+    let collector = SlowlogCollector::start(
+        client.clone(),
+        server.id,
+        app_state.client_no_evict(),
+        on_slowlog,
+    );
+    let watcher = server
+        .sentinel
+        .clone()
+        .map(|sentinel| SentinelWatcher::start(sentinel, on_failover));
+
     app_state.set_redis_client(Some(client));
+    app_state.set_cluster(cluster);
+    app_state.set_server(Some(server.clone()));
+    app_state.set_command_docs(None);
+    app_state.set_metrics_sampler(Some(sampler));
+    app_state.set_slowlog_collector(Some(collector));
+    app_state.set_monitor(None);
+    app_state.set_pubsub(None);
+    app_state.set_keyspace_watcher(None);
+    app_state.set_sentinel_watcher(watcher);
+    app_state.set_console_connection(None);
+    app_state.set_ssh_tunnel(tunnel);
     Ok(server)
 }
 
+/// Reconnects to the master promoted by a failover and restarts the tasks bound to the
+/// previous one, the cluster connection and the SSH tunnel are opened again too. `MONITOR`,
+/// subscriptions, the keyspace watcher and the console connection are closed rather than
+/// moved, the UI starts them again on the failover event.
+///
+/// Returns `false` when another server was opened in the meantime.
+async fn follow_failover<F, G>(
+    state: &Mutex<AppState>,
+    server_id: Uuid,
+    on_sample: F,
+    on_slowlog: G,
+) -> Result<bool, AppError>
+where
+    F: Fn(MetricSample) + Send + 'static,
+    G: Fn(Vec<SlowlogRecord>) + Send + 'static,
+{
+    let mut app_state = state.lock().await;
+    let Some(server) = app_state
+        .get_server()
+        .filter(|server| server.id == server_id)
+        .cloned()
+    else {
+        return Ok(false);
+    };
+
+    let (client, cluster, tunnel) = connect_server(&server).await?;
+    let settings = load_metrics_settings(&app_state)?;
+    let sampler = MetricsSampler::start(
+        client.clone(),
+        server.id,
+        &settings,
+        app_state.client_no_evict(),
+        on_sample,
+    );
+    let collector = SlowlogCollector::start(
+        client.clone(),
+        server.id,
+        app_state.client_no_evict(),
+        on_slowlog,
+    );
+
+    app_state.set_redis_client(Some(client));
+    app_state.set_cluster(cluster);
+    app_state.set_command_docs(None);
+    app_state.set_metrics_sampler(Some(sampler));
+    app_state.set_slowlog_collector(Some(collector));
+    app_state.set_monitor(None);
+    app_state.set_pubsub(None);
+    app_state.set_keyspace_watcher(None);
+    app_state.set_console_connection(None);
+    app_state.set_ssh_tunnel(tunnel);
+    Ok(true)
+}
+
+fn sample_callback(app: AppHandle) -> impl Fn(MetricSample) + Send + 'static {
+    move |sample| {
+        if let Err(e) = app.emit(METRICS_SAMPLE_EVENT, sample.clone()) {
+            log::warn!("Failed to emit metrics sample: {}", e);
+        }
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<Mutex<AppState>>();
+            record_sample(state.inner(), &sample).await;
+        });
+    }
+}
+
+fn slowlog_callback(app: AppHandle) -> impl Fn(Vec<SlowlogRecord>) + Send + 'static {
+    move |records| {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<Mutex<AppState>>();
+            record_slowlog(state.inner(), &records).await;
+        });
+    }
+}
+
 #[tauri::command]
-pub async fn open_server(state: State<'_, Mutex<AppState>>, id: Uuid) -> Result<Server, AppError> {
-    _open_server(state.inner(), id).await
+pub async fn open_server(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    id: Uuid,
+) -> Result<Server, AppError> {
+    let failover_app = app.clone();
+    _open_server(
+        state.inner(),
+        id,
+        sample_callback(app.clone()),
+        slowlog_callback(app),
+        move |address, port| {
+            let app = failover_app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<Mutex<AppState>>();
+                let followed = follow_failover(
+                    state.inner(),
+                    id,
+                    sample_callback(app.clone()),
+                    slowlog_callback(app.clone()),
+                )
+                .await;
+                match followed {
+                    Ok(true) => {
+                        let failover = SentinelFailover {
+                            server_id: id,
+                            address,
+                            port,
+                        };
+                        if let Err(e) = app.emit(SENTINEL_FAILOVER_EVENT, failover) {
+                            log::warn!("Failed to emit Sentinel failover: {}", e);
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => log::error!("Failed to follow the failover of {}: {}", id, e),
+                }
+            });
+        },
+    )
+    .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{core::Database, tests::run_redis_container};
+    use crate::{core::Database, services::PubSubSession, tests::run_redis_container};
 
     #[tokio::test]
     async fn test_open_server_with_non_existing_server_id() {
         let app_state = Mutex::new(AppState::new());
         let invalid_id = Uuid::new_v4();
 
-        let result = _open_server(&app_state, invalid_id).await;
+        let result = _open_server(&app_state, invalid_id, |_| {}, |_| {}, |_, _| {}).await;
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), AppError::DbNotReady);
     }
 
+    #[tokio::test]
+    async fn test_follow_failover_of_closed_server() {
+        let app_state = Mutex::new(AppState::new());
+        let followed = follow_failover(&app_state, Uuid::new_v4(), |_| {}, |_| {}).await;
+
+        assert_eq!(followed, Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_follow_failover_swaps_client() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Test Server".to_string(), host, port);
+        let stale = redis::Client::open("redis://127.0.0.1:1").unwrap();
+        let app_state = Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(Database::new_in_memory().unwrap()));
+            s.set_server(Some(server.clone()));
+            s.set_redis_client(Some(stale.clone()));
+            s
+        });
+
+        let followed = follow_failover(&app_state, server.id, |_| {}, |_| {}).await;
+
+        assert_eq!(followed, Ok(true));
+        let state = app_state.lock().await;
+        let client = state.get_redis_client().unwrap();
+        assert_ne!(
+            client.get_connection_info().addr(),
+            stale.get_connection_info().addr()
+        );
+        assert!(state.get_cluster().is_none());
+        assert!(state.get_console_connection().is_none());
+        drop(state);
+
+        container.rm().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_open_server_success() {
         let app_state = Mutex::new({
@@ -65,11 +300,12 @@ mod tests {
             new_server_payload.create(db_connection).unwrap()
         };
 
-        let result = _open_server(&app_state, new_server.id).await;
+        let result = _open_server(&app_state, new_server.id, |_| {}, |_| {}, |_, _| {}).await;
 
         assert!(result.is_ok());
         let opened_server = result.unwrap();
         assert_eq!(opened_server, new_server);
+        assert_eq!(app_state.lock().await.get_server(), Some(&new_server));
         container.rm().await.unwrap();
     }
 
@@ -90,13 +326,49 @@ mod tests {
             new_server_payload.create(db_connection).unwrap()
         };
 
-        let result = _open_server(&app_state, new_server.id).await;
+        let result = _open_server(&app_state, new_server.id, |_| {}, |_| {}, |_, _| {}).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), new_server);
         container.rm().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_open_server_closes_previous_sessions() {
+        let app_state = Mutex::new({
+            let mut s = AppState::new();
+            s.set_db_connection(Some(Database::new_in_memory().unwrap()));
+            s
+        });
+
+        let (host, port, container) = run_redis_container(6379).await;
+        let (first, second) = {
+            let state = app_state.lock().await;
+            let db = state.get_db_connection().unwrap();
+            let first = Server::from_payload("A".to_string(), host.clone(), port);
+            let second = Server::from_payload("B".to_string(), host, port);
+            (first.create(db).unwrap(), second.create(db).unwrap())
+        };
+
+        _open_server(&app_state, first.id, |_| {}, |_| {}, |_, _| {})
+            .await
+            .unwrap();
+        let client = app_state.lock().await.get_redis_client().cloned().unwrap();
+        let session = PubSubSession::start(&client, false, |_| {}).await.unwrap();
+        app_state.lock().await.set_pubsub(Some(session));
+
+        _open_server(&app_state, second.id, |_| {}, |_| {}, |_, _| {})
+            .await
+            .unwrap();
+        let state = app_state.lock().await;
+        assert_eq!(state.get_server(), Some(&second));
+        assert!(state.get_pubsub().is_none());
+        assert!(state.get_keyspace_watcher().is_none());
+        drop(state);
+
+        container.rm().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_open_server_with_invalid_connection() {
         let app_state = Mutex::new({
@@ -116,7 +388,7 @@ mod tests {
             new_server_payload.create(db_connection).unwrap()
         };
 
-        let result = _open_server(&app_state, new_server.id).await;
+        let result = _open_server(&app_state, new_server.id, |_| {}, |_| {}, |_, _| {}).await;
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), AppError::RedisFailed);
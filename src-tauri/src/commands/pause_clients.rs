@@ -0,0 +1,94 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Suspends clients for `timeout_ms`. With `write_only` only commands that may write are
+/// held back, which needs Redis 6.2.
+async fn _pause_clients(
+    state: &Mutex<AppState>,
+    timeout_ms: u64,
+    write_only: bool,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let mut args = vec![
+        b"CLIENT".to_vec(),
+        b"PAUSE".to_vec(),
+        timeout_ms.to_string().into_bytes(),
+    ];
+    if write_only {
+        args.push(b"WRITE".to_vec());
+    }
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut cmd = redis::cmd("CLIENT");
+    cmd.arg("PAUSE").arg(timeout_ms);
+    if write_only {
+        cmd.arg("WRITE");
+    }
+    cmd.query_async(&mut connection).await.map_err(|e| {
+        log::error!("Failed to pause clients: {}", e);
+        AppError::RedisFailed
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn pause_clients(
+    state: State<'_, Mutex<AppState>>,
+    timeout_ms: u64,
+    write_only: bool,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _pause_clients(state.inner(), timeout_ms, write_only, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_pause_clients_for_writes() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client.clone()));
+        let app_state = Mutex::new(app_state);
+
+        _pause_clients(&app_state, 500, true, None).await.unwrap();
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        let started = std::time::Instant::now();
+        let _: Option<String> = redis::cmd("GET")
+            .arg("key")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_millis(300));
+
+        let _: () = redis::cmd("SET")
+            .arg("key")
+            .arg("value")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= std::time::Duration::from_millis(300));
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,99 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Publishes a message with `PUBLISH`, or `SPUBLISH` when `sharded`, and returns
+/// the number of clients that received it.
+async fn _publish_message(
+    state: &Mutex<AppState>,
+    channel: String,
+    message: String,
+    sharded: bool,
+    confirmation: Option<Uuid>,
+) -> Result<u64, AppError> {
+    let command = if sharded { "SPUBLISH" } else { "PUBLISH" };
+    let args = vec![
+        command.as_bytes().to_vec(),
+        channel.as_bytes().to_vec(),
+        message.as_bytes().to_vec(),
+    ];
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd(command)
+        .arg(&channel)
+        .arg(&message)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to publish to {}: {}", channel, e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn publish_message(
+    state: State<'_, Mutex<AppState>>,
+    channel: String,
+    message: String,
+    sharded: bool,
+    confirmation: Option<Uuid>,
+) -> Result<u64, AppError> {
+    _publish_message(state.inner(), channel, message, sharded, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_publish_message() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let mut pubsub = client.get_async_pubsub().await.unwrap();
+        pubsub.subscribe("orders").await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let receivers = _publish_message(
+            &app_state,
+            "orders".to_string(),
+            "created".to_string(),
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(receivers, 1);
+
+        let receivers = _publish_message(
+            &app_state,
+            "nobody".to_string(),
+            "created".to_string(),
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(receivers, 0);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Makes the connected node follow another primary with `REPLICAOF host port`, or promotes
+/// it with `REPLICAOF NO ONE` when no address is given.
+async fn _replica_of(
+    state: &Mutex<AppState>,
+    address: Option<String>,
+    port: Option<u16>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let target = match (address, port) {
+        (Some(address), Some(port)) => vec![address.into_bytes(), port.to_string().into_bytes()],
+        (None, None) => vec![b"NO".to_vec(), b"ONE".to_vec()],
+        _ => {
+            log::warn!("REPLICAOF needs both an address and a port");
+            return Err(AppError::InvalidCommand);
+        }
+    };
+    let mut args = vec![b"REPLICAOF".to_vec()];
+    args.extend(target);
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("REPLICAOF")
+        .arg(&args[1..])
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to change the replication source: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn replica_of(
+    state: State<'_, Mutex<AppState>>,
+    address: Option<String>,
+    port: Option<u16>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _replica_of(state.inner(), address, port, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database,
+        models::Server,
+        services::{replication_overview, test_connection},
+        tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_replica_of_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _replica_of(&app_state, None, None, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_replica_of_without_port() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _replica_of(&app_state, Some("10.0.0.1".to_string()), None, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+
+    #[tokio::test]
+    async fn test_replica_of_and_promote() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(client.clone()));
+        let first = app_state.issue_confirmation("REPLICAOF".to_string());
+        let second = app_state.issue_confirmation("REPLICAOF".to_string());
+        let app_state = Mutex::new(app_state);
+
+        // The server keeps trying to reach an unknown primary, which is enough to be a replica.
+        _replica_of(
+            &app_state,
+            Some("127.0.0.1".to_string()),
+            Some(1),
+            Some(first),
+        )
+        .await
+        .unwrap();
+        let overview = replication_overview(&client).await.unwrap();
+        assert_eq!(overview.role, "slave");
+        assert_eq!(overview.primary.unwrap().port, 1);
+
+        _replica_of(&app_state, None, None, Some(second))
+            .await
+            .unwrap();
+        let overview = replication_overview(&client).await.unwrap();
+        assert_eq!(overview.role, "master");
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{replication_overview as read_replication, ReplicationOverview},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reads the role and offset of the connected node, with the lag of every replica
+/// when it is a primary.
+async fn _replication_overview(state: &Mutex<AppState>) -> Result<ReplicationOverview, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+
+    read_replication(&client).await
+}
+
+#[tauri::command]
+pub async fn replication_overview(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<ReplicationOverview, AppError> {
+    _replication_overview(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_replication_overview_no_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _replication_overview(&app_state).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_replication_overview_of_standalone_primary() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let overview = _replication_overview(&app_state).await.unwrap();
+        assert_eq!(overview.role, "master");
+        assert!(overview.replicas.is_empty());
+        assert!(overview.primary.is_none());
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Resets the statistics reported by `INFO`, such as the keyspace hits and command stats.
+async fn _reset_config_stats(
+    state: &Mutex<AppState>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let args = vec![b"CONFIG".to_vec(), b"RESETSTAT".to_vec()];
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("CONFIG")
+        .arg("RESETSTAT")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to reset the server stats: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn reset_config_stats(
+    state: State<'_, Mutex<AppState>>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _reset_config_stats(state.inner(), confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reset_config_stats_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _reset_config_stats(&app_state, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+}
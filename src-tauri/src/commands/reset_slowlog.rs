@@ -0,0 +1,92 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Empties the server's slow log. Entries already captured to the database are kept.
+async fn _reset_slowlog(
+    state: &Mutex<AppState>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let args = vec![b"SLOWLOG".to_vec(), b"RESET".to_vec()];
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("SLOWLOG")
+        .arg("RESET")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to reset the slow log: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn reset_slowlog(
+    state: State<'_, Mutex<AppState>>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _reset_slowlog(state.inner(), confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_reset_slowlog() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        // Log everything once, then raise the threshold so the reset itself is not logged.
+        for threshold in [0, 10_000_000] {
+            let _: () = redis::cmd("CONFIG")
+                .arg("SET")
+                .arg("slowlog-log-slower-than")
+                .arg(threshold)
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let mut slowlog_len = redis::cmd("SLOWLOG");
+        slowlog_len.arg("LEN");
+        assert!(
+            slowlog_len
+                .query_async::<u32>(&mut connection)
+                .await
+                .unwrap()
+                > 0
+        );
+
+        _reset_slowlog(&app_state, None).await.unwrap();
+        assert_eq!(
+            slowlog_len
+                .query_async::<u32>(&mut connection)
+                .await
+                .unwrap(),
+            0
+        );
+
+        container.rm().await.unwrap();
+    }
+}
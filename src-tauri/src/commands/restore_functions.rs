@@ -0,0 +1,129 @@
+use super::execute_command::{run_command, CommandReply};
+use crate::core::{AppError, AppState};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// How restored libraries are merged with the ones already on the server.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionRestorePolicy {
+    /// Fails if a restored library already exists.
+    Append,
+    /// Replaces existing libraries with the restored ones.
+    Replace,
+    /// Deletes every library before restoring.
+    Flush,
+}
+
+impl FunctionRestorePolicy {
+    fn as_arg(&self) -> &'static str {
+        match self {
+            Self::Append => "APPEND",
+            Self::Replace => "REPLACE",
+            Self::Flush => "FLUSH",
+        }
+    }
+}
+
+/// Restores the libraries of a file written by `dump_functions`.
+/// The reply holds the server error when the payload or policy is rejected.
+async fn _restore_functions(
+    state: &Mutex<AppState>,
+    path: PathBuf,
+    policy: FunctionRestorePolicy,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    let payload = std::fs::read(&path).map_err(|e| {
+        log::error!("Failed to read functions from {}: {}", path.display(), e);
+        AppError::FileFailed
+    })?;
+
+    let args = vec![
+        b"FUNCTION".to_vec(),
+        b"RESTORE".to_vec(),
+        payload,
+        policy.as_arg().as_bytes().to_vec(),
+    ];
+
+    run_command(state, &args, confirmation).await
+}
+
+#[tauri::command]
+pub async fn restore_functions(
+    state: State<'_, Mutex<AppState>>,
+    path: PathBuf,
+    policy: FunctionRestorePolicy,
+    confirmation: Option<Uuid>,
+) -> Result<CommandReply, AppError> {
+    _restore_functions(state.inner(), path, policy, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::Server, services::test_connection, tests::run_redis_container, utils::RespValue,
+    };
+
+    #[tokio::test]
+    async fn test_restore_functions_from_dump() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        let path = std::env::temp_dir().join(format!("functions-{}.rdb", Uuid::new_v4()));
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: String = redis::cmd("FUNCTION")
+                .arg("LOAD")
+                .arg("#!lua name=mylib\nredis.register_function('hello', function() return 'hi' end)")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+            let payload: Vec<u8> = redis::cmd("FUNCTION")
+                .arg("DUMP")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+            std::fs::write(&path, payload).unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let result = _restore_functions(
+            &app_state,
+            path.clone(),
+            FunctionRestorePolicy::Append,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(matches!(result.reply, RespValue::Error(_)));
+
+        let result = _restore_functions(
+            &app_state,
+            path.clone(),
+            FunctionRestorePolicy::Replace,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.reply, RespValue::SimpleString("OK".to_string()));
+
+        std::fs::remove_file(path).unwrap();
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_functions_missing_file() {
+        let app_state = Mutex::new(AppState::new());
+        let path = std::env::temp_dir().join(format!("missing-{}.rdb", Uuid::new_v4()));
+        let result = _restore_functions(&app_state, path, FunctionRestorePolicy::Flush, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::FileFailed);
+    }
+}
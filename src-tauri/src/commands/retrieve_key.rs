@@ -2,7 +2,7 @@ use crate::{
     core::{app_state::AppState, AppError},
     utils::{format_ttl_to_human_readable, redis_to_json},
 };
-use redis::{AsyncCommands, AsyncConnectionConfig, Value as RedisValue};
+use redis::{aio::ConnectionLike, AsyncCommands, AsyncConnectionConfig, Value as RedisValue};
 use serde_json::{json, Value as JsonValue};
 use std::time::Duration;
 use tauri::State;
@@ -33,15 +33,34 @@ async fn _retrieve_key(
         AppError::RedisFailed
     })?;
 
-    let config = AsyncConnectionConfig::new().set_connection_timeout(Some(Duration::from_secs(6)));
-    let mut connection = redis_client
-        .get_multiplexed_async_connection_with_config(&config)
-        .await
-        .map_err(|e| {
-            log::error!("Failed to get Redis connection: {}", e);
-            AppError::RedisFailed
-        })?;
+    // A cluster connection routes the key to the node serving its slot.
+    let response = match state.get_cluster() {
+        Some(cluster) => read_key(&mut cluster.connection(), key).await?,
+        None => {
+            let config =
+                AsyncConnectionConfig::new().set_connection_timeout(Some(Duration::from_secs(6)));
+            let mut connection = redis_client
+                .get_multiplexed_async_connection_with_config(&config)
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to get Redis connection: {}", e);
+                    AppError::RedisFailed
+                })?;
+            read_key(&mut connection, key).await?
+        }
+    };
+
+    if let Some(watcher) = state.get_keyspace_watcher() {
+        watcher.set_current_key(Some(response.details.key.clone()));
+    }
+
+    Ok(response)
+}
 
+async fn read_key<C>(connection: &mut C, key: String) -> Result<RetrieveKeyResponse, AppError>
+where
+    C: ConnectionLike + Send + Sync,
+{
     let mut pipe = redis::pipe();
 
     log::debug!("Retrieving info for key: '{}'", key);
@@ -53,7 +72,7 @@ async fn _retrieve_key(
         // .arg("USAGE")
         // .arg(key.clone());
         // .query_async::<(String, isize, usize)>(&mut connection)
-        .query_async::<(String, i64)>(&mut connection)
+        .query_async::<(String, i64)>(connection)
         .await
         .map_err(|e| {
             log::error!("Error retrieving key info: {:?}", e);
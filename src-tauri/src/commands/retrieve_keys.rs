@@ -1,9 +1,9 @@
 use crate::{
     core::{app_state::AppState, AppError},
+    services::{get_connection, keyspace_clients, scan_options},
     utils::format_ttl_to_human_readable,
 };
-use redis::{AsyncCommands, AsyncConnectionConfig, ScanOptions};
-use std::time::Duration;
+use redis::{aio::MultiplexedConnection, AsyncCommands};
 use tauri::State;
 use tokio::sync::Mutex;
 
@@ -27,32 +27,30 @@ async fn _retrieve_keys(
         AppError::RedisFailed
     })?;
 
-    let config = AsyncConnectionConfig::new().set_connection_timeout(Some(Duration::from_secs(6)));
-    let mut connection = redis_client
-        .get_multiplexed_async_connection_with_config(&config)
-        .await
-        .map_err(|e| {
-            log::error!("Failed to get Redis connection: {}", e);
-            AppError::RedisFailed
-        })?;
-
-    let mut scan_options = ScanOptions::default();
-    if !pattern.is_empty() {
-        scan_options = scan_options.with_pattern(&pattern);
-    }
-
-    if key_type != "*" {
-        scan_options = scan_options.with_type(&key_type);
-    }
     log::debug!(
         "Scanning keys with pattern: '{}' - type: {}",
         pattern,
         key_type
     );
 
+    // Every primary of a cluster only scans the slots it serves.
+    let mut keys = vec![];
+    for client in keyspace_clients(redis_client, state.get_cluster().is_some()).await? {
+        let mut connection = get_connection(&client).await?;
+        keys.extend(scan_node_keys(&mut connection, &pattern, &key_type).await?);
+    }
+    Ok(keys)
+}
+
+/// Scans the keys of a single node and reads their type and TTL from the same node.
+async fn scan_node_keys(
+    connection: &mut MultiplexedConnection,
+    pattern: &str,
+    key_type: &str,
+) -> Result<Vec<KeyInfo>, AppError> {
     let keys: Vec<KeyInfo> = {
         let mut keys_iter = connection
-            .scan_options::<String>(scan_options)
+            .scan_options::<String>(scan_options(pattern, key_type))
             .await
             .map_err(|e| {
                 log::error!("Error scanning keys: {:?}", e);
@@ -86,7 +84,7 @@ async fn _retrieve_keys(
     }
 
     // let types: Vec<(String, isize, usize)> =
-    let types: Vec<(String, i64)> = pipe.query_async(&mut connection).await.map_err(|e| {
+    let types: Vec<(String, i64)> = pipe.query_async(connection).await.map_err(|e| {
         log::error!("Failed to retrieve key types and TTLs: {}", e);
         AppError::RedisFailed
     })?;
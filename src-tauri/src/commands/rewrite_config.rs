@@ -0,0 +1,77 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Writes the running configuration back to the server's config file. Fails on servers
+/// started without one.
+async fn _rewrite_config(
+    state: &Mutex<AppState>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    let args = vec![b"CONFIG".to_vec(), b"REWRITE".to_vec()];
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("CONFIG")
+        .arg("REWRITE")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to rewrite the config file: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn rewrite_config(
+    state: State<'_, Mutex<AppState>>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _rewrite_config(state.inner(), confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_rewrite_config_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _rewrite_config(&app_state, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_rewrite_config_without_config_file() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let token = app_state.issue_confirmation("CONFIG REWRITE".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let result = _rewrite_config(&app_state, Some(token)).await;
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,476 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{AuditEntry, Model},
+    services::{get_connection, keyspace_clients, scan_options},
+};
+use redis::{aio::MultiplexedConnection, AsyncCommands, Client, RedisResult};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Event emitted while a bulk job is scanning or applying its action.
+pub const BULK_JOB_PROGRESS_EVENT: &str = "bulk-job-progress";
+const DEFAULT_BATCH_SIZE: usize = 500;
+const SAMPLE_SIZE: usize = 20;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkAction {
+    Delete,
+    Unlink,
+    Expire {
+        seconds: i64,
+    },
+    Persist,
+    /// Renames every matched key starting with `from` so it starts with `to` instead.
+    /// Uses RENAMENX so an existing key is never overwritten.
+    RenamePrefix {
+        from: String,
+        to: String,
+    },
+}
+
+impl BulkAction {
+    fn name(&self) -> String {
+        match self {
+            BulkAction::Delete => "delete".to_string(),
+            BulkAction::Unlink => "unlink".to_string(),
+            BulkAction::Expire { seconds } => format!("expire({})", seconds),
+            BulkAction::Persist => "persist".to_string(),
+            BulkAction::RenamePrefix { from, to } => format!("rename_prefix({} -> {})", from, to),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BulkJobRequest {
+    /// Generated by the caller so the job can be cancelled while it runs.
+    job_id: Uuid,
+    pattern: String,
+    key_type: String,
+    action: BulkAction,
+    dry_run: bool,
+    batch_size: Option<usize>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkJobPhase {
+    Scanning,
+    Applying,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BulkJobProgress {
+    job_id: Uuid,
+    phase: BulkJobPhase,
+    processed: usize,
+    total: usize,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct BulkJobSummary {
+    job_id: Uuid,
+    action: String,
+    pattern: String,
+    dry_run: bool,
+    matched: usize,
+    affected: usize,
+    failed: usize,
+    cancelled: bool,
+    samples: Vec<String>,
+}
+
+async fn _run_bulk_job<F>(
+    state: &Mutex<AppState>,
+    request: BulkJobRequest,
+    on_progress: F,
+) -> Result<BulkJobSummary, AppError>
+where
+    F: Fn(BulkJobProgress),
+{
+    let (client, cluster, server_id, cancelled) = {
+        let mut app_state = state.lock().await;
+        if !request.dry_run && app_state.get_server().is_some_and(|s| s.read_only) {
+            log::warn!("Blocked bulk job {} on a read-only server", request.job_id);
+            return Err(AppError::WriteBlocked);
+        }
+        let client = app_state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        let server_id = app_state.get_server().map_or(Uuid::nil(), |s| s.id);
+        let cluster = app_state.get_cluster().is_some();
+        (
+            client,
+            cluster,
+            server_id,
+            app_state.register_job(request.job_id),
+        )
+    };
+
+    let result = match keyspace_clients(&client, cluster).await {
+        Ok(clients) => execute_bulk_job(&clients, &request, &cancelled, &on_progress).await,
+        Err(e) => Err(e),
+    };
+
+    let mut app_state = state.lock().await;
+    app_state.finish_job(&request.job_id);
+    let summary = result?;
+
+    // The job already ran, so an audit failure is logged rather than reported as a job failure.
+    match app_state.get_db_connection() {
+        Some(db) => {
+            let entry = AuditEntry {
+                server_id,
+                action: summary.action.clone(),
+                pattern: summary.pattern.clone(),
+                dry_run: summary.dry_run,
+                matched: summary.matched as u32,
+                affected: summary.affected as u32,
+                failed: summary.failed as u32,
+                cancelled: summary.cancelled,
+                ..AuditEntry::default()
+            };
+            if let Err(e) = entry.create(db) {
+                log::error!("Failed to write bulk job {} to audit log: {}", entry, e);
+            }
+        }
+        None => log::warn!("Database connection is not ready, bulk job was not audited"),
+    }
+
+    Ok(summary)
+}
+
+/// Every node of `clients` is scanned and applied to on its own, so the keys of a cluster are
+/// changed on the primary serving them.
+async fn execute_bulk_job<F>(
+    clients: &[Client],
+    request: &BulkJobRequest,
+    cancelled: &AtomicBool,
+    on_progress: &F,
+) -> Result<BulkJobSummary, AppError>
+where
+    F: Fn(BulkJobProgress),
+{
+    let batch_size = request
+        .batch_size
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+
+    log::debug!(
+        "Running bulk job {} - action: {}, pattern: '{}', type: {}, dry run: {}",
+        request.job_id,
+        request.action.name(),
+        request.pattern,
+        request.key_type,
+        request.dry_run
+    );
+
+    // Keys are collected before anything is applied so renamed keys are never scanned twice.
+    let mut nodes: Vec<(MultiplexedConnection, Vec<String>)> = vec![];
+    let mut matched: usize = 0;
+    for client in clients {
+        let mut connection = get_connection(client).await?;
+        let mut keys: Vec<String> = vec![];
+        {
+            let mut keys_iter = connection
+                .scan_options::<String>(
+                    scan_options(&request.pattern, &request.key_type).with_count(batch_size),
+                )
+                .await
+                .map_err(|e| {
+                    log::error!("Error scanning keys: {:?}", e);
+                    AppError::RedisFailed
+                })?;
+
+            while let Some(key) = keys_iter.next_item().await {
+                if cancelled.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                keys.push(key.map_err(|_| {
+                    log::error!("Error retrieving key during scan");
+                    AppError::RedisFailed
+                })?);
+                matched += 1;
+
+                if matched.is_multiple_of(batch_size) {
+                    on_progress(BulkJobProgress {
+                        job_id: request.job_id,
+                        phase: BulkJobPhase::Scanning,
+                        processed: matched,
+                        total: matched,
+                    });
+                }
+            }
+        }
+        nodes.push((connection, keys));
+    }
+
+    let mut summary = BulkJobSummary {
+        job_id: request.job_id,
+        action: request.action.name(),
+        pattern: request.pattern.clone(),
+        dry_run: request.dry_run,
+        matched,
+        samples: nodes
+            .iter()
+            .flat_map(|(_, keys)| keys)
+            .take(SAMPLE_SIZE)
+            .cloned()
+            .collect(),
+        ..BulkJobSummary::default()
+    };
+
+    if request.dry_run {
+        summary.cancelled = cancelled.load(Ordering::Relaxed);
+        return Ok(summary);
+    }
+
+    let mut processed = 0;
+    let batches = nodes.iter().flat_map(|(connection, keys)| {
+        keys.chunks(batch_size)
+            .map(|batch| (connection.clone(), batch))
+    });
+    for (mut connection, batch) in batches {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let mut pipe = redis::pipe();
+        pipe.ignore_errors();
+        for key in batch {
+            match &request.action {
+                BulkAction::Delete => {
+                    pipe.del(key);
+                }
+                BulkAction::Unlink => {
+                    pipe.unlink(key);
+                }
+                BulkAction::Expire { seconds } => {
+                    pipe.expire(key, *seconds);
+                }
+                BulkAction::Persist => {
+                    pipe.persist(key);
+                }
+                BulkAction::RenamePrefix { from, to } => match key.strip_prefix(from.as_str()) {
+                    Some(rest) => {
+                        pipe.rename_nx(key, format!("{}{}", to, rest));
+                    }
+                    None => continue,
+                },
+            }
+        }
+
+        let results: Vec<RedisResult<i64>> =
+            pipe.query_async(&mut connection).await.map_err(|e| {
+                log::error!("Failed to apply bulk job batch: {}", e);
+                AppError::RedisFailed
+            })?;
+
+        for result in results {
+            match result {
+                Ok(count) => summary.affected += count as usize,
+                Err(e) => {
+                    log::warn!("Bulk job {} command failed: {}", request.job_id, e);
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        processed += batch.len();
+        on_progress(BulkJobProgress {
+            job_id: request.job_id,
+            phase: BulkJobPhase::Applying,
+            processed,
+            total: matched,
+        });
+    }
+
+    summary.cancelled = cancelled.load(Ordering::Relaxed);
+    log::debug!(
+        "Bulk job {} finished - matched: {}, affected: {}, failed: {}, cancelled: {}",
+        request.job_id,
+        summary.matched,
+        summary.affected,
+        summary.failed,
+        summary.cancelled
+    );
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn run_bulk_job(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    request: BulkJobRequest,
+) -> Result<BulkJobSummary, AppError> {
+    _run_bulk_job(state.inner(), request, |progress| {
+        if let Err(e) = app.emit(BULK_JOB_PROGRESS_EVENT, progress) {
+            log::warn!("Failed to emit bulk job progress: {}", e);
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+    const PORT: u16 = 6379;
+
+    async fn setup_state(host: String, port: u16) -> Mutex<AppState> {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let redis_client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = redis_client
+                .get_multiplexed_async_connection()
+                .await
+                .unwrap();
+            for i in 0..25 {
+                let _: () = connection.set(format!("old:{}", i), i).await.unwrap();
+            }
+            let _: () = connection.set("keep", "value").await.unwrap();
+        }
+        app_state.set_redis_client(Some(redis_client));
+
+        Mutex::new(app_state)
+    }
+
+    fn request(action: BulkAction, dry_run: bool) -> BulkJobRequest {
+        BulkJobRequest {
+            job_id: Uuid::new_v4(),
+            pattern: "old:*".to_string(),
+            key_type: "*".to_string(),
+            action,
+            dry_run,
+            batch_size: Some(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_dry_run() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        let summary = _run_bulk_job(&app_state, request(BulkAction::Delete, true), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(summary.matched, 25);
+        assert_eq!(summary.affected, 0);
+        assert_eq!(summary.samples.len(), SAMPLE_SIZE);
+        assert!(summary.samples.iter().all(|k| k.starts_with("old:")));
+
+        let state = app_state.lock().await;
+        let mut connection = state
+            .get_redis_client()
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .unwrap();
+        let remaining: Vec<String> = connection.keys("old:*").await.unwrap();
+        assert_eq!(remaining.len(), 25);
+
+        let audit = AuditEntry::get(state.get_db_connection().unwrap()).unwrap();
+        assert_eq!(audit.len(), 1);
+        assert!(audit[0].dry_run);
+        assert_eq!(audit[0].matched, 25);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_delete_reports_progress() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+        let applied = std::sync::Mutex::new(vec![]);
+
+        let summary = _run_bulk_job(&app_state, request(BulkAction::Delete, false), |p| {
+            if p.phase == BulkJobPhase::Applying {
+                applied.lock().unwrap().push(p.processed);
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(summary.matched, 25);
+        assert_eq!(summary.affected, 25);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(*applied.lock().unwrap(), vec![10, 20, 25]);
+
+        let state = app_state.lock().await;
+        let mut connection = state
+            .get_redis_client()
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .unwrap();
+        let remaining: Vec<String> = connection.keys("*").await.unwrap();
+        assert_eq!(remaining, vec!["keep".to_string()]);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_rename_prefix() {
+        let (host, port, container) = run_redis_container(PORT).await;
+        let app_state = setup_state(host, port).await;
+
+        let action = BulkAction::RenamePrefix {
+            from: "old:".to_string(),
+            to: "new:".to_string(),
+        };
+        let summary = _run_bulk_job(&app_state, request(action, false), |_| {})
+            .await
+            .unwrap();
+        assert_eq!(summary.affected, 25);
+
+        let state = app_state.lock().await;
+        let mut connection = state
+            .get_redis_client()
+            .unwrap()
+            .get_multiplexed_async_connection()
+            .await
+            .unwrap();
+        let renamed: Vec<String> = connection.keys("new:*").await.unwrap();
+        assert_eq!(renamed.len(), 25);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_no_redis_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _run_bulk_job(&app_state, request(BulkAction::Persist, false), |_| {}).await;
+
+        assert!(result.is_err());
+        assert_eq!(result.err().unwrap(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_job_read_only_server() {
+        let mut app_state = AppState::new();
+        app_state.set_server(Some(Server {
+            read_only: true,
+            ..Server::default()
+        }));
+        let app_state = Mutex::new(app_state);
+
+        let result = _run_bulk_job(&app_state, request(BulkAction::Delete, false), |_| {}).await;
+        assert_eq!(result.unwrap_err(), AppError::WriteBlocked);
+
+        // Dry runs only read, so they get as far as the missing client.
+        let result = _run_bulk_job(&app_state, request(BulkAction::Delete, true), |_| {}).await;
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
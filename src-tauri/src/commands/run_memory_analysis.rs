@@ -0,0 +1,350 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{MemoryReport, Model},
+    services::{
+        get_connection, keyspace_clients, scan_options, KeyStats, MemoryAnalysis, MemoryAnalyzer,
+        MEMORY_ANALYSIS_PROGRESS_EVENT,
+    },
+};
+use redis::{aio::MultiplexedConnection, AsyncCommands, Client, RedisResult, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+const DEFAULT_TOP: usize = 10;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MemoryAnalysisRequest {
+    /// Generated by the caller so the analysis can be cancelled with `cancel_job`.
+    job_id: Uuid,
+    pattern: String,
+    /// Number of leading key segments keys are grouped by.
+    prefix_depth: u32,
+    separator: Option<String>,
+    /// Number of biggest keys kept per type.
+    top: Option<usize>,
+    batch_size: Option<usize>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MemoryAnalysisProgress {
+    job_id: Uuid,
+    scanned: u64,
+    /// `DBSIZE` when the analysis started, only an estimate with a pattern.
+    total: u64,
+}
+
+/// Scans the keyspace in batches, reading the memory, size, encoding and TTL of every key,
+/// and stores the aggregated report when a database connection is available.
+async fn _run_memory_analysis<F>(
+    state: &Mutex<AppState>,
+    request: MemoryAnalysisRequest,
+    on_progress: F,
+) -> Result<MemoryReport, AppError>
+where
+    F: Fn(MemoryAnalysisProgress),
+{
+    let (client, cluster, server_id, cancelled) = {
+        let mut app_state = state.lock().await;
+        let client = app_state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        let server_id = app_state.get_server().map_or(Uuid::nil(), |s| s.id);
+        let cluster = app_state.get_cluster().is_some();
+        (
+            client,
+            cluster,
+            server_id,
+            app_state.register_job(request.job_id),
+        )
+    };
+
+    let result = match keyspace_clients(&client, cluster).await {
+        Ok(clients) => execute_memory_analysis(&clients, &request, &cancelled, &on_progress).await,
+        Err(e) => Err(e),
+    };
+
+    let mut app_state = state.lock().await;
+    app_state.finish_job(&request.job_id);
+    let (analysis, cancelled) = result?;
+
+    let report = MemoryReport {
+        server_id,
+        pattern: request.pattern,
+        separator: request.separator.unwrap_or_else(|| ":".to_string()),
+        prefix_depth: request.prefix_depth,
+        cancelled,
+        analysis,
+        ..MemoryReport::default()
+    };
+    // The report is still returned when it cannot be kept for later comparisons.
+    match app_state.get_db_connection() {
+        Some(db) => match report.create(db) {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                log::error!("Failed to store {}: {}", report, e);
+                Ok(report)
+            }
+        },
+        None => {
+            log::warn!("Database connection is not ready, memory report was not stored");
+            Ok(report)
+        }
+    }
+}
+
+/// Every node of `clients` is scanned in turn, the primaries of a cluster each hold a share of
+/// the keyspace.
+async fn execute_memory_analysis<F>(
+    clients: &[Client],
+    request: &MemoryAnalysisRequest,
+    cancelled: &AtomicBool,
+    on_progress: &F,
+) -> Result<(MemoryAnalysis, bool), AppError>
+where
+    F: Fn(MemoryAnalysisProgress),
+{
+    let batch_size = request
+        .batch_size
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+
+    let mut connections = Vec::with_capacity(clients.len());
+    let mut total = 0;
+    for client in clients {
+        let mut connection = get_connection(client).await?;
+        total += redis::cmd("DBSIZE")
+            .query_async::<u64>(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to read the database size: {}", e);
+                AppError::RedisFailed
+            })?;
+        connections.push(connection);
+    }
+
+    let mut analyzer = MemoryAnalyzer::new(
+        request.top.unwrap_or(DEFAULT_TOP),
+        request.separator.clone().unwrap_or_else(|| ":".to_string()),
+        request.prefix_depth as usize,
+    );
+    let mut scanned = 0;
+    for mut connection in connections {
+        let mut pipeline_connection = connection.clone();
+        let mut keys_iter = connection
+            .scan_options::<String>(scan_options(&request.pattern, "*").with_count(batch_size))
+            .await
+            .map_err(|e| {
+                log::error!("Error scanning keys: {:?}", e);
+                AppError::RedisFailed
+            })?;
+
+        let mut batch = Vec::with_capacity(batch_size);
+        loop {
+            let mut done = false;
+            while batch.len() < batch_size {
+                match keys_iter.next_item().await {
+                    Some(key) => batch.push(key.map_err(|_| {
+                        log::error!("Error retrieving key during scan");
+                        AppError::RedisFailed
+                    })?),
+                    None => {
+                        done = true;
+                        break;
+                    }
+                }
+            }
+            if cancelled.load(Ordering::Relaxed) {
+                return Ok((analyzer.finish(), true));
+            }
+
+            if !batch.is_empty() {
+                scanned += batch.len() as u64;
+                for stats in read_key_stats(&mut pipeline_connection, &batch).await? {
+                    analyzer.add(stats);
+                }
+                batch.clear();
+                on_progress(MemoryAnalysisProgress {
+                    job_id: request.job_id,
+                    scanned,
+                    total,
+                });
+            }
+            if done {
+                break;
+            }
+        }
+    }
+
+    Ok((analyzer.finish(), cancelled.load(Ordering::Relaxed)))
+}
+
+/// Reads the stats of a batch of keys in two round trips, the commands giving the size
+/// depend on the type. Keys deleted since they were scanned are skipped.
+async fn read_key_stats(
+    connection: &mut MultiplexedConnection,
+    keys: &[String],
+) -> Result<Vec<KeyStats>, AppError> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.key_type(key);
+    }
+    let types: Vec<String> = pipe.query_async(connection).await.map_err(|e| {
+        log::error!("Failed to read key types: {}", e);
+        AppError::RedisFailed
+    })?;
+
+    let mut pipe = redis::pipe();
+    pipe.ignore_errors();
+    for (key, key_type) in keys.iter().zip(&types) {
+        pipe.cmd("MEMORY").arg("USAGE").arg(key);
+        pipe.cmd("OBJECT").arg("ENCODING").arg(key);
+        pipe.ttl(key);
+        match key_type.as_str() {
+            "string" => pipe.strlen(key),
+            "list" => pipe.llen(key),
+            "hash" => pipe.hlen(key),
+            "set" => pipe.scard(key),
+            "zset" => pipe.zcard(key),
+            "stream" => pipe.xlen(key),
+            _ => pipe.cmd("EXISTS").arg(key),
+        };
+    }
+    let results: Vec<RedisResult<RedisValue>> =
+        pipe.query_async(connection).await.map_err(|e| {
+            log::error!("Failed to read key stats: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    let int = |result: &RedisResult<RedisValue>| match result {
+        Ok(RedisValue::Int(value)) => Some(*value),
+        _ => None,
+    };
+    let text = |result: &RedisResult<RedisValue>| match result {
+        Ok(RedisValue::BulkString(bytes)) => String::from_utf8_lossy(bytes).into_owned(),
+        Ok(RedisValue::SimpleString(value)) => value.clone(),
+        _ => String::new(),
+    };
+
+    Ok(keys
+        .iter()
+        .zip(&types)
+        .zip(results.chunks_exact(4))
+        .filter(|((_, key_type), _)| key_type.as_str() != "none")
+        .map(|((key, key_type), results)| {
+            let known_type =
+                ["string", "list", "hash", "set", "zset", "stream"].contains(&key_type.as_str());
+            KeyStats {
+                key: key.clone(),
+                key_type: key_type.clone(),
+                memory: int(&results[0]).unwrap_or_default().max(0) as u64,
+                encoding: text(&results[1]),
+                ttl: int(&results[2]).unwrap_or(-1),
+                cardinality: match known_type {
+                    true => int(&results[3]).unwrap_or_default().max(0) as u64,
+                    false => 0,
+                },
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn run_memory_analysis(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    request: MemoryAnalysisRequest,
+) -> Result<MemoryReport, AppError> {
+    _run_memory_analysis(state.inner(), request, |progress| {
+        if let Err(e) = app.emit(MEMORY_ANALYSIS_PROGRESS_EVENT, progress) {
+            log::warn!("Failed to emit memory analysis progress: {}", e);
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    fn request(prefix_depth: u32) -> MemoryAnalysisRequest {
+        MemoryAnalysisRequest {
+            job_id: Uuid::new_v4(),
+            pattern: String::new(),
+            prefix_depth,
+            separator: None,
+            top: Some(2),
+            batch_size: Some(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_memory_analysis_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _run_memory_analysis(&app_state, request(1), |_| {}).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_run_memory_analysis() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            for i in 0..25 {
+                let _: () = connection
+                    .hset(format!("user:{}", i), "name", "x".repeat(i * 10))
+                    .await
+                    .unwrap();
+            }
+            let _: () = connection.set_ex("session:1", "token", 60).await.unwrap();
+            let _: () = connection.rpush("queue", &[1, 2, 3]).await.unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let progress = std::sync::Mutex::new(vec![]);
+        let report = _run_memory_analysis(&app_state, request(1), |p| {
+            progress.lock().unwrap().push(p.scanned)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(progress.lock().unwrap().last(), Some(&27));
+        assert_eq!(report.analysis.key_count, 27);
+        assert!(!report.cancelled);
+        let hashes = report
+            .analysis
+            .types
+            .iter()
+            .find(|t| t.key_type == "hash")
+            .unwrap();
+        assert_eq!(hashes.keys, 25);
+        assert_eq!(hashes.biggest[0].key, "user:24");
+        assert_eq!(hashes.biggest.len(), 2);
+        assert_eq!(report.analysis.prefixes[0].prefix, "user");
+        assert_eq!(report.analysis.ttl.expiring_keys, 1);
+
+        let app_state = app_state.lock().await;
+        let stored = MemoryReport::find_by_id(
+            &report.id.to_string(),
+            app_state.get_db_connection().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(stored.analysis, report.analysis);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{
+        get_connection, keyspace_clients, scan_options, TtlAnalysis, TtlAnalyzer,
+        TTL_ANALYSIS_PROGRESS_EVENT,
+    },
+};
+use redis::{aio::MultiplexedConnection, AsyncCommands, Client};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+/// How keys are picked for the analysis.
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum TtlSampling {
+    /// Reads `samples` keys picked with `RANDOMKEY`, cheap on big databases.
+    Random { samples: u64 },
+    /// Reads every key matching `pattern`.
+    Scan { pattern: String },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TtlAnalysisRequest {
+    /// Generated by the caller so the analysis can be cancelled with `cancel_job`.
+    job_id: Uuid,
+    sampling: TtlSampling,
+    /// Number of leading key segments keys are grouped by.
+    prefix_depth: u32,
+    separator: Option<String>,
+    batch_size: Option<usize>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TtlAnalysisProgress {
+    job_id: Uuid,
+    sampled: u64,
+    /// Number of samples requested, or `DBSIZE` when scanning.
+    total: u64,
+}
+
+/// Histograms the TTLs of sampled or scanned keys per prefix.
+async fn _run_ttl_analysis<F>(
+    state: &Mutex<AppState>,
+    request: TtlAnalysisRequest,
+    on_progress: F,
+) -> Result<TtlAnalysis, AppError>
+where
+    F: Fn(TtlAnalysisProgress),
+{
+    let (client, cluster, cancelled) = {
+        let mut app_state = state.lock().await;
+        let client = app_state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        let cluster = app_state.get_cluster().is_some();
+        (client, cluster, app_state.register_job(request.job_id))
+    };
+
+    let result = match keyspace_clients(&client, cluster).await {
+        Ok(clients) => execute_ttl_analysis(&clients, &request, &cancelled, &on_progress).await,
+        Err(e) => Err(e),
+    };
+
+    state.lock().await.finish_job(&request.job_id);
+    result
+}
+
+/// Every node of `clients` is read in turn, random samples are spread over the primaries of
+/// a cluster in proportion to their number of keys.
+async fn execute_ttl_analysis<F>(
+    clients: &[Client],
+    request: &TtlAnalysisRequest,
+    cancelled: &AtomicBool,
+    on_progress: &F,
+) -> Result<TtlAnalysis, AppError>
+where
+    F: Fn(TtlAnalysisProgress),
+{
+    let batch_size = request
+        .batch_size
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_BATCH_SIZE);
+
+    let mut nodes = Vec::with_capacity(clients.len());
+    let mut db_size = 0;
+    for client in clients {
+        let mut connection = get_connection(client).await?;
+        let size: u64 = redis::cmd("DBSIZE")
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to read the database size: {}", e);
+                AppError::RedisFailed
+            })?;
+        db_size += size;
+        nodes.push((connection, size));
+    }
+
+    let mut analyzer = TtlAnalyzer::new(
+        request.separator.clone().unwrap_or_else(|| ":".to_string()),
+        request.prefix_depth as usize,
+    );
+    let mut sampled = 0;
+
+    match &request.sampling {
+        TtlSampling::Random { samples } => {
+            let total = if db_size == 0 { 0 } else { *samples };
+            let mut assigned = 0;
+            for (index, (connection, size)) in nodes.iter_mut().enumerate() {
+                // The last node takes what rounding left over.
+                let node_total = match index + 1 == clients.len() {
+                    true => total - assigned,
+                    false => total * *size / db_size.max(1),
+                };
+                assigned += node_total;
+
+                let mut node_sampled = 0;
+                while node_sampled < node_total && !cancelled.load(Ordering::Relaxed) {
+                    let count = (node_total - node_sampled).min(batch_size as u64);
+                    let mut pipe = redis::pipe();
+                    for _ in 0..count {
+                        pipe.cmd("RANDOMKEY");
+                    }
+                    let keys: Vec<Option<String>> =
+                        pipe.query_async(connection).await.map_err(|e| {
+                            log::error!("Failed to sample random keys: {}", e);
+                            AppError::RedisFailed
+                        })?;
+                    let keys: Vec<String> = keys.into_iter().flatten().collect();
+                    let added = add_ttls(connection, &keys, &mut analyzer).await?;
+                    // The node was emptied, or its keys expire faster than they are sampled.
+                    if added == 0 {
+                        break;
+                    }
+
+                    node_sampled += added;
+                    sampled += added;
+                    on_progress(TtlAnalysisProgress {
+                        job_id: request.job_id,
+                        sampled,
+                        total,
+                    });
+                }
+            }
+            Ok(analyzer.finish(db_size))
+        }
+        TtlSampling::Scan { pattern } => {
+            'nodes: for (mut connection, _) in nodes {
+                let mut pipeline_connection = connection.clone();
+                let mut keys_iter = connection
+                    .scan_options::<String>(scan_options(pattern, "*").with_count(batch_size))
+                    .await
+                    .map_err(|e| {
+                        log::error!("Error scanning keys: {:?}", e);
+                        AppError::RedisFailed
+                    })?;
+
+                let mut batch = Vec::with_capacity(batch_size);
+                loop {
+                    let mut done = false;
+                    while batch.len() < batch_size {
+                        match keys_iter.next_item().await {
+                            Some(key) => batch.push(key.map_err(|_| {
+                                log::error!("Error retrieving key during scan");
+                                AppError::RedisFailed
+                            })?),
+                            None => {
+                                done = true;
+                                break;
+                            }
+                        }
+                    }
+                    if cancelled.load(Ordering::Relaxed) {
+                        break 'nodes;
+                    }
+
+                    if !batch.is_empty() {
+                        sampled +=
+                            add_ttls(&mut pipeline_connection, &batch, &mut analyzer).await?;
+                        batch.clear();
+                        on_progress(TtlAnalysisProgress {
+                            job_id: request.job_id,
+                            sampled,
+                            total: db_size,
+                        });
+                    }
+                    if done {
+                        break;
+                    }
+                }
+            }
+            // Every matching key was read, so nothing is scaled up.
+            Ok(analyzer.finish(sampled))
+        }
+    }
+}
+
+/// Reads the TTL of a batch of keys, keys deleted since they were picked are skipped.
+/// Returns how many keys were added to the analysis.
+async fn add_ttls(
+    connection: &mut MultiplexedConnection,
+    keys: &[String],
+    analyzer: &mut TtlAnalyzer,
+) -> Result<u64, AppError> {
+    if keys.is_empty() {
+        return Ok(0);
+    }
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.ttl(key);
+    }
+    let ttls: Vec<i64> = pipe.query_async(connection).await.map_err(|e| {
+        log::error!("Failed to read key TTLs: {}", e);
+        AppError::RedisFailed
+    })?;
+
+    let mut added = 0;
+    for (key, ttl) in keys.iter().zip(ttls) {
+        if ttl != -2 {
+            analyzer.add(key, ttl);
+            added += 1;
+        }
+    }
+    Ok(added)
+}
+
+#[tauri::command]
+pub async fn run_ttl_analysis(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    request: TtlAnalysisRequest,
+) -> Result<TtlAnalysis, AppError> {
+    _run_ttl_analysis(state.inner(), request, |progress| {
+        if let Err(e) = app.emit(TTL_ANALYSIS_PROGRESS_EVENT, progress) {
+            log::warn!("Failed to emit TTL analysis progress: {}", e);
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    fn request(sampling: TtlSampling) -> TtlAnalysisRequest {
+        TtlAnalysisRequest {
+            job_id: Uuid::new_v4(),
+            sampling,
+            prefix_depth: 1,
+            separator: None,
+            batch_size: Some(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_ttl_analysis_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _run_ttl_analysis(
+            &app_state,
+            request(TtlSampling::Random { samples: 10 }),
+            |_| {},
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_run_ttl_analysis() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            for i in 0..15 {
+                let _: () = connection
+                    .set_ex(format!("session:{}", i), i, 600)
+                    .await
+                    .unwrap();
+                let _: () = connection.set(format!("user:{}", i), i).await.unwrap();
+            }
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let analysis = _run_ttl_analysis(
+            &app_state,
+            request(TtlSampling::Scan {
+                pattern: String::new(),
+            }),
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(analysis.sampled, 30);
+        assert_eq!(analysis.expiring_within_hour, 15);
+        let users = analysis
+            .prefixes
+            .iter()
+            .find(|p| p.prefix == "user")
+            .unwrap();
+        assert!(users.mostly_persistent);
+
+        let progressed = std::sync::Mutex::new(0);
+        let analysis = _run_ttl_analysis(
+            &app_state,
+            request(TtlSampling::Random { samples: 25 }),
+            |progress| *progressed.lock().unwrap() = progress.sampled,
+        )
+        .await
+        .unwrap();
+        assert_eq!(analysis.sampled, 25);
+        // Progress counts the TTLs read, not the keys asked for.
+        assert_eq!(*progressed.lock().unwrap(), analysis.sampled);
+        assert_eq!(analysis.population, 30);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Script},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Adds a script to a server's library, or replaces the name and body of an existing one.
+async fn _save_script(
+    state: &Mutex<AppState>,
+    id: Option<Uuid>,
+    server_id: Uuid,
+    name: String,
+    body: String,
+) -> Result<Script, AppError> {
+    if body.trim().is_empty() {
+        log::warn!("Refusing to save an empty script named {}", name);
+        return Err(AppError::InvalidCommand);
+    }
+
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    match id {
+        Some(id) => {
+            let existing = Script::find_by_id(&id.to_string(), db_connection)?;
+            let script = Script {
+                id,
+                created_at: existing.created_at,
+                ..Script::from_payload(server_id, name, body)
+            };
+            script.save(db_connection)?;
+            Ok(script)
+        }
+        None => Script::from_payload(server_id, name, body).create(db_connection),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_script(
+    state: State<'_, Mutex<AppState>>,
+    id: Option<Uuid>,
+    server_id: Uuid,
+    name: String,
+    body: String,
+) -> Result<Script, AppError> {
+    _save_script(state.inner(), id, server_id, name, body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_save_and_update_script() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+        let server_id = Uuid::now_v7();
+
+        let script = _save_script(
+            &app_state,
+            None,
+            server_id,
+            "One".to_string(),
+            "return 1".to_string(),
+        )
+        .await
+        .unwrap();
+        assert!(!script.id.is_nil());
+        assert_eq!(script.sha1, Script::sha1_of("return 1"));
+
+        let updated = _save_script(
+            &app_state,
+            Some(script.id),
+            server_id,
+            "Two".to_string(),
+            "return 2".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.id, script.id);
+        assert_eq!(updated.sha1, Script::sha1_of("return 2"));
+
+        let state = app_state.lock().await;
+        let scripts =
+            Script::get_for_server(&server_id, state.get_db_connection().unwrap()).unwrap();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].name, "Two");
+    }
+
+    #[tokio::test]
+    async fn test_save_empty_script() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _save_script(
+            &app_state,
+            None,
+            Uuid::now_v7(),
+            "Empty".to_string(),
+            "\n".to_string(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Snippet},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+async fn _save_snippet(
+    state: &Mutex<AppState>,
+    server_id: Uuid,
+    name: String,
+    command: String,
+) -> Result<Snippet, AppError> {
+    if command.trim().is_empty() {
+        log::warn!("Refusing to save an empty snippet named {}", name);
+        return Err(AppError::InvalidCommand);
+    }
+
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Snippet::from_payload(server_id, name, command).create(db_connection)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_snippet(
+    state: State<'_, Mutex<AppState>>,
+    server_id: Uuid,
+    name: String,
+    command: String,
+) -> Result<Snippet, AppError> {
+    _save_snippet(state.inner(), server_id, name, command).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_save_snippet_extracts_parameters() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let snippet = _save_snippet(
+            &app_state,
+            Uuid::now_v7(),
+            "Session".to_string(),
+            "TTL session:{{id}}".to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!snippet.id.is_nil());
+        assert_eq!(snippet.parameters, vec!["id".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_save_empty_snippet() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _save_snippet(
+            &app_state,
+            Uuid::now_v7(),
+            "Empty".to_string(),
+            " ".to_string(),
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    core::{AppError, AppState},
+    services::ServerConnection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reports, for each SHA1, whether the server has the script cached.
+async fn _script_exists(
+    state: &Mutex<AppState>,
+    sha1s: Vec<String>,
+) -> Result<Vec<bool>, AppError> {
+    if sha1s.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let (client, cluster) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.get_cluster().cloned())
+    };
+    let mut connection = ServerConnection::open(&client, cluster.as_ref()).await?;
+
+    redis::cmd("SCRIPT")
+        .arg("EXISTS")
+        .arg(&sha1s)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to check the script cache: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn script_exists(
+    state: State<'_, Mutex<AppState>>,
+    sha1s: Vec<String>,
+) -> Result<Vec<bool>, AppError> {
+    _script_exists(state.inner(), sha1s).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Script, Server},
+        services::test_connection,
+        tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_script_exists() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: String = redis::cmd("SCRIPT")
+                .arg("LOAD")
+                .arg("return 1")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let exists = _script_exists(
+            &app_state,
+            vec![Script::sha1_of("return 1"), Script::sha1_of("return 2")],
+        )
+        .await
+        .unwrap();
+        assert_eq!(exists, vec![true, false]);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_script_exists_without_sha1s() {
+        let app_state = Mutex::new(AppState::new());
+        assert_eq!(_script_exists(&app_state, vec![]).await, Ok(vec![]));
+    }
+}
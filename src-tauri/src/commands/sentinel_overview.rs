@@ -0,0 +1,46 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{sentinel_overview as read_sentinel_overview, SentinelOverview},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Reads the master, replicas and sentinels of the opened Sentinel-managed server.
+async fn _sentinel_overview(state: &Mutex<AppState>) -> Result<SentinelOverview, AppError> {
+    let settings = {
+        let state = state.lock().await;
+        let server = state.get_server().ok_or_else(|| {
+            log::error!("No server is opened");
+            AppError::RedisFailed
+        })?;
+        server.sentinel.clone().ok_or_else(|| {
+            log::warn!("{} is not managed by Sentinel", server);
+            AppError::InvalidCommand
+        })?
+    };
+
+    read_sentinel_overview(&settings).await
+}
+
+#[tauri::command]
+pub async fn sentinel_overview(
+    state: State<'_, Mutex<AppState>>,
+) -> Result<SentinelOverview, AppError> {
+    _sentinel_overview(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Server;
+
+    #[tokio::test]
+    async fn test_sentinel_overview_of_direct_server() {
+        let mut app_state = AppState::new();
+        app_state.set_server(Some(Server::default()));
+        let app_state = Mutex::new(app_state);
+
+        let result = _sentinel_overview(&app_state).await;
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+}
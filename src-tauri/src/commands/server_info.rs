@@ -0,0 +1,78 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+    utils::{parse_server_info, ServerInfo},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Runs `INFO everything`, which also includes `commandstats`, `errorstats` and
+/// `latencystats` unlike a plain `INFO`.
+async fn _server_info(state: &Mutex<AppState>) -> Result<ServerInfo, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let info: String = redis::cmd("INFO")
+        .arg("everything")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get server info: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_server_info(&info))
+}
+
+#[tauri::command]
+pub async fn server_info(state: State<'_, Mutex<AppState>>) -> Result<ServerInfo, AppError> {
+    _server_info(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_server_info() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+        {
+            let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+            let _: () = redis::cmd("SET")
+                .arg("greeting")
+                .arg("hello")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client));
+        let app_state = Mutex::new(app_state);
+
+        let info = _server_info(&app_state).await.unwrap();
+        let info = serde_json::to_value(&info).unwrap();
+        assert_eq!(info["server"]["tcp_port"], 6379);
+        assert_eq!(info["keyspace"]["db0"]["keys"], 1);
+        assert_eq!(info["commandstats"]["set"]["calls"], 1);
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_server_info_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _server_info(&app_state).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
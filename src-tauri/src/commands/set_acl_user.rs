@@ -0,0 +1,142 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, parse_acl_user, AclSetUserResult},
+    utils::server_error_message,
+};
+use redis::Value as RedisValue;
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Creates a user or applies rules to an existing one, e.g. `on`, `>password`, `~user:*`
+/// or `+@read`, and returns the user as stored. Rejected rules are returned with the server's
+/// message. `ACL SETUSER` is guarded by default, so a token from `confirm_command` is usually
+/// required.
+async fn _set_acl_user(
+    state: &Mutex<AppState>,
+    username: String,
+    rules: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<AclSetUserResult, AppError> {
+    let mut args = vec![
+        b"ACL".to_vec(),
+        b"SETUSER".to_vec(),
+        username.clone().into_bytes(),
+    ];
+    args.extend(rules.iter().map(|rule| rule.as_bytes().to_vec()));
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let result = redis::cmd("ACL")
+        .arg("SETUSER")
+        .arg(&username)
+        .arg(&rules)
+        .query_async::<()>(&mut connection)
+        .await;
+    if let Err(e) = result {
+        return match e.code() {
+            Some(code) => Ok(AclSetUserResult {
+                user: None,
+                error: Some(server_error_message(code, e.detail())),
+            }),
+            None => {
+                log::error!("Failed to set ACL user {}: {}", username, e);
+                Err(AppError::RedisFailed)
+            }
+        };
+    }
+
+    let reply: RedisValue = redis::cmd("ACL")
+        .arg("GETUSER")
+        .arg(&username)
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get ACL user {}: {}", username, e);
+            AppError::RedisFailed
+        })?;
+    let user = parse_acl_user(&username, &reply).ok_or_else(|| {
+        log::error!("ACL user {} is missing after being set", username);
+        AppError::RedisFailed
+    })?;
+    Ok(AclSetUserResult {
+        user: Some(user),
+        error: None,
+    })
+}
+
+#[tauri::command]
+pub async fn set_acl_user(
+    state: State<'_, Mutex<AppState>>,
+    username: String,
+    rules: Vec<String>,
+    confirmation: Option<Uuid>,
+) -> Result<AclSetUserResult, AppError> {
+    _set_acl_user(state.inner(), username, rules, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_set_acl_user_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_acl_user(&app_state, "reader".to_string(), vec![], None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_set_acl_user() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        let token = app_state.issue_confirmation("ACL SETUSER".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let rules = ["on", ">secret", "~user:*", "+@read"]
+            .iter()
+            .map(|rule| rule.to_string())
+            .collect();
+        let result = _set_acl_user(&app_state, "reader".to_string(), rules, Some(token))
+            .await
+            .unwrap();
+        assert_eq!(result.error, None);
+        let user = result.user.unwrap();
+        assert!(user.enabled);
+        assert_eq!(user.password_hashes.len(), 1);
+        assert_eq!(user.permissions.keys, vec!["~user:*"]);
+        assert!(user.categories.contains(&"+@read".to_string()));
+
+        let token = app_state
+            .lock()
+            .await
+            .issue_confirmation("ACL SETUSER".to_string());
+        let rules = vec!["+@nosuchcategory".to_string()];
+        let result = _set_acl_user(&app_state, "reader".to_string(), rules, Some(token))
+            .await
+            .unwrap();
+        assert_eq!(result.user, None);
+        assert!(result
+            .error
+            .is_some_and(|error| error.starts_with("ERR") && error.contains("+@nosuchcategory")));
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, set_no_evict},
+    utils::server_error_message,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Turns `CLIENT NO-EVICT` on or off for the app's long-lived connections, so they are not
+/// evicted when clients exceed `maxmemory-clients`. Open connections are switched right
+/// away and the ones opened later follow, until the app is closed. `MONITOR` is not
+/// covered, its connection takes no other command.
+///
+/// Returns the server's message when it rejects the command, e.g. before Redis 7.0.
+async fn _set_client_no_evict(
+    state: &Mutex<AppState>,
+    enabled: bool,
+) -> Result<Option<String>, AppError> {
+    let (client, connections) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.long_lived_connections())
+    };
+
+    // Tried on a connection of its own first, so an unsupported server changes nothing.
+    let mut connection = get_connection(&client).await?;
+    if let Err(e) = set_no_evict(&mut connection, enabled).await {
+        return match e.code() {
+            Some(code) => Ok(Some(server_error_message(code, e.detail()))),
+            None => {
+                log::error!("Failed to set CLIENT NO-EVICT: {}", e);
+                Err(AppError::RedisFailed)
+            }
+        };
+    }
+
+    state.lock().await.set_client_no_evict(enabled);
+    for mut connection in connections {
+        if let Err(e) = set_no_evict(&mut connection, enabled).await {
+            log::warn!("Failed to set CLIENT NO-EVICT on an open connection: {}", e);
+        }
+    }
+    Ok(None)
+}
+
+#[tauri::command]
+pub async fn set_client_no_evict(
+    state: State<'_, Mutex<AppState>>,
+    enabled: bool,
+) -> Result<Option<String>, AppError> {
+    _set_client_no_evict(state.inner(), enabled).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::Server,
+        services::{parse_client_list, test_connection, PubSubSession, CLIENT_NAME},
+        tests::{run_redis_container, run_redis_version_container},
+    };
+    use std::sync::atomic::Ordering;
+
+    async fn app_state(host: String, port: u16) -> Mutex<AppState> {
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(test_connection(&server).await.unwrap()));
+        Mutex::new(app_state)
+    }
+
+    #[tokio::test]
+    async fn test_set_client_no_evict_on_open_connections() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let app_state = app_state(host, port).await;
+        {
+            let mut state = app_state.lock().await;
+            let client = state.get_redis_client().cloned().unwrap();
+            let session = PubSubSession::start(&client, false, |_| {}).await.unwrap();
+            state.set_pubsub(Some(session));
+        }
+
+        let result = _set_client_no_evict(&app_state, true).await.unwrap();
+        assert_eq!(result, None);
+
+        let state = app_state.lock().await;
+        assert!(state.client_no_evict().load(Ordering::Relaxed));
+        let mut connection = state.get_pubsub().unwrap().connection();
+        let info: String = redis::cmd("CLIENT")
+            .arg("INFO")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        let pubsub = &parse_client_list(&info)[0];
+        assert_eq!(pubsub.name, *CLIENT_NAME);
+        assert!(pubsub.flags.contains('e'));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_set_client_no_evict_before_redis_7() {
+        let (host, port, container) = run_redis_version_container("6.2-alpine", 6379).await;
+        let app_state = app_state(host, port).await;
+
+        let result = _set_client_no_evict(&app_state, true).await.unwrap();
+        assert!(result.unwrap().starts_with("ERR"));
+        assert!(!app_state
+            .lock()
+            .await
+            .client_no_evict()
+            .load(Ordering::Relaxed));
+
+        container.rm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_set_client_no_evict_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_client_no_evict(&app_state, true).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
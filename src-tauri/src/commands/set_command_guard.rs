@@ -0,0 +1,74 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{CommandGuard, Setting, COMMAND_GUARD_SETTING},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Replaces the guard list, commands are stored uppercase without surrounding whitespace.
+async fn _set_command_guard(
+    state: &Mutex<AppState>,
+    guard: CommandGuard,
+) -> Result<CommandGuard, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    let guard = CommandGuard {
+        commands: guard
+            .commands
+            .iter()
+            .map(|command| command.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|command| !command.is_empty())
+            .map(|command| command.to_uppercase())
+            .collect(),
+    };
+
+    Setting::set_value(COMMAND_GUARD_SETTING, &guard, db_connection)?;
+    Ok(guard)
+}
+
+#[tauri::command]
+pub async fn set_command_guard(
+    state: State<'_, Mutex<AppState>>,
+    guard: CommandGuard,
+) -> Result<CommandGuard, AppError> {
+    _set_command_guard(state.inner(), guard).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_set_command_guard_normalizes_commands() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let guard = CommandGuard {
+            commands: vec![" flushall ".into(), "config   set".into(), "".into()],
+        };
+        let stored = _set_command_guard(&app_state, guard).await.unwrap();
+        assert_eq!(
+            stored.commands,
+            vec!["FLUSHALL".to_string(), "CONFIG SET".to_string()]
+        );
+
+        let state = app_state.lock().await;
+        let loaded: Option<CommandGuard> =
+            Setting::get_value(COMMAND_GUARD_SETTING, state.get_db_connection().unwrap()).unwrap();
+        assert_eq!(loaded, Some(stored));
+    }
+
+    #[tokio::test]
+    async fn test_set_command_guard_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_command_guard(&app_state, CommandGuard::default()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
@@ -0,0 +1,123 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, ConfigSetResult},
+    utils::server_error_message,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Sets one or many parameters. `CONFIG SET` is guarded by default, so a token from
+/// `confirm_command` is usually required.
+///
+/// Parameters are set one per call, as setting several at once needs Redis 7, so a
+/// rejected value does not stop the others. Rejections are returned with the server's message.
+async fn _set_config(
+    state: &Mutex<AppState>,
+    parameters: Vec<(String, String)>,
+    confirmation: Option<Uuid>,
+) -> Result<Vec<ConfigSetResult>, AppError> {
+    if parameters.is_empty() {
+        return Err(AppError::InvalidCommand);
+    }
+    let mut args = vec![b"CONFIG".to_vec(), b"SET".to_vec()];
+    for (name, value) in &parameters {
+        args.push(name.as_bytes().to_vec());
+        args.push(value.as_bytes().to_vec());
+    }
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let mut results = Vec::with_capacity(parameters.len());
+    for (name, value) in parameters {
+        let result = redis::cmd("CONFIG")
+            .arg("SET")
+            .arg(&name)
+            .arg(&value)
+            .query_async::<()>(&mut connection)
+            .await;
+        let error = match result {
+            Ok(()) => None,
+            Err(e) => match e.code() {
+                Some(code) => Some(server_error_message(code, e.detail())),
+                None => {
+                    log::error!("Failed to set {}: {}", name, e);
+                    return Err(AppError::RedisFailed);
+                }
+            },
+        };
+        results.push(ConfigSetResult { name, value, error });
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub async fn set_config(
+    state: State<'_, Mutex<AppState>>,
+    parameters: Vec<(String, String)>,
+    confirmation: Option<Uuid>,
+) -> Result<Vec<ConfigSetResult>, AppError> {
+    _set_config(state.inner(), parameters, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_set_config_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let parameters = vec![("hz".to_string(), "20".to_string())];
+        let result = _set_config(&app_state, parameters, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_set_config_returns_server_errors() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(client.clone()));
+        let token = app_state.issue_confirmation("CONFIG SET".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let parameters = vec![
+            ("maxmemory-policy".to_string(), "allkeys-lru".to_string()),
+            ("hz".to_string(), "fast".to_string()),
+        ];
+        let results = _set_config(&app_state, parameters, Some(token))
+            .await
+            .unwrap();
+        assert_eq!(results[0].error, None);
+        assert!(results[1].error.as_ref().unwrap().starts_with("ERR"));
+        assert!(results[1].error.as_ref().unwrap().contains("hz"));
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        let policy: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("maxmemory-policy")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(policy, vec!["maxmemory-policy", "allkeys-lru"]);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{HistoryRetention, Setting, HISTORY_RETENTION_SETTING},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Stores the retention, it is applied the next time a command is recorded.
+async fn _set_history_retention(
+    state: &Mutex<AppState>,
+    retention: HistoryRetention,
+) -> Result<HistoryRetention, AppError> {
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Setting::set_value(HISTORY_RETENTION_SETTING, &retention, db_connection)?;
+    Ok(retention)
+}
+
+#[tauri::command]
+pub async fn set_history_retention(
+    state: State<'_, Mutex<AppState>>,
+    retention: HistoryRetention,
+) -> Result<HistoryRetention, AppError> {
+    _set_history_retention(state.inner(), retention).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::Database, models::CommandHistoryEntry};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_set_history_retention_prunes_on_next_record() {
+        let server_id = Uuid::now_v7();
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let retention = HistoryRetention {
+            max_entries: Some(2),
+            max_age_days: None,
+        };
+        assert_eq!(
+            _set_history_retention(&app_state, retention).await,
+            Ok(retention)
+        );
+
+        let state = app_state.lock().await;
+        let db = state.get_db_connection().unwrap();
+        for command in ["GET a", "GET b", "GET c"] {
+            CommandHistoryEntry::from_payload(server_id, command.to_string(), 10, true)
+                .record(db)
+                .unwrap();
+        }
+
+        let (entries, total) = CommandHistoryEntry::search(&server_id, "", 10, 0, db).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(entries[0].command, "GET c");
+        assert_eq!(entries[1].command, "GET b");
+    }
+
+    #[tokio::test]
+    async fn test_set_history_retention_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_history_retention(&app_state, HistoryRetention::default()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
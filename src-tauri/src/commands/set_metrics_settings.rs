@@ -0,0 +1,73 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{MetricsSettings, Setting, METRICS_SETTINGS_SETTING},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Stores the sampler settings. Persistence applies to the next sample, the interval
+/// and buffer size the next time a server is opened.
+async fn _set_metrics_settings(
+    state: &Mutex<AppState>,
+    settings: MetricsSettings,
+) -> Result<MetricsSettings, AppError> {
+    let settings = MetricsSettings {
+        interval_secs: settings.interval_secs.max(1),
+        capacity: settings.capacity.max(1),
+        ..settings
+    };
+
+    let state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    Setting::set_value(METRICS_SETTINGS_SETTING, &settings, db_connection)?;
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn set_metrics_settings(
+    state: State<'_, Mutex<AppState>>,
+    settings: MetricsSettings,
+) -> Result<MetricsSettings, AppError> {
+    _set_metrics_settings(state.inner(), settings).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_set_metrics_settings() {
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        let app_state = Mutex::new(app_state);
+
+        let settings = MetricsSettings {
+            interval_secs: 0,
+            capacity: 60,
+            persist: true,
+            max_age_days: 1,
+        };
+        let stored = _set_metrics_settings(&app_state, settings).await.unwrap();
+        assert_eq!(stored.interval_secs, 1);
+
+        let state = app_state.lock().await;
+        let db = state.get_db_connection().unwrap();
+        assert_eq!(
+            Setting::get_value::<MetricsSettings>(METRICS_SETTINGS_SETTING, db).unwrap(),
+            Some(stored)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_metrics_settings_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_metrics_settings(&app_state, MetricsSettings::default()).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    core::{AppError, AppState},
+    models::{Model, Server},
+};
+use tauri::State;
+use time::OffsetDateTime;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Changes the read-only flag of a server, taking effect immediately when it is open.
+async fn _set_server_read_only(
+    state: &Mutex<AppState>,
+    id: Uuid,
+    read_only: bool,
+) -> Result<Server, AppError> {
+    let mut state = state.lock().await;
+    let db_connection = state.get_db_connection().ok_or_else(|| {
+        log::error!("Database connection is not ready");
+        AppError::DbNotReady
+    })?;
+
+    let mut server = Server::find_by_id(&id.to_string(), db_connection)?;
+    server.read_only = read_only;
+    server.updated_at = OffsetDateTime::now_utc();
+    server.save(db_connection)?;
+
+    if state.get_server().is_some_and(|open| open.id == server.id) {
+        state.set_server(Some(server.clone()));
+    }
+
+    Ok(server)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_server_read_only(
+    state: State<'_, Mutex<AppState>>,
+    id: Uuid,
+    read_only: bool,
+) -> Result<Server, AppError> {
+    _set_server_read_only(state.inner(), id, read_only).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Database;
+
+    #[tokio::test]
+    async fn test_set_server_read_only_updates_open_server() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server::from_payload("Production".to_string(), "127.0.0.1".to_string(), 6379)
+            .create(&db)
+            .unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(db));
+        app_state.set_server(Some(server.clone()));
+        let app_state = Mutex::new(app_state);
+
+        let updated = _set_server_read_only(&app_state, server.id, true)
+            .await
+            .unwrap();
+        assert!(updated.read_only);
+
+        let state = app_state.lock().await;
+        assert!(state.get_server().unwrap().read_only);
+        let stored =
+            Server::find_by_id(&server.id.to_string(), state.get_db_connection().unwrap()).unwrap();
+        assert!(stored.read_only);
+    }
+
+    #[tokio::test]
+    async fn test_set_server_read_only_no_db() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_server_read_only(&app_state, Uuid::now_v7(), true).await;
+
+        assert_eq!(result.unwrap_err(), AppError::DbNotReady);
+    }
+}
@@ -0,0 +1,112 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::{get_connection, SlowlogConfig},
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Updates both slow log thresholds. `CONFIG SET` is guarded by default, so a token from
+/// `confirm_command` is usually required.
+async fn _set_slowlog_config(
+    state: &Mutex<AppState>,
+    config: SlowlogConfig,
+    confirmation: Option<Uuid>,
+) -> Result<SlowlogConfig, AppError> {
+    let parameters = [
+        (
+            "slowlog-log-slower-than",
+            config.log_slower_than.to_string(),
+        ),
+        ("slowlog-max-len", config.max_len.to_string()),
+    ];
+    let mut args = vec![b"CONFIG".to_vec(), b"SET".to_vec()];
+    for (name, value) in &parameters {
+        args.push(name.as_bytes().to_vec());
+        args.push(value.as_bytes().to_vec());
+    }
+    guard_command(state, &args, confirmation).await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    // One parameter per call, setting several at once needs Redis 7.
+    for (name, value) in parameters {
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg(name)
+            .arg(value)
+            .query_async::<()>(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to set {}: {}", name, e);
+                AppError::RedisFailed
+            })?;
+    }
+
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn set_slowlog_config(
+    state: State<'_, Mutex<AppState>>,
+    config: SlowlogConfig,
+    confirmation: Option<Uuid>,
+) -> Result<SlowlogConfig, AppError> {
+    _set_slowlog_config(state.inner(), config, confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Database, models::Server, services::test_connection, tests::run_redis_container,
+    };
+
+    #[tokio::test]
+    async fn test_set_slowlog_config_requires_confirmation() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _set_slowlog_config(&app_state, SlowlogConfig::default(), None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::ConfirmationRequired);
+    }
+
+    #[tokio::test]
+    async fn test_set_slowlog_config() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_db_connection(Some(Database::new_in_memory().unwrap()));
+        app_state.set_redis_client(Some(client.clone()));
+        let token = app_state.issue_confirmation("CONFIG SET".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let config = SlowlogConfig {
+            log_slower_than: 500,
+            max_len: 1024,
+        };
+        _set_slowlog_config(&app_state, config, Some(token))
+            .await
+            .unwrap();
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        let max_len: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("slowlog-max-len")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(max_len, vec!["slowlog-max-len", "1024"]);
+
+        container.rm().await.unwrap();
+    }
+}
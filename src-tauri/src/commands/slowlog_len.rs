@@ -0,0 +1,44 @@
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn _slowlog_len(state: &Mutex<AppState>) -> Result<u32, AppError> {
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("SLOWLOG")
+        .arg("LEN")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get the slow log length: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn slowlog_len(state: State<'_, Mutex<AppState>>) -> Result<u32, AppError> {
+    _slowlog_len(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slowlog_len_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _slowlog_len(&app_state).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
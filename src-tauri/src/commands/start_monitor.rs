@@ -0,0 +1,94 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{MonitorEvent, MonitorFilter, MonitorSession},
+};
+use tauri::{ipc::Channel, State};
+use tokio::sync::Mutex;
+
+/// Streams the commands processed by the opened server to `on_event`,
+/// replacing the running `MONITOR` session if any.
+async fn _start_monitor<F>(
+    state: &Mutex<AppState>,
+    filter: MonitorFilter,
+    on_event: F,
+) -> Result<(), AppError>
+where
+    F: Fn(MonitorEvent) -> bool + Send + 'static,
+{
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+
+    let session = MonitorSession::start(&client, filter, on_event).await?;
+    state.lock().await.set_monitor(Some(session));
+    Ok(())
+}
+
+/// `MONITOR` slows the server down noticeably, the stream should be stopped with
+/// `stop_monitor` as soon as it is no longer watched.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_monitor(
+    state: State<'_, Mutex<AppState>>,
+    filter: MonitorFilter,
+    on_event: Channel<MonitorEvent>,
+) -> Result<(), AppError> {
+    _start_monitor(state.inner(), filter, move |event| {
+        on_event.send(event).is_ok()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_start_monitor_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _start_monitor(&app_state, MonitorFilter::default(), |_| true).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+
+    #[tokio::test]
+    async fn test_start_monitor_with_filter() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client.clone()));
+        let app_state = Mutex::new(app_state);
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let filter = MonitorFilter {
+            command: Some("set".to_string()),
+            key_pattern: Some("user:*".to_string()),
+            ..MonitorFilter::default()
+        };
+        _start_monitor(&app_state, filter, move |event| sender.send(event).is_ok())
+            .await
+            .unwrap();
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        for key in ["session:1", "user:1"] {
+            let _: () = redis::cmd("SET")
+                .arg(key)
+                .arg("value")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.args, vec!["SET", "user:1", "value"]);
+        assert_eq!(event.db, 0);
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,25 @@
+use crate::core::AppState;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Stops the running `MONITOR` session, doing nothing when none is running.
+async fn _stop_monitor(state: &Mutex<AppState>) -> Result<(), String> {
+    state.lock().await.set_monitor(None);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_monitor(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    _stop_monitor(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stop_monitor_when_not_running() {
+        let app_state = Mutex::new(AppState::new());
+        assert!(_stop_monitor(&app_state).await.is_ok());
+    }
+}
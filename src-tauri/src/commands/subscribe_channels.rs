@@ -0,0 +1,126 @@
+use crate::{
+    core::{AppError, AppState},
+    services::{PubSubMessage, PubSubSession, SubscriptionKind, PUBSUB_MESSAGE_EVENT},
+};
+use std::sync::atomic::Ordering;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+/// Subscribes to channels, patterns or shard channels, opening the pub/sub connection on
+/// the first subscription. Messages of every subscription are passed to `on_message`.
+async fn _subscribe_channels<F>(
+    state: &Mutex<AppState>,
+    kind: SubscriptionKind,
+    channels: Vec<String>,
+    on_message: F,
+) -> Result<(), AppError>
+where
+    F: Fn(PubSubMessage) + Send + Sync + 'static,
+{
+    if channels.is_empty() {
+        log::warn!("No channel to subscribe to");
+        return Err(AppError::InvalidCommand);
+    }
+
+    let (client, session, no_evict) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        let no_evict = state.client_no_evict().load(Ordering::Relaxed);
+        (client, state.get_pubsub().cloned(), no_evict)
+    };
+
+    let session = match session {
+        Some(session) => session,
+        None => {
+            let session = PubSubSession::start(&client, no_evict, on_message).await?;
+            state.lock().await.set_pubsub(Some(session.clone()));
+            session
+        }
+    };
+    session.subscribe(kind, &channels).await
+}
+
+/// Messages are emitted as `PUBSUB_MESSAGE_EVENT` events.
+#[tauri::command]
+pub async fn subscribe_channels(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    kind: SubscriptionKind,
+    channels: Vec<String>,
+) -> Result<(), AppError> {
+    _subscribe_channels(state.inner(), kind, channels, move |message| {
+        if let Err(e) = app.emit(PUBSUB_MESSAGE_EVENT, message) {
+            log::warn!("Failed to emit pub/sub message: {}", e);
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_subscribe_without_channels() {
+        let app_state = Mutex::new(AppState::new());
+        let result =
+            _subscribe_channels(&app_state, SubscriptionKind::Channel, vec![], |_| {}).await;
+
+        assert_eq!(result.unwrap_err(), AppError::InvalidCommand);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_channels_and_patterns() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client.clone()));
+        let app_state = Mutex::new(app_state);
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        _subscribe_channels(
+            &app_state,
+            SubscriptionKind::Channel,
+            vec!["orders".to_string()],
+            move |message| {
+                let _ = sender.send(message);
+            },
+        )
+        .await
+        .unwrap();
+        // The second subscription reuses the connection and its callback.
+        _subscribe_channels(
+            &app_state,
+            SubscriptionKind::Pattern,
+            vec!["events:*".to_string()],
+            |_| unreachable!(),
+        )
+        .await
+        .unwrap();
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        for channel in ["orders", "events:login"] {
+            let _: u64 = redis::cmd("PUBLISH")
+                .arg(channel)
+                .arg("payload")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let message = receiver.recv().await.unwrap();
+        assert_eq!(message.kind, SubscriptionKind::Channel);
+        assert_eq!(message.channel, "orders");
+        let message = receiver.recv().await.unwrap();
+        assert_eq!(message.pattern, Some("events:*".to_string()));
+        assert_eq!(message.channel, "events:login");
+
+        container.rm().await.unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::get_connection,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Resumes clients paused with `pause_clients` before their timeout, needs Redis 6.2.
+async fn _unpause_clients(
+    state: &Mutex<AppState>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    guard_command(
+        state,
+        &[b"CLIENT".to_vec(), b"UNPAUSE".to_vec()],
+        confirmation,
+    )
+    .await?;
+
+    let client = {
+        let state = state.lock().await;
+        state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?
+    };
+    let mut connection = get_connection(&client).await?;
+
+    redis::cmd("CLIENT")
+        .arg("UNPAUSE")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to unpause clients: {}", e);
+            AppError::RedisFailed
+        })
+}
+
+#[tauri::command]
+pub async fn unpause_clients(
+    state: State<'_, Mutex<AppState>>,
+    confirmation: Option<Uuid>,
+) -> Result<(), AppError> {
+    _unpause_clients(state.inner(), confirmation).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unpause_clients_without_client() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _unpause_clients(&app_state, None).await;
+
+        assert_eq!(result.unwrap_err(), AppError::RedisFailed);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{
+    core::{AppError, AppState},
+    services::SubscriptionKind,
+};
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Unsubscribes from the given channels, or from every channel of `kind` when empty.
+/// Does nothing when nothing was subscribed yet.
+async fn _unsubscribe_channels(
+    state: &Mutex<AppState>,
+    kind: SubscriptionKind,
+    channels: Vec<String>,
+) -> Result<(), AppError> {
+    let session = state.lock().await.get_pubsub().cloned();
+    match session {
+        Some(session) => session.unsubscribe(kind, &channels).await,
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub async fn unsubscribe_channels(
+    state: State<'_, Mutex<AppState>>,
+    kind: SubscriptionKind,
+    channels: Vec<String>,
+) -> Result<(), AppError> {
+    _unsubscribe_channels(state.inner(), kind, channels).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unsubscribe_without_subscriptions() {
+        let app_state = Mutex::new(AppState::new());
+        let result = _unsubscribe_channels(&app_state, SubscriptionKind::Pattern, vec![]).await;
+
+        assert!(result.is_ok());
+    }
+}
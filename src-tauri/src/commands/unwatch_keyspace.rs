@@ -0,0 +1,26 @@
+use crate::core::AppState;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Stops watching keyspace notifications, doing nothing when not watching.
+/// `notify-keyspace-events` is left as is since other clients may rely on it.
+async fn _unwatch_keyspace(state: &Mutex<AppState>) -> Result<(), String> {
+    state.lock().await.set_keyspace_watcher(None);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn unwatch_keyspace(state: State<'_, Mutex<AppState>>) -> Result<(), String> {
+    _unwatch_keyspace(state.inner()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unwatch_keyspace_when_not_watching() {
+        let app_state = Mutex::new(AppState::new());
+        assert!(_unwatch_keyspace(&app_state).await.is_ok());
+    }
+}
@@ -0,0 +1,188 @@
+use super::confirm_command::guard_command;
+use crate::{
+    core::{AppError, AppState},
+    services::{
+        complete_notifications, get_connection, KeyspaceEvent, KeyspaceWatcher, KEYSPACE_EVENT,
+    },
+};
+use serde::Serialize;
+use std::{collections::HashMap, sync::atomic::Ordering};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct KeyspaceWatchStatus {
+    /// `notify-keyspace-events` of the server once the call returns.
+    notifications: String,
+    /// `false` when notifications miss flags the watcher needs and were not completed,
+    /// so the user can be offered to complete them.
+    watching: bool,
+    /// The value `notify-keyspace-events` would be set to when `watching` is `false`.
+    suggested_notifications: Option<String>,
+}
+
+/// Watches the keyspace notifications of the current database, replacing the running
+/// watcher if any. Set, deleted or changed keys are only notified with the `K` or `E` channel
+/// class and the `g$lshzxe` event classes, the missing ones are merged in first when
+/// `enable_notifications` is set. `CONFIG SET` is guarded by default so a token is usually
+/// required.
+async fn _watch_keyspace<F>(
+    state: &Mutex<AppState>,
+    enable_notifications: bool,
+    confirmation: Option<Uuid>,
+    on_event: F,
+) -> Result<KeyspaceWatchStatus, AppError>
+where
+    F: Fn(KeyspaceEvent) + Send + Sync + 'static,
+{
+    let (client, no_evict) = {
+        let state = state.lock().await;
+        let client = state.get_redis_client().cloned().ok_or_else(|| {
+            log::error!("Redis client is not ready");
+            AppError::RedisFailed
+        })?;
+        (client, state.client_no_evict().load(Ordering::Relaxed))
+    };
+    let mut connection = get_connection(&client).await?;
+
+    let config: HashMap<String, String> = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("notify-keyspace-events")
+        .query_async(&mut connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to read notify-keyspace-events: {}", e);
+            AppError::RedisFailed
+        })?;
+    let mut notifications = config
+        .get("notify-keyspace-events")
+        .cloned()
+        .unwrap_or_default();
+
+    if let Some(completed) = complete_notifications(&notifications) {
+        if !enable_notifications {
+            return Ok(KeyspaceWatchStatus {
+                notifications,
+                watching: false,
+                suggested_notifications: Some(completed),
+            });
+        }
+
+        let args = [
+            b"CONFIG".to_vec(),
+            b"SET".to_vec(),
+            b"notify-keyspace-events".to_vec(),
+            completed.as_bytes().to_vec(),
+        ];
+        guard_command(state, &args, confirmation).await?;
+        redis::cmd("CONFIG")
+            .arg("SET")
+            .arg("notify-keyspace-events")
+            .arg(&completed)
+            .query_async::<()>(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to enable keyspace notifications: {}", e);
+                AppError::RedisFailed
+            })?;
+        notifications = completed;
+    }
+
+    let watcher = KeyspaceWatcher::start(&client, &notifications, no_evict, on_event).await?;
+    state.lock().await.set_keyspace_watcher(Some(watcher));
+
+    Ok(KeyspaceWatchStatus {
+        notifications,
+        watching: true,
+        suggested_notifications: None,
+    })
+}
+
+/// Changes are emitted as `KEYSPACE_EVENT` events.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn watch_keyspace(
+    app: AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    enable_notifications: bool,
+    confirmation: Option<Uuid>,
+) -> Result<KeyspaceWatchStatus, AppError> {
+    _watch_keyspace(
+        state.inner(),
+        enable_notifications,
+        confirmation,
+        move |event| {
+            if let Err(e) = app.emit(KEYSPACE_EVENT, event) {
+                log::warn!("Failed to emit keyspace event: {}", e);
+            }
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{models::Server, services::test_connection, tests::run_redis_container};
+
+    #[tokio::test]
+    async fn test_watch_keyspace() {
+        let (host, port, container) = run_redis_container(6379).await;
+        let server = Server::from_payload("Local Server".to_string(), host, port);
+        let client = test_connection(&server).await.unwrap();
+
+        let mut app_state = AppState::new();
+        app_state.set_redis_client(Some(client.clone()));
+        let token = app_state.issue_confirmation("CONFIG SET".to_string());
+        let app_state = Mutex::new(app_state);
+
+        let status = _watch_keyspace(&app_state, false, None, |_| {})
+            .await
+            .unwrap();
+        assert!(!status.watching);
+        assert_eq!(status.suggested_notifications, Some("KEA".to_string()));
+        assert_eq!(
+            _watch_keyspace(&app_state, true, None, |_| {})
+                .await
+                .unwrap_err(),
+            AppError::ConfirmationRequired
+        );
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let status = _watch_keyspace(&app_state, true, Some(token), move |event| {
+            let _ = sender.send(event);
+        })
+        .await
+        .unwrap();
+        assert!(status.watching);
+        assert_eq!(status.notifications, "AKE");
+        app_state
+            .lock()
+            .await
+            .get_keyspace_watcher()
+            .unwrap()
+            .set_current_key(Some("user:1".to_string()));
+
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+        for key in ["user:1", "user:2"] {
+            let _: () = redis::cmd("SET")
+                .arg(key)
+                .arg("value")
+                .query_async(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(
+            (event.key.as_str(), event.event.as_str()),
+            ("user:1", "set")
+        );
+        assert!(event.current);
+        let event = receiver.recv().await.unwrap();
+        assert_eq!(event.key, "user:2");
+        assert!(!event.current);
+
+        container.rm().await.unwrap();
+    }
+}
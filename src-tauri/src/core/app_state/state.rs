@@ -1,10 +1,50 @@
-use crate::core::Database;
-use redis::Client;
+use crate::{
+    core::Database,
+    models::Server,
+    services::{
+        CommandSpec, KeyspaceWatcher, MetricsSampler, MonitorSession, PubSubSession, RedisCluster,
+        SentinelWatcher, SlowlogCollector, SshTunnel,
+    },
+};
+use redis::{aio::MultiplexedConnection, Client};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// How long a confirmation token stays valid once issued.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Default)]
 pub struct AppState {
     redis_client: Option<Client>,
+    cluster: Option<RedisCluster>,
     db_connection: Option<Database>,
+    server: Option<Server>,
+    jobs: HashMap<Uuid, Arc<AtomicBool>>,
+    command_docs: Option<Arc<Vec<CommandSpec>>>,
+    confirmations: HashMap<Uuid, (String, Instant)>,
+    metrics_sampler: Option<MetricsSampler>,
+    slowlog_collector: Option<SlowlogCollector>,
+    /// Drop guard, the stream stops when the session is replaced or dropped.
+    _monitor: Option<MonitorSession>,
+    pubsub: Option<PubSubSession>,
+    keyspace_watcher: Option<KeyspaceWatcher>,
+    /// Kept for the console so `MULTI`, `WATCH`, `SELECT` and `CLIENT TRACKING` apply to the
+    /// following lines.
+    console_connection: Option<MultiplexedConnection>,
+    /// Drop guard, failovers stop being followed when the watcher is replaced or dropped.
+    _sentinel_watcher: Option<SentinelWatcher>,
+    /// Drop guard, the forward closes when the tunnel is replaced or dropped.
+    _ssh_tunnel: Option<SshTunnel>,
+    /// Whether the app's long-lived connections ask not to be evicted, shared with the
+    /// background tasks so they apply it when they reconnect.
+    client_no_evict: Arc<AtomicBool>,
 }
 
 impl AppState {
@@ -22,6 +62,16 @@ impl AppState {
         self.redis_client.as_ref()
     }
 
+    /// Sets the cluster client of the currently opened server, `None` outside of cluster mode.
+    pub fn set_cluster(&mut self, cluster: Option<RedisCluster>) {
+        self.cluster = cluster;
+    }
+
+    /// Returns the cluster client when the opened server is a Redis Cluster.
+    pub fn get_cluster(&self) -> Option<&RedisCluster> {
+        self.cluster.as_ref()
+    }
+
     /// Sets the Database connection.
     pub fn set_db_connection(&mut self, db_connection: Option<Database>) {
         self.db_connection = db_connection;
@@ -31,6 +81,160 @@ impl AppState {
     pub fn get_db_connection(&self) -> Option<&Database> {
         self.db_connection.as_ref()
     }
+
+    /// Sets the server the current Redis Client is connected to.
+    pub fn set_server(&mut self, server: Option<Server>) {
+        self.server = server;
+    }
+
+    /// Returns a reference to the currently opened server if present.
+    pub fn get_server(&self) -> Option<&Server> {
+        self.server.as_ref()
+    }
+
+    /// Sets the commands supported by the currently opened server.
+    pub fn set_command_docs(&mut self, command_docs: Option<Arc<Vec<CommandSpec>>>) {
+        self.command_docs = command_docs;
+    }
+
+    /// Returns the commands supported by the currently opened server if loaded.
+    pub fn get_command_docs(&self) -> Option<Arc<Vec<CommandSpec>>> {
+        self.command_docs.clone()
+    }
+
+    /// Sets the sampler polling the currently opened server, dropping the previous one stops it.
+    pub fn set_metrics_sampler(&mut self, metrics_sampler: Option<MetricsSampler>) {
+        self.metrics_sampler = metrics_sampler;
+    }
+
+    /// Returns the sampler of the currently opened server if running.
+    pub fn get_metrics_sampler(&self) -> Option<&MetricsSampler> {
+        self.metrics_sampler.as_ref()
+    }
+
+    /// Sets the task capturing the slow log of the currently opened server, dropping the
+    /// previous one stops it.
+    pub fn set_slowlog_collector(&mut self, slowlog_collector: Option<SlowlogCollector>) {
+        self.slowlog_collector = slowlog_collector;
+    }
+
+    /// Sets the `MONITOR` session of the currently opened server, dropping the previous one
+    /// stops it.
+    pub fn set_monitor(&mut self, monitor: Option<MonitorSession>) {
+        self._monitor = monitor;
+    }
+
+    /// Sets the connection holding the subscriptions on the currently opened server,
+    /// dropping the previous one closes it.
+    pub fn set_pubsub(&mut self, pubsub: Option<PubSubSession>) {
+        self.pubsub = pubsub;
+    }
+
+    /// Returns the pub/sub connection of the currently opened server if subscribed.
+    pub fn get_pubsub(&self) -> Option<&PubSubSession> {
+        self.pubsub.as_ref()
+    }
+
+    /// Sets the watcher of the current database's keyspace notifications, dropping the
+    /// previous one stops it.
+    pub fn set_keyspace_watcher(&mut self, keyspace_watcher: Option<KeyspaceWatcher>) {
+        self.keyspace_watcher = keyspace_watcher;
+    }
+
+    /// Returns the keyspace watcher of the currently opened server if running.
+    pub fn get_keyspace_watcher(&self) -> Option<&KeyspaceWatcher> {
+        self.keyspace_watcher.as_ref()
+    }
+
+    /// Sets the connection the console runs its commands on, dropping the previous one closes
+    /// it along with its transaction, selected database and tracking.
+    pub fn set_console_connection(&mut self, console_connection: Option<MultiplexedConnection>) {
+        self.console_connection = console_connection;
+    }
+
+    /// Returns the console connection of the currently opened server if opened.
+    pub fn get_console_connection(&self) -> Option<&MultiplexedConnection> {
+        self.console_connection.as_ref()
+    }
+
+    /// Sets the watcher following failovers of the currently opened Sentinel-managed server,
+    /// dropping the previous one stops it.
+    pub fn set_sentinel_watcher(&mut self, sentinel_watcher: Option<SentinelWatcher>) {
+        self._sentinel_watcher = sentinel_watcher;
+    }
+
+    /// Sets the SSH tunnel the currently opened server is reached through, dropping the
+    /// previous one closes it.
+    pub fn set_ssh_tunnel(&mut self, ssh_tunnel: Option<SshTunnel>) {
+        self._ssh_tunnel = ssh_tunnel;
+    }
+
+    /// Returns the `CLIENT NO-EVICT` flag, shared with the tasks started from now on.
+    pub fn client_no_evict(&self) -> Arc<AtomicBool> {
+        self.client_no_evict.clone()
+    }
+
+    pub fn set_client_no_evict(&self, enabled: bool) {
+        self.client_no_evict.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns the open connections of the metrics sampler, the slow log collector, the
+    /// subscriptions, the keyspace watcher and the console. `MONITOR` is left out, its connection
+    /// accepts no other command once streaming.
+    pub fn long_lived_connections(&self) -> Vec<MultiplexedConnection> {
+        let mut connections = vec![];
+        connections.extend(self.metrics_sampler.as_ref().and_then(|s| s.connection()));
+        connections.extend(self.slowlog_collector.as_ref().and_then(|c| c.connection()));
+        connections.extend(self.pubsub.as_ref().map(|pubsub| pubsub.connection()));
+        connections.extend(self.keyspace_watcher.as_ref().map(|w| w.connection()));
+        connections.extend(self.console_connection.clone());
+        connections
+    }
+
+    /// Issues a single-use token confirming that a guarded command line may run.
+    pub fn issue_confirmation(&mut self, command: String) -> Uuid {
+        self.confirmations
+            .retain(|_, (_, issued_at)| issued_at.elapsed() < CONFIRMATION_TTL);
+
+        let token = Uuid::new_v4();
+        self.confirmations.insert(token, (command, Instant::now()));
+        token
+    }
+
+    /// Consumes a confirmation token.
+    /// Returns `false` when the token is unknown, expired or was issued for another command line.
+    pub fn take_confirmation(&mut self, token: &Uuid, command: &str) -> bool {
+        match self.confirmations.remove(token) {
+            Some((confirmed, issued_at)) => {
+                confirmed == command && issued_at.elapsed() < CONFIRMATION_TTL
+            }
+            None => false,
+        }
+    }
+
+    /// Registers a long-running job and returns its cancellation flag.
+    pub fn register_job(&mut self, id: Uuid) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.jobs.insert(id, flag.clone());
+        flag
+    }
+
+    /// Flags a running job for cancellation.
+    /// Returns `false` when no job with the given ID is running.
+    pub fn cancel_job(&self, id: &Uuid) -> bool {
+        match self.jobs.get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a finished job from the registry.
+    pub fn finish_job(&mut self, id: &Uuid) {
+        self.jobs.remove(id);
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +279,55 @@ mod tests {
         app_state.set_db_connection(None);
         assert!(app_state.get_db_connection().is_none());
     }
+
+    #[test]
+    fn test_set_and_get_server() {
+        let mut app_state = AppState::new();
+        let server = Server::from_payload("Local".to_string(), "127.0.0.1".to_string(), 6379);
+        app_state.set_server(Some(server.clone()));
+
+        assert_eq!(app_state.get_server(), Some(&server));
+
+        app_state.set_server(None);
+        assert!(app_state.get_server().is_none());
+    }
+
+    #[test]
+    fn test_set_and_get_command_docs() {
+        let mut app_state = AppState::new();
+        let command_docs = Arc::new(vec![CommandSpec::default()]);
+        app_state.set_command_docs(Some(command_docs.clone()));
+
+        assert_eq!(app_state.get_command_docs(), Some(command_docs));
+
+        app_state.set_command_docs(None);
+        assert!(app_state.get_command_docs().is_none());
+    }
+
+    #[test]
+    fn test_issue_and_take_confirmation() {
+        let mut app_state = AppState::new();
+        let token = app_state.issue_confirmation("FLUSHALL".to_string());
+
+        assert!(!app_state.take_confirmation(&Uuid::new_v4(), "FLUSHALL"));
+        assert!(app_state.take_confirmation(&token, "FLUSHALL"));
+        assert!(!app_state.take_confirmation(&token, "FLUSHALL"));
+
+        let token = app_state.issue_confirmation("FLUSHDB".to_string());
+        assert!(!app_state.take_confirmation(&token, "FLUSHALL"));
+    }
+
+    #[test]
+    fn test_register_and_cancel_job() {
+        let mut app_state = AppState::new();
+        let id = Uuid::new_v4();
+        let flag = app_state.register_job(id);
+
+        assert!(!flag.load(Ordering::Relaxed));
+        assert!(app_state.cancel_job(&id));
+        assert!(flag.load(Ordering::Relaxed));
+
+        app_state.finish_job(&id);
+        assert!(!app_state.cancel_job(&id));
+    }
 }
@@ -109,4 +109,96 @@ mod tests {
             .table_exists(None, "servers")
             .unwrap());
     }
+
+    #[test]
+    fn test_audit_log_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.connection.table_exists(None, "audit_log").unwrap());
+    }
+
+    #[test]
+    fn test_command_history_migrations_run() {
+        let db = Database::new_in_memory().unwrap();
+        for table in ["command_history", "snippets", "settings"] {
+            assert!(db.connection.table_exists(None, table).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_command_docs_cache_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db
+            .connection
+            .table_exists(None, "command_docs_cache")
+            .unwrap());
+    }
+
+    #[test]
+    fn test_scripts_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.connection.table_exists(None, "scripts").unwrap());
+    }
+
+    #[test]
+    fn test_metric_samples_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.connection.table_exists(None, "metric_samples").unwrap());
+    }
+
+    #[test]
+    fn test_slowlog_entries_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.connection.table_exists(None, "slowlog_entries").unwrap());
+    }
+
+    #[test]
+    fn test_memory_reports_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.connection.table_exists(None, "memory_reports").unwrap());
+    }
+
+    #[test]
+    fn test_server_sentinel_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        let columns: Vec<String> = db
+            .connection
+            .prepare("SELECT name FROM pragma_table_info('servers')")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(columns.contains(&"sentinel".to_string()));
+    }
+
+    #[test]
+    fn test_server_cluster_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        let columns: Vec<String> = db
+            .connection
+            .prepare("SELECT name FROM pragma_table_info('servers')")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(columns.contains(&"cluster".to_string()));
+    }
+
+    #[test]
+    fn test_server_connection_kind_migration_run() {
+        let db = Database::new_in_memory().unwrap();
+        db.connection
+            .execute(
+                "INSERT INTO servers (id, name, address, port, created_at, updated_at) \
+                 VALUES ('1', 'Existing', 'localhost', 6379, '', '')",
+                [],
+            )
+            .unwrap();
+        let kind: String = db
+            .connection
+            .query_row("SELECT connection_kind FROM servers", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(kind, "tcp");
+    }
 }
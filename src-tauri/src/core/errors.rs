@@ -4,6 +4,18 @@ use serde::Serialize;
 pub enum AppError {
     /// Failure to interact with Redis server, e.g., connection failed, command failed
     RedisFailed = 910,
+    /// The command line could not be parsed, e.g., unbalanced quotes or unsupported command
+    InvalidCommand = 920,
+    /// The connection URL could not be parsed, e.g., unknown scheme or invalid port
+    InvalidUrl = 921,
+    /// The command writes data but the server is opened in read-only mode
+    WriteBlocked = 930,
+    /// The command is on the dangerous command guard list and was sent without a valid confirmation token
+    ConfirmationRequired = 931,
+    /// Failure to read or write a file picked by the user, e.g., missing file or permission denied
+    FileFailed = 940,
+    /// Failure to open the SSH tunnel, e.g., bastion unreachable, authentication rejected or unknown host key
+    SshFailed = 950,
     /// Database is not ready, e.g., connection not established
     DbNotReady = 900,
     /// Generic database query failure, e.g., SELECT, UPDATE, DELETE
@@ -4,7 +4,24 @@ mod models;
 mod services;
 mod utils;
 
-use commands::{add_server, close_server, get_servers, open_server, retrieve_key, retrieve_keys};
+use commands::{
+    acl_dry_run, acl_whoami, add_server, add_server_from_url, cancel_job, clear_command_history,
+    client_list, close_server, cluster_topology, complete_command, confirm_command,
+    delete_acl_users, delete_function, delete_script, delete_snippet, diff_memory_reports,
+    dump_functions, eval_script, execute_command, execute_snippet, export_servers, fcall,
+    flush_scripts, get_acl_log, get_acl_user, get_command_docs, get_command_guard,
+    get_command_history, get_config, get_history_retention, get_memory_reports, get_metrics,
+    get_metrics_settings, get_scripts, get_servers, get_slowlog, get_slowlog_config,
+    get_slowlog_history, get_snippets, import_connections, import_servers, kill_clients,
+    list_acl_categories, list_acl_users, list_functions, list_pubsub_channels, load_function,
+    load_script, open_server, pause_clients, publish_message, replica_of, replication_overview,
+    reset_config_stats, reset_slowlog, restore_functions, retrieve_key, retrieve_keys,
+    rewrite_config, run_bulk_job, run_memory_analysis, run_ttl_analysis, save_script, save_snippet,
+    script_exists, sentinel_overview, server_info, set_acl_user, set_client_no_evict,
+    set_command_guard, set_config, set_history_retention, set_metrics_settings,
+    set_server_read_only, set_slowlog_config, slowlog_len, start_monitor, stop_monitor,
+    subscribe_channels, unpause_clients, unsubscribe_channels, unwatch_keyspace, watch_keyspace,
+};
 use core::{AppState, Database};
 use log::error;
 use tauri::{Builder, Manager};
@@ -37,7 +54,84 @@ pub fn run() {
             get_servers,
             open_server,
             close_server,
-            retrieve_key
+            retrieve_key,
+            run_bulk_job,
+            cancel_job,
+            execute_command,
+            get_command_history,
+            clear_command_history,
+            get_history_retention,
+            set_history_retention,
+            save_snippet,
+            get_snippets,
+            delete_snippet,
+            execute_snippet,
+            get_command_docs,
+            complete_command,
+            confirm_command,
+            get_command_guard,
+            set_command_guard,
+            set_server_read_only,
+            save_script,
+            get_scripts,
+            delete_script,
+            eval_script,
+            load_script,
+            script_exists,
+            flush_scripts,
+            list_functions,
+            load_function,
+            delete_function,
+            dump_functions,
+            restore_functions,
+            fcall,
+            server_info,
+            get_metrics,
+            get_metrics_settings,
+            set_metrics_settings,
+            get_slowlog,
+            slowlog_len,
+            reset_slowlog,
+            get_slowlog_config,
+            set_slowlog_config,
+            get_slowlog_history,
+            client_list,
+            kill_clients,
+            pause_clients,
+            unpause_clients,
+            set_client_no_evict,
+            start_monitor,
+            stop_monitor,
+            subscribe_channels,
+            unsubscribe_channels,
+            publish_message,
+            list_pubsub_channels,
+            watch_keyspace,
+            unwatch_keyspace,
+            run_memory_analysis,
+            get_memory_reports,
+            diff_memory_reports,
+            run_ttl_analysis,
+            get_config,
+            set_config,
+            reset_config_stats,
+            rewrite_config,
+            list_acl_users,
+            get_acl_user,
+            set_acl_user,
+            delete_acl_users,
+            acl_whoami,
+            list_acl_categories,
+            get_acl_log,
+            acl_dry_run,
+            sentinel_overview,
+            cluster_topology,
+            replication_overview,
+            replica_of,
+            add_server_from_url,
+            export_servers,
+            import_servers,
+            import_connections
         ])
         .run(tauri::generate_context!())
         .map_err(|e| {
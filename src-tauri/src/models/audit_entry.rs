@@ -0,0 +1,196 @@
+use super::Model;
+use crate::core::{AppError, Database};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+/// Summary of a bulk operation, kept locally so destructive jobs can be traced back.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    /// Server the job ran against, nil when it was not opened through `open_server`.
+    pub server_id: Uuid,
+    pub action: String,
+    pub pattern: String,
+    pub dry_run: bool,
+    pub matched: u32,
+    pub affected: u32,
+    pub failed: u32,
+    pub cancelled: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Default for AuditEntry {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            action: String::new(),
+            pattern: String::new(),
+            dry_run: false,
+            matched: 0,
+            affected: 0,
+            failed: 0,
+            cancelled: false,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for AuditEntry {
+    fn table_name() -> &'static str {
+        "audit_log"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY created_at DESC",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving audit log: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let entries_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!("Failed to execute query while retrieving audit log: {}", e);
+            AppError::DbQueryFailed
+        })?;
+
+        let entries: Result<Vec<Self>, _> = entries_iter.collect();
+        entries.map_err(|e| {
+            log::error!("Failed to collect audit log from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save audit log entry: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding audit entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding audit entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            action: row.get("action")?,
+            pattern: row.get("pattern")?,
+            dry_run: row.get("dry_run")?,
+            matched: row.get("matched")?,
+            affected: row.get("affected")?,
+            failed: row.get("failed")?,
+            cancelled: row.get("cancelled")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.action.clone(),
+            self.pattern.clone(),
+            (self.dry_run as u8).to_string(),
+            self.matched.to_string(),
+            self.affected.to_string(),
+            self.failed.to_string(),
+            (self.cancelled as u8).to_string(),
+            self.created_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AuditEntry {{ id: {}, action: {}, pattern: {}, dry_run: {}, affected: {} }}",
+            self.id, self.action, self.pattern, self.dry_run, self.affected
+        )
+    }
+}
+
+impl PartialEq for AuditEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
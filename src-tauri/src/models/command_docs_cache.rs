@@ -0,0 +1,182 @@
+use super::Model;
+use crate::{
+    core::{AppError, Database},
+    services::CommandSpec,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// The commands reported by a server flavour and version, stored so `COMMAND DOCS`
+/// is only fetched once per distinct command set.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct CommandDocsCache {
+    /// Server flavour, version and loaded modules, e.g. `redis-7.2.4+search@20612`.
+    pub version: String,
+    pub commands: Vec<CommandSpec>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl CommandDocsCache {
+    pub fn from_payload(version: String, commands: Vec<CommandSpec>) -> Self {
+        Self {
+            version,
+            commands,
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Returns the cached commands of a version, `None` when they were never fetched.
+    pub fn find_by_version(version: &str, db: &Database) -> Result<Option<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE version = ? LIMIT 1",
+            Self::table_name()
+        );
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while reading command docs for {}: {}",
+                version,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let mut rows = stmt.query_map([version], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while reading command docs for {}: {}",
+                version,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        match rows.next() {
+            Some(Ok(cache)) => Ok(Some(cache)),
+            Some(Err(e)) => {
+                log::error!("Failed to read command docs for {}: {}", version, e);
+                Err(AppError::DbQueryFailed)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Model for CommandDocsCache {
+    fn table_name() -> &'static str {
+        "command_docs_cache"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {}", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving command docs: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let caches_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving command docs: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let caches: Result<Vec<Self>, _> = caches_iter.collect();
+        caches.map_err(|e| {
+            log::error!("Failed to collect command docs from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    /// Caches are identified by their version, so creating one is the same as saving it.
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        self.save(db)?;
+        Ok(self.clone())
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE version = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.version])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save command docs for {}: {}", self.version, e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        Self::find_by_version(id, db)?.ok_or_else(|| {
+            log::error!("No command docs cached for {}", id);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let commands: String = row.get("commands")?;
+        let commands = serde_json::from_str(&commands).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(Self {
+            version: row.get("version")?,
+            commands,
+            created_at: row.get("created_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.version.clone(),
+            serde_json::to_string(&self.commands).unwrap(),
+            self.created_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for CommandDocsCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CommandDocsCache {{ version: {}, commands: {} }}",
+            self.version,
+            self.commands.len()
+        )
+    }
+}
+
+impl PartialEq for CommandDocsCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+    }
+}
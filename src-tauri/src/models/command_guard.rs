@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Setting key the dangerous command guard list is stored under.
+pub const COMMAND_GUARD_SETTING: &str = "command_guard";
+
+/// Commands that only run when the request carries a confirmation token, whatever the
+/// server's read-only flag. Subcommands are written with a space, e.g. `CONFIG SET`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CommandGuard {
+    pub commands: Vec<String>,
+}
+
+impl Default for CommandGuard {
+    fn default() -> Self {
+        Self {
+            commands: [
+                "FLUSHALL",
+                "FLUSHDB",
+                "KEYS",
+                "DEBUG",
+                "SHUTDOWN",
+                "CONFIG SET",
+                "CONFIG RESETSTAT",
+                "CONFIG REWRITE",
+                "SCRIPT FLUSH",
+                "FUNCTION FLUSH",
+                "CLIENT KILL",
+                "ACL SETUSER",
+                "ACL DELUSER",
+                "CLUSTER RESET",
+                "FAILOVER",
+                "REPLICAOF",
+                "SLAVEOF",
+                "SWAPDB",
+                "MIGRATE",
+            ]
+            .iter()
+            .map(|command| command.to_string())
+            .collect(),
+        }
+    }
+}
+
+impl CommandGuard {
+    /// Returns the guarded command an argument list matches, if any.
+    pub fn matches(&self, args: &[Vec<u8>]) -> Option<&str> {
+        let name = String::from_utf8_lossy(args.first()?).to_uppercase();
+        let subcommand = args
+            .get(1)
+            .map(|sub| format!("{} {}", name, String::from_utf8_lossy(sub).to_uppercase()));
+
+        self.commands
+            .iter()
+            .find(|guarded| {
+                let guarded = guarded.trim().to_uppercase();
+                guarded == name || subcommand.as_ref() == Some(&guarded)
+            })
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<Vec<u8>> {
+        line.split(' ').map(|arg| arg.as_bytes().to_vec()).collect()
+    }
+
+    #[test]
+    fn test_matches_commands_and_subcommands() {
+        let guard = CommandGuard::default();
+
+        assert_eq!(guard.matches(&args("flushall")), Some("FLUSHALL"));
+        assert_eq!(
+            guard.matches(&args("config set maxmemory 1")),
+            Some("CONFIG SET")
+        );
+        assert_eq!(guard.matches(&args("CONFIG GET maxmemory")), None);
+        assert_eq!(guard.matches(&args("GET k")), None);
+        assert_eq!(guard.matches(&[]), None);
+    }
+}
@@ -0,0 +1,334 @@
+use super::{Model, Setting};
+use crate::core::{AppError, Database};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+/// Setting key the history retention is stored under.
+pub const HISTORY_RETENTION_SETTING: &str = "history_retention";
+
+/// How long executed commands are kept, both limits apply when set.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryRetention {
+    /// Maximum number of entries kept per server.
+    pub max_entries: Option<u32>,
+    /// Entries older than this many days are removed.
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_entries: Some(1000),
+            max_age_days: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct CommandHistoryEntry {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub command: String,
+    pub duration_us: i64,
+    pub success: bool,
+    #[serde(with = "time::serde::rfc3339")]
+    pub executed_at: OffsetDateTime,
+}
+
+impl CommandHistoryEntry {
+    pub fn from_payload(server_id: Uuid, command: String, duration_us: i64, success: bool) -> Self {
+        Self {
+            server_id,
+            command,
+            duration_us,
+            success,
+            ..Self::default()
+        }
+    }
+
+    /// Stores an executed command and prunes the server's history to the configured retention.
+    pub fn record(&self, db: &Database) -> Result<Self, AppError> {
+        let entry = self.create(db)?;
+        let retention = Setting::get_value::<HistoryRetention>(HISTORY_RETENTION_SETTING, db)?
+            .unwrap_or_default();
+        Self::prune(&entry.server_id, &retention, db)?;
+        Ok(entry)
+    }
+
+    /// Returns a page of a server's history, newest first, optionally filtered by a
+    /// case-insensitive substring, along with the total number of matching entries.
+    pub fn search(
+        server_id: &Uuid,
+        query: &str,
+        limit: u32,
+        offset: u32,
+        db: &Database,
+    ) -> Result<(Vec<Self>, u32), AppError> {
+        let filter = "server_id = ?1 AND (?2 = '' OR instr(lower(command), lower(?2)) > 0)";
+        let sql = format!(
+            "SELECT * FROM {} WHERE {} ORDER BY id DESC LIMIT ?3 OFFSET ?4",
+            Self::table_name(),
+            filter
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while searching command history: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let entries: Result<Vec<Self>, _> = stmt
+            .query_map(
+                params![server_id.to_string(), query, limit, offset],
+                Self::from_row,
+            )
+            .map_err(|e| {
+                log::error!(
+                    "Failed to execute query while searching command history: {}",
+                    e
+                );
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        let entries = entries.map_err(|e| {
+            log::error!("Failed to collect command history from query result: {}", e);
+            AppError::DbQueryFailed
+        })?;
+
+        let count_sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            Self::table_name(),
+            filter
+        );
+        let total = db
+            .get_connection()
+            .query_row(&count_sql, params![server_id.to_string(), query], |row| {
+                row.get(0)
+            })
+            .map_err(|e| {
+                log::error!("Failed to count command history: {}", e);
+                AppError::DbQueryFailed
+            })?;
+
+        Ok((entries, total))
+    }
+
+    /// Removes a server's entries that fall outside the retention limits.
+    pub fn prune(
+        server_id: &Uuid,
+        retention: &HistoryRetention,
+        db: &Database,
+    ) -> Result<(), AppError> {
+        if let Some(max_age_days) = retention.max_age_days {
+            let cutoff = OffsetDateTime::now_utc() - Duration::days(max_age_days as i64);
+            let sql = format!(
+                "DELETE FROM {} WHERE server_id = ? AND executed_at < ?",
+                Self::table_name()
+            );
+            db.get_connection()
+                .execute(
+                    &sql,
+                    params![server_id.to_string(), cutoff.format(&Rfc3339).unwrap()],
+                )
+                .map_err(|e| {
+                    log::error!("Failed to prune command history by age: {}", e);
+                    AppError::DbQueryFailed
+                })?;
+        }
+
+        if let Some(max_entries) = retention.max_entries {
+            let sql = format!(
+                "DELETE FROM {table} WHERE server_id = ?1 AND id NOT IN \
+                (SELECT id FROM {table} WHERE server_id = ?1 ORDER BY id DESC LIMIT ?2)",
+                table = Self::table_name()
+            );
+            db.get_connection()
+                .execute(&sql, params![server_id.to_string(), max_entries])
+                .map_err(|e| {
+                    log::error!("Failed to prune command history by size: {}", e);
+                    AppError::DbQueryFailed
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes every history entry of a server.
+    pub fn clear(server_id: &Uuid, db: &Database) -> Result<(), AppError> {
+        let sql = format!("DELETE FROM {} WHERE server_id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&server_id.to_string()])
+            .map(|_| ())
+            .map_err(|e| {
+                log::error!("Failed to clear command history: {}", e);
+                AppError::DbQueryFailed
+            })
+    }
+}
+
+impl Default for CommandHistoryEntry {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            command: String::new(),
+            duration_us: 0,
+            success: false,
+            executed_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for CommandHistoryEntry {
+    fn table_name() -> &'static str {
+        "command_history"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {} ORDER BY id DESC", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving command history: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let entries_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving command history: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let entries: Result<Vec<Self>, _> = entries_iter.collect();
+        entries.map_err(|e| {
+            log::error!("Failed to collect command history from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save command history entry: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding history entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding history entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            command: row.get("command")?,
+            duration_us: row.get("duration_us")?,
+            success: row.get("success")?,
+            executed_at: row.get("executed_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.command.clone(),
+            self.duration_us.to_string(),
+            (self.success as u8).to_string(),
+            self.executed_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for CommandHistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CommandHistoryEntry {{ id: {}, server_id: {}, command: {}, success: {} }}",
+            self.id, self.server_id, self.command, self.success
+        )
+    }
+}
+
+impl PartialEq for CommandHistoryEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
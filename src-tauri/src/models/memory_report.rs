@@ -0,0 +1,271 @@
+use super::Model;
+use crate::{
+    core::{AppError, Database},
+    services::MemoryAnalysis,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+/// A memory analysis of a server, kept so reports can be compared over time.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct MemoryReport {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    /// SCAN pattern the analysed keys matched.
+    pub pattern: String,
+    pub separator: String,
+    pub prefix_depth: u32,
+    /// Whether the analysis was cancelled before the whole keyspace was scanned.
+    pub cancelled: bool,
+    pub analysis: MemoryAnalysis,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl MemoryReport {
+    /// Returns every report of a server, newest first.
+    pub fn get_for_server(server_id: &Uuid, db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE server_id = ? ORDER BY created_at DESC",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving memory reports: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let reports: Result<Vec<Self>, _> = stmt
+            .query_map([server_id.to_string()], Self::from_row)
+            .map_err(|e| {
+                log::error!(
+                    "Failed to execute query while retrieving memory reports: {}",
+                    e
+                );
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        reports.map_err(|e| {
+            log::error!("Failed to collect memory reports from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+}
+
+impl Default for MemoryReport {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            pattern: String::new(),
+            separator: ":".to_string(),
+            prefix_depth: 1,
+            cancelled: false,
+            analysis: MemoryAnalysis::default(),
+            created_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for MemoryReport {
+    fn table_name() -> &'static str {
+        "memory_reports"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY created_at DESC",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving memory reports: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let reports_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving memory reports: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let reports: Result<Vec<Self>, _> = reports_iter.collect();
+        reports.map_err(|e| {
+            log::error!("Failed to collect memory reports from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save memory report: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding memory report by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding memory report by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        let analysis: String = row.get("analysis")?;
+        let analysis = serde_json::from_str(&analysis).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            pattern: row.get("pattern")?,
+            separator: row.get("separator")?,
+            prefix_depth: row.get("prefix_depth")?,
+            cancelled: row.get("cancelled")?,
+            analysis,
+            created_at: row.get("created_at")?,
+        })
+    }
+
+    /// `key_count` and `total_memory` are copied out of the analysis so reports can be
+    /// listed without reading it.
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.pattern.clone(),
+            self.separator.clone(),
+            self.prefix_depth.to_string(),
+            self.analysis.key_count.to_string(),
+            self.analysis.total_memory.to_string(),
+            (self.cancelled as u8).to_string(),
+            serde_json::to_string(&self.analysis).unwrap(),
+            self.created_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MemoryReport {{ id: {}, server_id: {}, key_count: {}, total_memory: {} }}",
+            self.id, self.server_id, self.analysis.key_count, self.analysis.total_memory
+        )
+    }
+}
+
+impl PartialEq for MemoryReport {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::memory_analysis::PrefixStats;
+
+    #[test]
+    fn test_get_for_server_keeps_analysis() {
+        let db = Database::new_in_memory().unwrap();
+        let server_id = Uuid::now_v7();
+        let report = MemoryReport {
+            server_id,
+            analysis: MemoryAnalysis {
+                key_count: 2,
+                total_memory: 128,
+                prefixes: vec![PrefixStats {
+                    prefix: "user".to_string(),
+                    keys: 2,
+                    memory: 128,
+                }],
+                ..MemoryAnalysis::default()
+            },
+            ..MemoryReport::default()
+        }
+        .create(&db)
+        .unwrap();
+        MemoryReport::default().create(&db).unwrap();
+
+        let reports = MemoryReport::get_for_server(&server_id, &db).unwrap();
+        assert_eq!(reports, vec![report.clone()]);
+        assert_eq!(reports[0].analysis, report.analysis);
+    }
+}
@@ -0,0 +1,323 @@
+use super::Model;
+use crate::core::{AppError, Database};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+/// Setting key the metrics sampler configuration is stored under.
+pub const METRICS_SETTINGS_SETTING: &str = "metrics_settings";
+
+/// How the background sampler polls an opened server.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsSettings {
+    /// Seconds between two `INFO` polls.
+    pub interval_secs: u32,
+    /// Number of samples kept in memory for the opened server.
+    pub capacity: u32,
+    /// Whether samples are also written to the database.
+    pub persist: bool,
+    /// Persisted samples older than this many days are removed.
+    pub max_age_days: u32,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 2,
+            capacity: 1800,
+            persist: false,
+            max_age_days: 7,
+        }
+    }
+}
+
+/// Rates and gauges computed from two consecutive `INFO` polls.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricSample {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub ops_per_sec: f64,
+    pub used_memory: i64,
+    pub connected_clients: u32,
+    /// Keyspace hits over lookups during the interval, `None` when nothing was looked up.
+    pub hit_ratio: Option<f64>,
+    pub input_bytes_per_sec: f64,
+    pub output_bytes_per_sec: f64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub sampled_at: OffsetDateTime,
+}
+
+impl MetricSample {
+    /// Returns a server's persisted samples taken at or after `since`, oldest first.
+    pub fn find_since(
+        server_id: &Uuid,
+        since: OffsetDateTime,
+        db: &Database,
+    ) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE server_id = ? AND sampled_at >= ? ORDER BY sampled_at",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving metric samples: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let samples: Result<Vec<Self>, _> = stmt
+            .query_map(
+                params![server_id.to_string(), since.format(&Rfc3339).unwrap()],
+                Self::from_row,
+            )
+            .map_err(|e| {
+                log::error!(
+                    "Failed to execute query while retrieving metric samples: {}",
+                    e
+                );
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        samples.map_err(|e| {
+            log::error!("Failed to collect metric samples from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    /// Removes a server's samples older than `max_age_days`.
+    pub fn prune(server_id: &Uuid, max_age_days: u32, db: &Database) -> Result<(), AppError> {
+        let cutoff = OffsetDateTime::now_utc() - Duration::days(max_age_days as i64);
+        let sql = format!(
+            "DELETE FROM {} WHERE server_id = ? AND sampled_at < ?",
+            Self::table_name()
+        );
+        db.get_connection()
+            .execute(
+                &sql,
+                params![server_id.to_string(), cutoff.format(&Rfc3339).unwrap()],
+            )
+            .map(|_| ())
+            .map_err(|e| {
+                log::error!("Failed to prune metric samples: {}", e);
+                AppError::DbQueryFailed
+            })
+    }
+}
+
+impl Default for MetricSample {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            ops_per_sec: 0.0,
+            used_memory: 0,
+            connected_clients: 0,
+            hit_ratio: None,
+            input_bytes_per_sec: 0.0,
+            output_bytes_per_sec: 0.0,
+            sampled_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for MetricSample {
+    fn table_name() -> &'static str {
+        "metric_samples"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {} ORDER BY sampled_at", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving metric samples: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let samples_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving metric samples: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let samples: Result<Vec<Self>, _> = samples_iter.collect();
+        samples.map_err(|e| {
+            log::error!("Failed to collect metric samples from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    /// Binds the values directly since `hit_ratio` has to be stored as `NULL` when missing.
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            Self::table_name()
+        );
+
+        db.get_connection()
+            .execute(
+                &sql,
+                params![
+                    self.id.to_string(),
+                    self.server_id.to_string(),
+                    self.ops_per_sec,
+                    self.used_memory,
+                    self.connected_clients,
+                    self.hit_ratio,
+                    self.input_bytes_per_sec,
+                    self.output_bytes_per_sec,
+                    self.sampled_at.format(&Rfc3339).unwrap(),
+                ],
+            )
+            .map_err(|e| {
+                log::error!("Failed to save metric sample: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding metric sample by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding metric sample by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            ops_per_sec: row.get("ops_per_sec")?,
+            used_memory: row.get("used_memory")?,
+            connected_clients: row.get("connected_clients")?,
+            hit_ratio: row.get("hit_ratio")?,
+            input_bytes_per_sec: row.get("input_bytes_per_sec")?,
+            output_bytes_per_sec: row.get("output_bytes_per_sec")?,
+            sampled_at: row.get("sampled_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.ops_per_sec.to_string(),
+            self.used_memory.to_string(),
+            self.connected_clients.to_string(),
+            self.hit_ratio.map(|r| r.to_string()).unwrap_or_default(),
+            self.input_bytes_per_sec.to_string(),
+            self.output_bytes_per_sec.to_string(),
+            self.sampled_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for MetricSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MetricSample {{ id: {}, server_id: {}, ops_per_sec: {:.2}, used_memory: {} }}",
+            self.id, self.server_id, self.ops_per_sec, self.used_memory
+        )
+    }
+}
+
+impl PartialEq for MetricSample {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for MetricSample {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_since_and_prune() {
+        let db = Database::new_in_memory().unwrap();
+        let server_id = Uuid::now_v7();
+        let now = OffsetDateTime::now_utc();
+
+        for (age, hit_ratio) in [
+            (Duration::days(10), Some(0.5)),
+            (Duration::minutes(1), None),
+        ] {
+            MetricSample {
+                server_id,
+                hit_ratio,
+                sampled_at: now - age,
+                ..MetricSample::default()
+            }
+            .create(&db)
+            .unwrap();
+        }
+
+        let samples = MetricSample::find_since(&server_id, now - Duration::hours(1), &db).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].hit_ratio, None);
+
+        MetricSample::prune(&server_id, 7, &db).unwrap();
+        assert_eq!(MetricSample::get(&db).unwrap().len(), 1);
+    }
+}
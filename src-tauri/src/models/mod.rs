@@ -1,5 +1,28 @@
+mod audit_entry;
+mod command_docs_cache;
+mod command_guard;
+mod command_history_entry;
+mod memory_report;
+mod metric_sample;
 mod model;
+mod script;
 mod server;
+mod setting;
+mod slowlog_record;
+mod snippet;
 
+pub use audit_entry::AuditEntry;
+pub use command_docs_cache::CommandDocsCache;
+pub use command_guard::{CommandGuard, COMMAND_GUARD_SETTING};
+pub use command_history_entry::{CommandHistoryEntry, HistoryRetention, HISTORY_RETENTION_SETTING};
+pub use memory_report::MemoryReport;
+pub use metric_sample::{MetricSample, MetricsSettings, METRICS_SETTINGS_SETTING};
 pub use model::Model;
-pub use server::Server;
+pub use script::Script;
+pub use server::{
+    ConnectionKind, SentinelAddress, SentinelSettings, Server, ServerBundle, SshTunnelSettings,
+    SERVER_BUNDLE_VERSION,
+};
+pub use setting::Setting;
+pub use slowlog_record::SlowlogRecord;
+pub use snippet::Snippet;
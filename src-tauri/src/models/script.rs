@@ -0,0 +1,248 @@
+use super::Model;
+use crate::core::{AppError, Database};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+/// A Lua script kept in the library of a server.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct Script {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub name: String,
+    pub body: String,
+    /// The digest `EVALSHA` and `SCRIPT EXISTS` refer to the script by.
+    pub sha1: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl Script {
+    pub fn from_payload(server_id: Uuid, name: String, body: String) -> Self {
+        Self {
+            server_id,
+            name,
+            sha1: Self::sha1_of(&body),
+            body,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the lowercase hex SHA1 digest the server computes for a script body.
+    pub fn sha1_of(body: &str) -> String {
+        redis::Script::new(body).get_hash().to_string()
+    }
+
+    /// Returns every script kept for a server, sorted by name.
+    pub fn get_for_server(server_id: &Uuid, db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE server_id = ? ORDER BY name",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving scripts: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let scripts: Result<Vec<Self>, _> = stmt
+            .query_map([server_id.to_string()], Self::from_row)
+            .map_err(|e| {
+                log::error!("Failed to execute query while retrieving scripts: {}", e);
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        scripts.map_err(|e| {
+            log::error!("Failed to collect scripts from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            name: String::new(),
+            body: String::new(),
+            sha1: String::new(),
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for Script {
+    fn table_name() -> &'static str {
+        "scripts"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {} ORDER BY name", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving all scripts: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let scripts_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving all scripts: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let scripts: Result<Vec<Self>, _> = scripts_iter.collect();
+        scripts.map_err(|e| {
+            log::error!("Failed to collect scripts from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save script: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding script by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding script by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            name: row.get("name")?,
+            body: row.get("body")?,
+            sha1: row.get("sha1")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.name.clone(),
+            self.body.clone(),
+            self.sha1.clone(),
+            self.created_at.format(&Rfc3339).unwrap(),
+            self.updated_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Script {{ id: {}, server_id: {}, name: {}, sha1: {} }}",
+            self.id, self.server_id, self.name, self.sha1
+        )
+    }
+}
+
+impl PartialEq for Script {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha1_matches_server_digest() {
+        // SHA1 of `return 1`, as returned by `SCRIPT LOAD "return 1"`.
+        assert_eq!(
+            Script::sha1_of("return 1"),
+            "e0e1f9fabfc9d4800c877a703b823ac0578ff8db"
+        );
+
+        let script = Script::from_payload(Uuid::nil(), "One".into(), "return 1".into());
+        assert_eq!(script.sha1, Script::sha1_of("return 1"));
+    }
+}
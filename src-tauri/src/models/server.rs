@@ -1,16 +1,114 @@
 use super::Model;
 use crate::core::{AppError, Database};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 use uuid::Uuid;
 
+/// How the app reaches the server.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConnectionKind {
+    /// `address` and `port`, directly or through the sentinels, the cluster or a tunnel.
+    #[default]
+    Tcp,
+    /// A local socket, e.g. `/var/run/redis/redis.sock`.
+    UnixSocket { path: String },
+}
+
+impl ConnectionKind {
+    /// The name stored in the `connection_kind` column.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::UnixSocket { .. } => "unix_socket",
+        }
+    }
+}
+
+/// A sentinel the master of a Sentinel-managed server is resolved through.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SentinelAddress {
+    pub address: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct SentinelSettings {
+    /// Name the master is monitored under, e.g. `mymaster`.
+    pub master_name: String,
+    /// Asked in order until one knows the master.
+    pub sentinels: Vec<SentinelAddress>,
+    /// The sentinels' own ACL user, their `requirepass` is often not the master's.
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub tls: bool,
+}
+
+/// Version written to exported bundles, bundles of a newer version are refused.
+pub const SERVER_BUNDLE_VERSION: u32 = 1;
+
+/// Saved servers as written by `export_servers` and read by `import_servers`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerBundle {
+    pub version: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub exported_at: OffsetDateTime,
+    pub servers: Vec<Server>,
+}
+
+impl ServerBundle {
+    pub fn new(servers: Vec<Server>) -> Self {
+        Self {
+            version: SERVER_BUNDLE_VERSION,
+            exported_at: OffsetDateTime::now_utc(),
+            servers,
+        }
+    }
+}
+
+/// A bastion the server is reached through with a local SSH port forward.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct SshTunnelSettings {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    /// Used when no private key is set.
+    pub password: Option<String>,
+    /// An OpenSSH or PEM private key, tried before the password.
+    pub private_key_path: Option<String>,
+    pub passphrase: Option<String>,
+    /// Verifies the bastion's host key against this file, any key is accepted when unset.
+    pub known_hosts_path: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, Clone)]
 pub struct Server {
     pub id: Uuid,
     pub name: String,
     pub address: String,
     pub port: u16,
+    /// `address` and `port` are ignored unless it is `Tcp`.
+    pub connection_kind: ConnectionKind,
+    /// The ACL user, `default` when unset.
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// The logical database selected on every connection.
+    pub database: i64,
+    pub tls: bool,
+    /// Rejects every write command before it reaches the server.
+    pub read_only: bool,
+    /// When set, `address` and `port` are ignored and the sentinels are asked for the
+    /// current master on every connection.
+    pub sentinel: Option<SentinelSettings>,
+    /// Connects to a Redis Cluster, the server only seeds the node discovery.
+    pub cluster: bool,
+    /// Forwards the connection through a bastion, `address` and `port` are then resolved
+    /// by the bastion.
+    pub ssh_tunnel: Option<SshTunnelSettings>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
@@ -30,6 +128,60 @@ impl Server {
             ..Self::new()
         }
     }
+
+    /// Returns a copy without the Redis and sentinel passwords and the tunnel password and
+    /// passphrase.
+    pub fn without_secrets(&self) -> Self {
+        Self {
+            password: None,
+            sentinel: self.sentinel.clone().map(|sentinel| SentinelSettings {
+                password: None,
+                ..sentinel
+            }),
+            ssh_tunnel: self.ssh_tunnel.clone().map(|ssh_tunnel| SshTunnelSettings {
+                password: None,
+                passphrase: None,
+                ..ssh_tunnel
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Whether both servers connect to the same database as the same user through the
+    /// same route, whatever their name and options. `127.0.0.1` behind two bastions are
+    /// two different servers.
+    pub fn same_target(&self, other: &Self) -> bool {
+        let same_address = match &self.connection_kind {
+            ConnectionKind::Tcp => {
+                self.address.eq_ignore_ascii_case(&other.address) && self.port == other.port
+            }
+            ConnectionKind::UnixSocket { .. } => true,
+        };
+        let same_tunnel = match (&self.ssh_tunnel, &other.ssh_tunnel) {
+            (Some(tunnel), Some(other)) => {
+                tunnel.host.eq_ignore_ascii_case(&other.host)
+                    && tunnel.port == other.port
+                    && tunnel.user == other.user
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        same_address
+            && same_tunnel
+            && self.connection_kind == other.connection_kind
+            && self
+                .sentinel
+                .as_ref()
+                .map(|s| (&s.master_name, &s.sentinels))
+                == other
+                    .sentinel
+                    .as_ref()
+                    .map(|s| (&s.master_name, &s.sentinels))
+            && self.cluster == other.cluster
+            && self.tls == other.tls
+            && self.database == other.database
+            && self.username == other.username
+    }
 }
 
 impl Default for Server {
@@ -39,6 +191,15 @@ impl Default for Server {
             name: String::new(),
             address: String::new(),
             port: 0,
+            connection_kind: ConnectionKind::Tcp,
+            username: None,
+            password: None,
+            database: 0,
+            tls: false,
+            read_only: false,
+            sentinel: None,
+            cluster: false,
+            ssh_tunnel: None,
             created_at: OffsetDateTime::now_utc(),
             updated_at: OffsetDateTime::now_utc(),
         }
@@ -146,6 +307,21 @@ impl Model for Server {
             rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
         })?;
 
+        let kind: String = row.get("connection_kind")?;
+        let connection_kind = match kind.as_str() {
+            "tcp" => ConnectionKind::Tcp,
+            "unix_socket" => ConnectionKind::UnixSocket {
+                path: row.get("socket_path")?,
+            },
+            _ => {
+                return Err(rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    format!("unknown connection kind: {}", kind).into(),
+                ))
+            }
+        };
+
         Ok(Self {
             id,
             name: row.get("name")?,
@@ -153,6 +329,15 @@ impl Model for Server {
             port: row.get("port")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
+            read_only: row.get("read_only")?,
+            sentinel: json_column(row, "sentinel")?,
+            cluster: row.get("cluster")?,
+            ssh_tunnel: json_column(row, "ssh_tunnel")?,
+            connection_kind,
+            username: optional_text(row, "username")?,
+            password: optional_text(row, "password")?,
+            database: row.get("database")?,
+            tls: row.get("tls")?,
         })
     }
 
@@ -164,16 +349,54 @@ impl Model for Server {
             self.port.to_string(),
             self.created_at.format(&Rfc3339).unwrap(),
             self.updated_at.format(&Rfc3339).unwrap(),
+            (self.read_only as u8).to_string(),
+            self.sentinel
+                .as_ref()
+                .map(|sentinel| serde_json::to_string(sentinel).unwrap())
+                .unwrap_or_default(),
+            (self.cluster as u8).to_string(),
+            self.ssh_tunnel
+                .as_ref()
+                .map(|ssh_tunnel| serde_json::to_string(ssh_tunnel).unwrap())
+                .unwrap_or_default(),
+            self.connection_kind.name().to_string(),
+            match &self.connection_kind {
+                ConnectionKind::UnixSocket { path } => path.clone(),
+                ConnectionKind::Tcp => String::new(),
+            },
+            self.username.clone().unwrap_or_default(),
+            self.password.clone().unwrap_or_default(),
+            self.database.to_string(),
+            (self.tls as u8).to_string(),
         ]
     }
 }
 
+fn optional_text(row: &rusqlite::Row, name: &str) -> Result<Option<String>, rusqlite::Error> {
+    let value: String = row.get(name)?;
+    Ok(Some(value).filter(|value| !value.is_empty()))
+}
+
+/// Reads settings stored as JSON, empty for servers that do not use them.
+fn json_column<T: DeserializeOwned>(
+    row: &rusqlite::Row,
+    name: &str,
+) -> Result<Option<T>, rusqlite::Error> {
+    let value: String = row.get(name)?;
+    match value.is_empty() {
+        true => Ok(None),
+        false => serde_json::from_str(&value).map(Some).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        }),
+    }
+}
+
 impl fmt::Display for Server {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Server {{ id: {}, name: {}, address: {}, port: {} }}",
-            self.id, self.name, self.address, self.port
+            "Server {{ id: {}, name: {}, address: {}, port: {}, read_only: {} }}",
+            self.id, self.name, self.address, self.port, self.read_only
         )
     }
 }
@@ -183,3 +406,150 @@ impl PartialEq for Server {
         self.id == other.id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentinel_settings_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server {
+            sentinel: Some(SentinelSettings {
+                master_name: "mymaster".to_string(),
+                sentinels: vec![SentinelAddress {
+                    address: "10.0.0.1".to_string(),
+                    port: 26379,
+                }],
+                password: Some("sentinel-secret".to_string()),
+                tls: true,
+                ..SentinelSettings::default()
+            }),
+            ..Server::from_payload("Sentinel".to_string(), String::new(), 0)
+        }
+        .create(&db)
+        .unwrap();
+        let direct = Server::from_payload("Direct".to_string(), "localhost".to_string(), 6379)
+            .create(&db)
+            .unwrap();
+
+        let stored = Server::find_by_id(&server.id.to_string(), &db).unwrap();
+        assert_eq!(stored.sentinel, server.sentinel);
+        let stored = Server::find_by_id(&direct.id.to_string(), &db).unwrap();
+        assert_eq!(stored.sentinel, None);
+    }
+
+    #[test]
+    fn test_ssh_tunnel_settings_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server {
+            ssh_tunnel: Some(SshTunnelSettings {
+                host: "bastion.example.com".to_string(),
+                port: 22,
+                user: "deploy".to_string(),
+                private_key_path: Some("/home/deploy/.ssh/id_ed25519".to_string()),
+                ..SshTunnelSettings::default()
+            }),
+            ..Server::from_payload("Tunnelled".to_string(), "10.0.0.5".to_string(), 6379)
+        }
+        .create(&db)
+        .unwrap();
+
+        let stored = Server::find_by_id(&server.id.to_string(), &db).unwrap();
+        assert_eq!(stored.ssh_tunnel, server.ssh_tunnel);
+        assert_eq!(stored.sentinel, None);
+    }
+
+    #[test]
+    fn test_connection_kind_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server {
+            connection_kind: ConnectionKind::UnixSocket {
+                path: "/var/run/redis/redis.sock".to_string(),
+            },
+            ..Server::from_payload("Sidecar".to_string(), String::new(), 0)
+        }
+        .create(&db)
+        .unwrap();
+        let direct = Server::from_payload("Direct".to_string(), "localhost".to_string(), 6379)
+            .create(&db)
+            .unwrap();
+
+        let stored = Server::find_by_id(&server.id.to_string(), &db).unwrap();
+        assert_eq!(stored.connection_kind, server.connection_kind);
+        let stored = Server::find_by_id(&direct.id.to_string(), &db).unwrap();
+        assert_eq!(stored.connection_kind, ConnectionKind::Tcp);
+    }
+
+    #[test]
+    fn test_credentials_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = Server {
+            username: Some("app".to_string()),
+            password: Some("s3cret".to_string()),
+            database: 3,
+            tls: true,
+            ..Server::from_payload("Managed".to_string(), "redis.example.com".to_string(), 6380)
+        }
+        .create(&db)
+        .unwrap();
+
+        let stored = Server::find_by_id(&server.id.to_string(), &db).unwrap();
+        assert_eq!(stored.username.as_deref(), Some("app"));
+        assert_eq!(stored.password.as_deref(), Some("s3cret"));
+        assert_eq!(stored.database, 3);
+        assert!(stored.tls);
+    }
+
+    #[test]
+    fn test_without_secrets() {
+        let server = Server {
+            password: Some("s3cret".to_string()),
+            ssh_tunnel: Some(SshTunnelSettings {
+                password: Some("bastion".to_string()),
+                passphrase: Some("key".to_string()),
+                private_key_path: Some("/home/deploy/.ssh/id_ed25519".to_string()),
+                ..SshTunnelSettings::default()
+            }),
+            ..Server::default()
+        };
+
+        let exported = server.without_secrets();
+        assert_eq!(exported.password, None);
+        let ssh_tunnel = exported.ssh_tunnel.unwrap();
+        assert_eq!((ssh_tunnel.password, ssh_tunnel.passphrase), (None, None));
+        assert!(ssh_tunnel.private_key_path.is_some());
+    }
+
+    #[test]
+    fn test_same_target() {
+        let server = Server::from_payload("A".to_string(), "Redis.local".to_string(), 6379);
+        let renamed = Server {
+            read_only: true,
+            ..Server::from_payload("B".to_string(), "redis.local".to_string(), 6379)
+        };
+        assert!(server.same_target(&renamed));
+
+        let other_database = Server {
+            database: 1,
+            ..renamed.clone()
+        };
+        assert!(!server.same_target(&other_database));
+        assert!(!server.same_target(&Server {
+            tls: true,
+            ..renamed.clone()
+        }));
+
+        let socket = Server {
+            connection_kind: ConnectionKind::UnixSocket {
+                path: "/tmp/redis.sock".to_string(),
+            },
+            ..Server::default()
+        };
+        assert!(!server.same_target(&socket));
+        assert!(socket.same_target(&Server {
+            address: "ignored".to_string(),
+            ..socket.clone()
+        }));
+    }
+}
@@ -0,0 +1,191 @@
+use super::Model;
+use crate::core::{AppError, Database};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// An application-wide preference stored as a JSON value under a unique key.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl Setting {
+    /// Reads and deserializes a setting, returning `None` when it was never stored.
+    pub fn get_value<T: DeserializeOwned>(key: &str, db: &Database) -> Result<Option<T>, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE key = ? LIMIT 1", Self::table_name());
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while reading setting {}: {}",
+                key,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let mut rows = stmt.query_map([key], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while reading setting {}: {}",
+                key,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        match rows.next() {
+            Some(Ok(setting)) => serde_json::from_str(&setting.value).map(Some).map_err(|e| {
+                log::error!("Failed to deserialize setting {}: {}", key, e);
+                AppError::DbQueryFailed
+            }),
+            Some(Err(e)) => {
+                log::error!("Failed to read setting {}: {}", key, e);
+                Err(AppError::DbQueryFailed)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Serializes and stores a setting, replacing any previous value.
+    pub fn set_value<T: Serialize>(key: &str, value: &T, db: &Database) -> Result<(), AppError> {
+        let value = serde_json::to_string(value).map_err(|e| {
+            log::error!("Failed to serialize setting {}: {}", key, e);
+            AppError::DbQueryFailed
+        })?;
+
+        Self {
+            key: key.to_string(),
+            value,
+            updated_at: OffsetDateTime::now_utc(),
+        }
+        .save(db)
+    }
+}
+
+impl Model for Setting {
+    fn table_name() -> &'static str {
+        "settings"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {}", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving all settings: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let settings_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving all settings: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let settings: Result<Vec<Self>, _> = settings_iter.collect();
+        settings.map_err(|e| {
+            log::error!("Failed to collect settings from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    /// Settings are identified by their key, so creating one is the same as saving it.
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        self.save(db)?;
+        Ok(self.clone())
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE key = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.key])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save setting {}: {}", self.key, e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE key = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, key: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding setting {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding setting {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        Ok(Self {
+            key: row.get("key")?,
+            value: row.get("value")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.value.clone(),
+            self.updated_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Setting {{ key: {}, value: {} }}", self.key, self.value)
+    }
+}
+
+impl PartialEq for Setting {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
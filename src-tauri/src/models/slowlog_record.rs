@@ -0,0 +1,329 @@
+use super::Model;
+use crate::{
+    core::{AppError, Database},
+    services::SlowlogEntry,
+};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use uuid::Uuid;
+
+/// Captured entries older than this many days are removed when the server is opened.
+pub const SLOWLOG_RETENTION_DAYS: i64 = 30;
+
+/// A slow log entry copied from a server, kept after the server's own log rolled over.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct SlowlogRecord {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    /// Id of the entry on the server, unique until the server restarts.
+    pub entry_id: i64,
+    pub timestamp: i64,
+    pub duration_us: i64,
+    pub args: Vec<String>,
+    pub client_addr: String,
+    pub client_name: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub captured_at: OffsetDateTime,
+}
+
+impl SlowlogRecord {
+    pub fn from_entry(server_id: Uuid, entry: &SlowlogEntry) -> Self {
+        Self {
+            server_id,
+            entry_id: entry.id,
+            timestamp: entry.timestamp,
+            duration_us: entry.duration_us,
+            args: entry.args.clone(),
+            client_addr: entry.client_addr.clone(),
+            client_name: entry.client_name.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a page of a server's captured entries, newest first.
+    pub fn search(
+        server_id: &Uuid,
+        limit: u32,
+        offset: u32,
+        db: &Database,
+    ) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE server_id = ?1 \
+            ORDER BY timestamp DESC, entry_id DESC LIMIT ?2 OFFSET ?3",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while searching slow log entries: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let records: Result<Vec<Self>, _> = stmt
+            .query_map(
+                params![server_id.to_string(), limit, offset],
+                Self::from_row,
+            )
+            .map_err(|e| {
+                log::error!(
+                    "Failed to execute query while searching slow log entries: {}",
+                    e
+                );
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        records.map_err(|e| {
+            log::error!(
+                "Failed to collect slow log entries from query result: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })
+    }
+
+    /// Removes a server's entries captured more than `SLOWLOG_RETENTION_DAYS` ago.
+    pub fn prune(server_id: &Uuid, db: &Database) -> Result<(), AppError> {
+        let cutoff = OffsetDateTime::now_utc() - Duration::days(SLOWLOG_RETENTION_DAYS);
+        let sql = format!(
+            "DELETE FROM {} WHERE server_id = ? AND captured_at < ?",
+            Self::table_name()
+        );
+        db.get_connection()
+            .execute(
+                &sql,
+                params![server_id.to_string(), cutoff.format(&Rfc3339).unwrap()],
+            )
+            .map(|_| ())
+            .map_err(|e| {
+                log::error!("Failed to prune slow log entries: {}", e);
+                AppError::DbQueryFailed
+            })
+    }
+}
+
+impl Default for SlowlogRecord {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            entry_id: 0,
+            timestamp: 0,
+            duration_us: 0,
+            args: vec![],
+            client_addr: String::new(),
+            client_name: String::new(),
+            captured_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for SlowlogRecord {
+    fn table_name() -> &'static str {
+        "slowlog_entries"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} ORDER BY timestamp DESC",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving slow log entries: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let records_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving slow log entries: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let records: Result<Vec<Self>, _> = records_iter.collect();
+        records.map_err(|e| {
+            log::error!(
+                "Failed to collect slow log entries from query result: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    /// Entries captured twice replace the earlier copy through the unique
+    /// `(server_id, entry_id, timestamp)` index.
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save slow log entry: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding slow log entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding slow log entry by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        let args: String = row.get("args")?;
+        let args = serde_json::from_str(&args).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            entry_id: row.get("entry_id")?,
+            timestamp: row.get("timestamp")?,
+            duration_us: row.get("duration_us")?,
+            args,
+            client_addr: row.get("client_addr")?,
+            client_name: row.get("client_name")?,
+            captured_at: row.get("captured_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.entry_id.to_string(),
+            self.timestamp.to_string(),
+            self.duration_us.to_string(),
+            serde_json::to_string(&self.args).unwrap(),
+            self.client_addr.clone(),
+            self.client_name.clone(),
+            self.captured_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for SlowlogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SlowlogRecord {{ id: {}, server_id: {}, entry_id: {}, duration_us: {} }}",
+            self.id, self.server_id, self.entry_id, self.duration_us
+        )
+    }
+}
+
+impl PartialEq for SlowlogRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captured_entries_are_not_duplicated() {
+        let db = Database::new_in_memory().unwrap();
+        let server_id = Uuid::now_v7();
+        let entry = SlowlogEntry {
+            id: 3,
+            timestamp: 1718030492,
+            duration_us: 20000,
+            args: vec!["KEYS".to_string(), "*".to_string()],
+            ..SlowlogEntry::default()
+        };
+
+        for _ in 0..2 {
+            SlowlogRecord::from_entry(server_id, &entry)
+                .create(&db)
+                .unwrap();
+        }
+        // Same id after a restart, but logged at another time.
+        SlowlogRecord::from_entry(
+            server_id,
+            &SlowlogEntry {
+                timestamp: 1718039999,
+                ..entry.clone()
+            },
+        )
+        .create(&db)
+        .unwrap();
+
+        let records = SlowlogRecord::search(&server_id, 10, 0, &db).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].timestamp, 1718039999);
+        assert_eq!(records[1].args, entry.args);
+    }
+}
@@ -0,0 +1,469 @@
+use super::Model;
+use crate::core::{AppError, Database};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, ops::Range};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use uuid::Uuid;
+
+/// A named command line pinned for a server.
+/// Parameters are written as `{{name}}` placeholders inside the command.
+#[derive(Debug, Serialize, Deserialize, Eq, Clone)]
+pub struct Snippet {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub name: String,
+    pub command: String,
+    pub parameters: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl Snippet {
+    pub fn from_payload(server_id: Uuid, name: String, command: String) -> Self {
+        Self {
+            server_id,
+            name,
+            parameters: Self::parse_parameters(&command),
+            command,
+            ..Self::default()
+        }
+    }
+
+    /// Finds the `{{name}}` placeholders of a command, whatever the spacing inside the
+    /// braces, as their byte range and trimmed name.
+    fn placeholders(command: &str) -> Vec<(Range<usize>, String)> {
+        let mut placeholders = vec![];
+        let mut offset = 0;
+
+        while let Some(start) = command[offset..].find("{{").map(|start| offset + start) {
+            let Some(end) = command[start + 2..].find("}}").map(|end| start + end + 4) else {
+                break;
+            };
+
+            let name = command[start + 2..end - 2].trim().to_string();
+            if !name.is_empty() {
+                placeholders.push((start..end, name));
+            }
+            offset = end;
+        }
+
+        placeholders
+    }
+
+    /// Extracts the unique `{{name}}` placeholders of a command, in order of appearance.
+    pub fn parse_parameters(command: &str) -> Vec<String> {
+        let mut parameters: Vec<String> = vec![];
+        for (_, name) in Self::placeholders(command) {
+            if !parameters.contains(&name) {
+                parameters.push(name);
+            }
+        }
+        parameters
+    }
+
+    /// Substitutes every placeholder so its value stays a single argument. Values are double
+    /// quoted, or only escaped when the placeholder already is inside a quoted argument. A
+    /// closing quote must end an argument, so when more text follows the value, e.g. in
+    /// `{{id}}:profile`, the quote is closed at the end of the argument instead.
+    ///
+    /// # Returns
+    /// * `None` if a parameter has no value.
+    pub fn render(&self, values: &HashMap<String, String>) -> Option<String> {
+        let mut command = String::with_capacity(self.command.len());
+        let mut quote: Option<char> = None;
+        // Set while the argument being written was opened with a double quote before a value.
+        let mut wrapped = false;
+        let mut last = 0;
+
+        for (range, name) in Self::placeholders(&self.command) {
+            let value = values.get(&name)?;
+            (quote, wrapped) = push_text(
+                &mut command,
+                &self.command[last..range.start],
+                quote,
+                wrapped,
+            );
+
+            let ends_argument = self.command[range.end..]
+                .chars()
+                .next()
+                .is_none_or(char::is_whitespace);
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+            match quote {
+                _ if wrapped => command.push_str(&escaped),
+                Some('\'') => command.push_str(&value.replace('\'', "\\'")),
+                Some(_) => command.push_str(&escaped),
+                None if ends_argument => command.push_str(&format!("\"{}\"", escaped)),
+                None => {
+                    command.push('"');
+                    command.push_str(&escaped);
+                    wrapped = true;
+                }
+            }
+            last = range.end;
+        }
+        (_, wrapped) = push_text(&mut command, &self.command[last..], quote, wrapped);
+        if wrapped {
+            command.push('"');
+        }
+
+        Some(command)
+    }
+
+    /// Returns every snippet pinned for a server, sorted by name.
+    pub fn get_for_server(server_id: &Uuid, db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!(
+            "SELECT * FROM {} WHERE server_id = ? ORDER BY name",
+            Self::table_name()
+        );
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving snippets: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let snippets: Result<Vec<Self>, _> = stmt
+            .query_map([server_id.to_string()], Self::from_row)
+            .map_err(|e| {
+                log::error!("Failed to execute query while retrieving snippets: {}", e);
+                AppError::DbQueryFailed
+            })?
+            .collect();
+        snippets.map_err(|e| {
+            log::error!("Failed to collect snippets from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+}
+
+/// Appends text of the command between placeholders and returns the quote still open and
+/// whether the argument is still `wrapped`. Inside a wrapped argument the text is escaped for
+/// double quotes, its own quotes are dropped, and the double quote is closed where the
+/// argument ends.
+fn push_text(
+    command: &mut String,
+    text: &str,
+    mut quote: Option<char>,
+    wrapped: bool,
+) -> (Option<char>, bool) {
+    if !wrapped {
+        command.push_str(text);
+        return (open_quote(quote, text), false);
+    }
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => {
+                command.push('"');
+                command.push_str(&text[i..]);
+                return (open_quote(None, &text[i..]), false);
+            }
+            (None, '"' | '\'') => quote = Some(c),
+            (Some('"'), '\\') => {
+                command.push('\\');
+                if let Some((_, escaped)) = chars.next() {
+                    command.push(escaped);
+                }
+            }
+            (Some('\''), '\\') if chars.peek().is_some_and(|(_, next)| *next == '\'') => {
+                chars.next();
+                command.push('\'');
+            }
+            (Some(open), c) if c == open => quote = None,
+            (_, '"' | '\\') => {
+                command.push('\\');
+                command.push(c);
+            }
+            _ => command.push(c),
+        }
+    }
+    (quote, true)
+}
+
+/// Returns the quote still open after `text`, following the rules of `split_command_args`:
+/// a quote opens anywhere outside quotes and a backslash escapes the next character inside
+/// double quotes or a single quote inside single quotes.
+fn open_quote(mut quote: Option<char>, text: &str) -> Option<char> {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('"'), '\\') => {
+                chars.next();
+            }
+            (Some('\''), '\\') if chars.peek() == Some(&'\'') => {
+                chars.next();
+            }
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            _ => {}
+        }
+    }
+    quote
+}
+
+impl Default for Snippet {
+    fn default() -> Self {
+        Self {
+            id: Uuid::nil(),
+            server_id: Uuid::nil(),
+            name: String::new(),
+            command: String::new(),
+            parameters: vec![],
+            created_at: OffsetDateTime::now_utc(),
+            updated_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl Model for Snippet {
+    fn table_name() -> &'static str {
+        "snippets"
+    }
+
+    fn get(db: &Database) -> Result<Vec<Self>, AppError> {
+        let sql = format!("SELECT * FROM {} ORDER BY name", Self::table_name());
+        log::debug!("Executing query: {}", sql);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while retrieving all snippets: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let snippets_iter = stmt.query_map([], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while retrieving all snippets: {}",
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let snippets: Result<Vec<Self>, _> = snippets_iter.collect();
+        snippets.map_err(|e| {
+            log::error!("Failed to collect snippets from query result: {}", e);
+            AppError::DbQueryFailed
+        })
+    }
+
+    fn create(&self, db: &Database) -> Result<Self, AppError>
+    where
+        Self: Sized,
+    {
+        let mut _self = self.clone();
+        _self.id = Uuid::now_v7();
+        _self.save(db)?;
+        Ok(_self)
+    }
+
+    fn delete(&self, db: &Database) -> Result<bool, AppError>
+    where
+        Self: Sized,
+    {
+        let sql = format!("DELETE FROM {} WHERE id = ?", Self::table_name());
+        db.get_connection()
+            .execute(&sql, [&self.id.to_string()])
+            .map(|_| true)
+            .map_err(|_| AppError::DbQueryFailed)
+    }
+
+    fn save(&self, db: &Database) -> Result<(), AppError> {
+        let placeholders = "?,"
+            .repeat(self.to_db_values().len())
+            .trim_end_matches(',')
+            .to_string();
+
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} VALUES ({})",
+            Self::table_name(),
+            placeholders
+        );
+
+        db.get_connection()
+            .execute(&sql, self.to_db_values_safe())
+            .map_err(|e| {
+                log::error!("Failed to save snippet: {}", e);
+                AppError::DbQueryFailed
+            })?;
+        Ok(())
+    }
+
+    fn find_by_id(id: &str, db: &Database) -> Result<Self, AppError> {
+        let sql = format!("SELECT * FROM {} WHERE id = ? LIMIT 1", Self::table_name());
+        log::debug!("Executing query: {}, ID: {}", sql, id);
+
+        let mut stmt = db.get_connection().prepare(&sql).map_err(|e| {
+            log::error!(
+                "Failed to prepare statement while finding snippet by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+
+        let row = stmt.query_row([id], Self::from_row).map_err(|e| {
+            log::error!(
+                "Failed to execute query while finding snippet by id {}: {}",
+                id,
+                e
+            );
+            AppError::DbQueryFailed
+        })?;
+        Ok(row)
+    }
+
+    fn from_row(row: &rusqlite::Row) -> Result<Self, rusqlite::Error> {
+        let parse_uuid = |column: &str| -> Result<Uuid, rusqlite::Error> {
+            let value: String = row.get(column)?;
+            Uuid::parse_str(&value).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        };
+
+        let parameters: String = row.get("parameters")?;
+        let parameters = serde_json::from_str(&parameters).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(Self {
+            id: parse_uuid("id")?,
+            server_id: parse_uuid("server_id")?,
+            name: row.get("name")?,
+            command: row.get("command")?,
+            parameters,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+
+    fn to_db_values(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.server_id.to_string(),
+            self.name.clone(),
+            self.command.clone(),
+            serde_json::to_string(&self.parameters).unwrap(),
+            self.created_at.format(&Rfc3339).unwrap(),
+            self.updated_at.format(&Rfc3339).unwrap(),
+        ]
+    }
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Snippet {{ id: {}, server_id: {}, name: {}, command: {} }}",
+            self.id, self.server_id, self.name, self.command
+        )
+    }
+}
+
+impl PartialEq for Snippet {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::split_command_args;
+
+    #[test]
+    fn test_parse_parameters() {
+        assert_eq!(
+            Snippet::parse_parameters("HGET {{key}} {{ field }} {{key}}"),
+            vec!["key".to_string(), "field".to_string()]
+        );
+        assert!(Snippet::parse_parameters("PING").is_empty());
+        assert!(Snippet::parse_parameters("GET {{unterminated").is_empty());
+        assert_eq!(
+            Snippet::parse_parameters("GET {{  key}} {{}}"),
+            vec!["key".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let snippet = Snippet::from_payload(
+            Uuid::nil(),
+            "Get field".to_string(),
+            "HGET {{key}} {{ field }}".to_string(),
+        );
+        let values = HashMap::from([
+            ("key".to_string(), "user:1".to_string()),
+            ("field".to_string(), "full \"name\"".to_string()),
+        ]);
+
+        assert_eq!(
+            snippet.render(&values).unwrap(),
+            r#"HGET "user:1" "full \"name\"""#
+        );
+        assert!(snippet.render(&HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_render_value_followed_by_text() {
+        let snippet = Snippet::from_payload(
+            Uuid::nil(),
+            "Profile".to_string(),
+            r#"HGETALL {{id}}:profile:{{ part }}'s x' {{id}}"#.to_string(),
+        );
+        let values = HashMap::from([
+            ("id".to_string(), "a b".to_string()),
+            ("part".to_string(), r#"c "d"\"#.to_string()),
+        ]);
+
+        let rendered = snippet.render(&values).unwrap();
+        assert_eq!(
+            split_command_args(&rendered).unwrap(),
+            vec![
+                b"HGETALL".to_vec(),
+                br#"a b:profile:c "d"\s x"#.to_vec(),
+                b"a b".to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_inside_quotes_and_keys() {
+        let snippet = Snippet::from_payload(
+            Uuid::nil(),
+            "Tag".to_string(),
+            r#"SET "tag:{{  id}}" 'by {{user }}' user:{{id}} {{id}}:profile"#.to_string(),
+        );
+        let values = HashMap::from([
+            ("id".to_string(), "1".to_string()),
+            ("user".to_string(), "o'brien".to_string()),
+        ]);
+
+        let rendered = snippet.render(&values).unwrap();
+        assert_eq!(
+            rendered,
+            r#"SET "tag:1" 'by o\'brien' user:"1" "1:profile""#
+        );
+        assert_eq!(
+            split_command_args(&rendered).unwrap(),
+            vec![
+                b"SET".to_vec(),
+                b"tag:1".to_vec(),
+                b"by o'brien".to_vec(),
+                b"user:1".to_vec(),
+                b"1:profile".to_vec(),
+            ]
+        );
+    }
+}
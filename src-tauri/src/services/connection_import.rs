@@ -0,0 +1,410 @@
+use crate::{
+    core::AppError,
+    models::{SentinelAddress, SentinelSettings, Server, SshTunnelSettings},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// The app a connections file was exported from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionSource {
+    /// The JSON file of RedisInsight's "Export" on the database list.
+    RedisInsight,
+    /// Another Redis Desktop Manager's connection export, base64-encoded JSON.
+    Ardm,
+    /// One `host port [password]` per line, `#` starts a comment.
+    TextList,
+}
+
+/// An entry that could not be mapped onto a server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionImportError {
+    /// The name of the entry, or its position when it has none.
+    pub name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct RedisInsightSentinelMaster {
+    name: String,
+    /// The master's credentials, the connection's own are the sentinel's.
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct RedisInsightSshOptions {
+    host: String,
+    port: u16,
+    username: String,
+    password: Option<String>,
+    private_key: Option<String>,
+    passphrase: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RedisInsightConnection {
+    name: Option<String>,
+    host: String,
+    port: u16,
+    #[serde(default)]
+    db: i64,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    tls: bool,
+    /// `STANDALONE`, `CLUSTER` or `SENTINEL`.
+    #[serde(default)]
+    connection_type: String,
+    sentinel_master: Option<RedisInsightSentinelMaster>,
+    #[serde(default)]
+    ssh: bool,
+    ssh_options: Option<RedisInsightSshOptions>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ArdmSshOptions {
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+    /// A path to the key file.
+    privatekey: String,
+    passphrase: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct ArdmSentinelOptions {
+    master_name: String,
+    /// The password of the Redis nodes, `auth` is the sentinels' own.
+    node_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArdmConnection {
+    #[serde(default)]
+    name: String,
+    host: String,
+    port: u16,
+    #[serde(default)]
+    auth: String,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    cluster: bool,
+    #[serde(default)]
+    connection_read_only: bool,
+    ssh_options: Option<ArdmSshOptions>,
+    /// Only exported when TLS is enabled.
+    ssl_options: Option<JsonValue>,
+    sentinel_options: Option<ArdmSentinelOptions>,
+}
+
+/// Empty strings stand for unset fields in the exports.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.is_empty())
+}
+
+/// The exported connection points at a sentinel, so its address, credentials and TLS are
+/// the sentinel's.
+fn sentinel_settings(master_name: String, sentinel: &Server) -> SentinelSettings {
+    SentinelSettings {
+        master_name,
+        sentinels: vec![SentinelAddress {
+            address: sentinel.address.clone(),
+            port: sentinel.port,
+        }],
+        username: sentinel.username.clone(),
+        password: sentinel.password.clone(),
+        tls: sentinel.tls,
+    }
+}
+
+impl RedisInsightConnection {
+    fn into_server(self) -> Result<Server, ConnectionImportError> {
+        let name = non_empty(self.name).unwrap_or_else(|| format!("{}:{}", self.host, self.port));
+        let failed = |message: &str| ConnectionImportError {
+            name: name.clone(),
+            message: message.to_string(),
+        };
+        let mut server = Server {
+            name: name.clone(),
+            username: non_empty(self.username),
+            password: non_empty(self.password),
+            database: self.db,
+            tls: self.tls,
+            cluster: self.connection_type == "CLUSTER",
+            ..Server::from_payload(String::new(), self.host, self.port)
+        };
+
+        // The host of a Sentinel connection is a sentinel, the master is resolved through it.
+        if self.connection_type == "SENTINEL" {
+            let master = self
+                .sentinel_master
+                .ok_or_else(|| failed("Sentinel connection without a master name"))?;
+            server.sentinel = Some(sentinel_settings(master.name, &server));
+            server.username = non_empty(master.username);
+            server.password = non_empty(master.password);
+        }
+
+        if let Some(ssh) = self.ssh_options.filter(|_| self.ssh) {
+            // Keys are exported inline, only key files are supported.
+            if non_empty(ssh.private_key).is_some() {
+                return Err(failed(
+                    "SSH private keys cannot be imported, use a key file",
+                ));
+            }
+            server.ssh_tunnel = Some(SshTunnelSettings {
+                host: ssh.host,
+                port: ssh.port,
+                user: ssh.username,
+                password: non_empty(ssh.password),
+                passphrase: non_empty(ssh.passphrase),
+                ..SshTunnelSettings::default()
+            });
+        }
+        Ok(server)
+    }
+}
+
+impl ArdmConnection {
+    fn into_server(self) -> Server {
+        let mut server = Server {
+            // ARDM shows unnamed connections as `host@port`.
+            name: non_empty(Some(self.name))
+                .unwrap_or_else(|| format!("{}@{}", self.host, self.port)),
+            username: non_empty(Some(self.username)),
+            password: non_empty(Some(self.auth)),
+            tls: self.ssl_options.is_some(),
+            cluster: self.cluster,
+            read_only: self.connection_read_only,
+            ..Server::from_payload(String::new(), self.host, self.port)
+        };
+
+        if let Some(sentinel) = self
+            .sentinel_options
+            .filter(|sentinel| !sentinel.master_name.is_empty())
+        {
+            // The user is sent to the nodes only, ARDM has none for the sentinels.
+            let sentinel_settings = SentinelSettings {
+                username: None,
+                ..sentinel_settings(sentinel.master_name, &server)
+            };
+            server.sentinel = Some(sentinel_settings);
+            server.password = non_empty(Some(sentinel.node_password));
+        }
+
+        if let Some(ssh) = self.ssh_options.filter(|ssh| !ssh.host.is_empty()) {
+            server.ssh_tunnel = Some(SshTunnelSettings {
+                host: ssh.host,
+                port: ssh.port,
+                user: ssh.username,
+                password: non_empty(Some(ssh.password)),
+                private_key_path: non_empty(Some(ssh.privatekey)),
+                passphrase: non_empty(Some(ssh.passphrase)),
+                ..SshTunnelSettings::default()
+            });
+        }
+        server
+    }
+}
+
+/// Deserializes every entry of a JSON array on its own so one malformed entry does not
+/// fail the others.
+fn json_entries<T: DeserializeOwned>(
+    contents: &str,
+) -> Result<Vec<Result<T, ConnectionImportError>>, AppError> {
+    let entries: Vec<JsonValue> = serde_json::from_str(contents).map_err(|e| {
+        log::error!("Invalid connections file: {}", e);
+        AppError::FileFailed
+    })?;
+
+    Ok(entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let name = entry
+                .get("name")
+                .and_then(JsonValue::as_str)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("Entry {}", i + 1));
+            serde_json::from_value(entry).map_err(|e| ConnectionImportError {
+                name,
+                message: e.to_string(),
+            })
+        })
+        .collect())
+}
+
+fn parse_text_line(line: &str) -> Result<Server, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let (host, port, password) = match fields.as_slice() {
+        [host, port] => (host, port, None),
+        [host, port, password] => (host, port, Some(password.to_string())),
+        _ => return Err("Expected `host port [password]`".to_string()),
+    };
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("Invalid port `{}`", port))?;
+
+    Ok(Server {
+        password,
+        ..Server::from_payload(format!("{}:{}", host, port), host.to_string(), port)
+    })
+}
+
+/// Maps the connections of an exported file onto unsaved servers, in file order.
+/// Fails when the file itself cannot be read as the given source.
+pub fn parse_connections(
+    source: ConnectionSource,
+    contents: &str,
+) -> Result<Vec<Result<Server, ConnectionImportError>>, AppError> {
+    let entries = match source {
+        ConnectionSource::RedisInsight => json_entries::<RedisInsightConnection>(contents)?
+            .into_iter()
+            .map(|entry| entry.and_then(RedisInsightConnection::into_server))
+            .collect(),
+        ConnectionSource::Ardm => {
+            // Plain JSON is accepted too, e.g. a file decoded by hand.
+            let contents = match contents.trim_start().starts_with('[') {
+                true => contents.to_string(),
+                false => STANDARD
+                    .decode(contents.trim())
+                    .ok()
+                    .and_then(|decoded| String::from_utf8(decoded).ok())
+                    .ok_or_else(|| {
+                        log::error!("Invalid ARDM connections file: not base64-encoded JSON");
+                        AppError::FileFailed
+                    })?,
+            };
+            json_entries::<ArdmConnection>(&contents)?
+                .into_iter()
+                .map(|entry| entry.map(ArdmConnection::into_server))
+                .collect()
+        }
+        ConnectionSource::TextList => contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.split('#').next().unwrap_or_default().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                parse_text_line(line).map_err(|message| ConnectionImportError {
+                    name: format!("Line {}", i + 1),
+                    message,
+                })
+            })
+            .collect(),
+    };
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_redis_insight_connections() {
+        let contents = r#"[
+            {"id": "1", "host": "10.0.0.1", "port": 6379, "name": "Orders", "db": 2,
+             "username": "app", "password": "secret", "tls": true, "connectionType": "STANDALONE"},
+            {"host": "10.0.0.9", "port": 26379, "name": "", "connectionType": "SENTINEL",
+             "password": "sentinel-pass", "tls": true,
+             "sentinelMaster": {"name": "mymaster", "username": "app", "password": "node"}},
+            {"host": "10.0.0.3", "port": 6379, "name": "Bastion", "ssh": true,
+             "sshOptions": {"host": "bastion", "port": 22, "username": "deploy", "privateKey": "-----BEGIN"}},
+            {"name": "Broken", "port": 6379}
+        ]"#;
+
+        let entries = parse_connections(ConnectionSource::RedisInsight, contents).unwrap();
+        let orders = entries[0].as_ref().unwrap();
+        assert_eq!(orders.name, "Orders");
+        assert_eq!(orders.database, 2);
+        assert_eq!(orders.username.as_deref(), Some("app"));
+        assert!(orders.tls);
+
+        let sentinel = entries[1].as_ref().unwrap();
+        assert_eq!(sentinel.name, "10.0.0.9:26379");
+        assert_eq!(sentinel.username.as_deref(), Some("app"));
+        assert_eq!(sentinel.password.as_deref(), Some("node"));
+        let settings = sentinel.sentinel.as_ref().unwrap();
+        assert_eq!(settings.master_name, "mymaster");
+        assert_eq!(settings.password.as_deref(), Some("sentinel-pass"));
+        assert!(settings.tls);
+
+        assert_eq!(entries[2].as_ref().unwrap_err().name, "Bastion");
+        assert_eq!(entries[3].as_ref().unwrap_err().name, "Broken");
+    }
+
+    #[test]
+    fn test_parse_ardm_connections() {
+        let json = r#"[
+            {"host": "127.0.0.1", "port": 6380, "auth": "pass", "name": "", "cluster": true,
+             "connectionReadOnly": true, "sslOptions": {"key": "", "ca": "", "cert": ""},
+             "sshOptions": {"host": "", "port": 22}},
+            {"host": "10.0.0.5", "port": 6379, "name": "Tunnelled",
+             "sshOptions": {"host": "bastion", "port": 22, "username": "deploy",
+                            "privatekey": "/home/deploy/.ssh/id_ed25519"}},
+            {"host": "10.0.0.9", "port": 26379, "name": "Sentinel", "auth": "sentinel-pass",
+             "username": "app",
+             "sentinelOptions": {"masterName": "mymaster", "nodePassword": "node"}}
+        ]"#;
+
+        let entries = parse_connections(ConnectionSource::Ardm, &STANDARD.encode(json)).unwrap();
+        let local = entries[0].as_ref().unwrap();
+        assert_eq!(local.name, "127.0.0.1@6380");
+        assert_eq!(local.password.as_deref(), Some("pass"));
+        assert!(local.cluster && local.read_only && local.tls);
+        assert_eq!(local.ssh_tunnel, None);
+
+        let tunnelled = entries[1].as_ref().unwrap();
+        let ssh_tunnel = tunnelled.ssh_tunnel.as_ref().unwrap();
+        assert_eq!(
+            ssh_tunnel.private_key_path.as_deref(),
+            Some("/home/deploy/.ssh/id_ed25519")
+        );
+
+        let sentinel = entries[2].as_ref().unwrap();
+        assert_eq!(sentinel.username.as_deref(), Some("app"));
+        assert_eq!(sentinel.password.as_deref(), Some("node"));
+        let settings = sentinel.sentinel.as_ref().unwrap();
+        assert_eq!(settings.master_name, "mymaster");
+        assert_eq!(settings.username, None);
+        assert_eq!(settings.password.as_deref(), Some("sentinel-pass"));
+
+        let entries = parse_connections(ConnectionSource::Ardm, json).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            parse_connections(ConnectionSource::Ardm, "not base64").unwrap_err(),
+            AppError::FileFailed
+        );
+    }
+
+    #[test]
+    fn test_parse_text_list() {
+        let contents = "# staging\n10.0.0.1 6379\n\n10.0.0.2 6380 secret # cache\n10.0.0.3 port\n";
+
+        let entries = parse_connections(ConnectionSource::TextList, contents).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].as_ref().unwrap().name, "10.0.0.1:6379");
+        assert_eq!(
+            entries[1].as_ref().unwrap().password.as_deref(),
+            Some("secret")
+        );
+        assert_eq!(
+            entries[2].as_ref().unwrap_err(),
+            &ConnectionImportError {
+                name: "Line 5".to_string(),
+                message: "Invalid port `port`".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Event emitted after every batch of a memory analysis.
+pub const MEMORY_ANALYSIS_PROGRESS_EVENT: &str = "memory-analysis-progress";
+
+/// Prefixes beyond this many, ranked by memory, are left out of a report.
+const MAX_PREFIXES: usize = 1000;
+
+/// What was read for a single key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KeyStats {
+    pub key: String,
+    pub key_type: String,
+    /// `MEMORY USAGE` in bytes.
+    pub memory: u64,
+    /// Length of a string, number of elements of anything else, `0` for unknown types.
+    pub cardinality: u64,
+    pub encoding: String,
+    /// Seconds to live, `-1` when the key does not expire.
+    pub ttl: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BigKey {
+    pub key: String,
+    pub memory: u64,
+    pub cardinality: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct TypeStats {
+    pub key_type: String,
+    pub keys: u64,
+    pub memory: u64,
+    /// The biggest keys of the type by memory, biggest first.
+    pub biggest: Vec<BigKey>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct PrefixStats {
+    /// Empty for keys without a separator.
+    pub prefix: String,
+    pub keys: u64,
+    pub memory: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TtlCoverage {
+    pub expiring_keys: u64,
+    pub expiring_memory: u64,
+    pub persistent_keys: u64,
+    pub persistent_memory: u64,
+}
+
+/// The aggregated result of a memory analysis.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct MemoryAnalysis {
+    pub key_count: u64,
+    pub total_memory: u64,
+    /// Sorted by memory, biggest first.
+    pub types: Vec<TypeStats>,
+    /// Sorted by memory, biggest first, at most `MAX_PREFIXES`.
+    pub prefixes: Vec<PrefixStats>,
+    pub ttl: TtlCoverage,
+    /// Number of keys per `OBJECT ENCODING`.
+    pub encodings: BTreeMap<String, u64>,
+}
+
+/// Returns the first `depth` segments of a key, without the last segment since
+/// that one names the key itself.
+pub fn key_prefix<'a>(key: &'a str, separator: &str, depth: usize) -> &'a str {
+    if separator.is_empty() || depth == 0 {
+        return "";
+    }
+
+    let mut end = 0;
+    for (segment, (index, _)) in key.match_indices(separator).enumerate() {
+        if segment == depth {
+            break;
+        }
+        end = index;
+    }
+    &key[..end]
+}
+
+/// Aggregates key stats into a `MemoryAnalysis` as they are read.
+#[derive(Debug)]
+pub struct MemoryAnalyzer {
+    top: usize,
+    separator: String,
+    prefix_depth: usize,
+    analysis: MemoryAnalysis,
+    types: HashMap<String, TypeStats>,
+    prefixes: HashMap<String, PrefixStats>,
+}
+
+impl MemoryAnalyzer {
+    pub fn new(top: usize, separator: String, prefix_depth: usize) -> Self {
+        Self {
+            top,
+            separator,
+            prefix_depth,
+            analysis: MemoryAnalysis::default(),
+            types: HashMap::new(),
+            prefixes: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, stats: KeyStats) {
+        self.analysis.key_count += 1;
+        self.analysis.total_memory += stats.memory;
+
+        if stats.ttl >= 0 {
+            self.analysis.ttl.expiring_keys += 1;
+            self.analysis.ttl.expiring_memory += stats.memory;
+        } else {
+            self.analysis.ttl.persistent_keys += 1;
+            self.analysis.ttl.persistent_memory += stats.memory;
+        }
+        *self
+            .analysis
+            .encodings
+            .entry(stats.encoding.clone())
+            .or_default() += 1;
+
+        let prefix = key_prefix(&stats.key, &self.separator, self.prefix_depth);
+        let prefix_stats = self
+            .prefixes
+            .entry(prefix.to_string())
+            .or_insert_with(|| PrefixStats {
+                prefix: prefix.to_string(),
+                ..PrefixStats::default()
+            });
+        prefix_stats.keys += 1;
+        prefix_stats.memory += stats.memory;
+
+        let type_stats = self
+            .types
+            .entry(stats.key_type.clone())
+            .or_insert_with(|| TypeStats {
+                key_type: stats.key_type.clone(),
+                ..TypeStats::default()
+            });
+        type_stats.keys += 1;
+        type_stats.memory += stats.memory;
+
+        let position = type_stats
+            .biggest
+            .partition_point(|key| key.memory >= stats.memory);
+        if position < self.top {
+            type_stats.biggest.insert(
+                position,
+                BigKey {
+                    key: stats.key,
+                    memory: stats.memory,
+                    cardinality: stats.cardinality,
+                },
+            );
+            type_stats.biggest.truncate(self.top);
+        }
+    }
+
+    pub fn finish(self) -> MemoryAnalysis {
+        let mut types: Vec<TypeStats> = self.types.into_values().collect();
+        types.sort_by(|a, b| b.memory.cmp(&a.memory).then(a.key_type.cmp(&b.key_type)));
+
+        let mut prefixes: Vec<PrefixStats> = self.prefixes.into_values().collect();
+        prefixes.sort_by(|a, b| b.memory.cmp(&a.memory).then(a.prefix.cmp(&b.prefix)));
+        prefixes.truncate(MAX_PREFIXES);
+
+        MemoryAnalysis {
+            types,
+            prefixes,
+            ..self.analysis
+        }
+    }
+}
+
+/// How a prefix changed between two reports.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct PrefixDiff {
+    pub prefix: String,
+    pub keys_before: u64,
+    pub keys_after: u64,
+    pub memory_before: u64,
+    pub memory_after: u64,
+}
+
+impl PrefixDiff {
+    pub fn memory_delta(&self) -> i64 {
+        self.memory_after as i64 - self.memory_before as i64
+    }
+}
+
+/// Compares the prefixes of two analyses, biggest memory change first.
+/// Prefixes missing from one side count as empty there.
+pub fn diff_prefixes(before: &MemoryAnalysis, after: &MemoryAnalysis) -> Vec<PrefixDiff> {
+    let mut diffs: BTreeMap<&str, PrefixDiff> = BTreeMap::new();
+    for stats in &before.prefixes {
+        let diff = diffs.entry(&stats.prefix).or_default();
+        diff.keys_before = stats.keys;
+        diff.memory_before = stats.memory;
+    }
+    for stats in &after.prefixes {
+        let diff = diffs.entry(&stats.prefix).or_default();
+        diff.keys_after = stats.keys;
+        diff.memory_after = stats.memory;
+    }
+
+    let mut diffs: Vec<PrefixDiff> = diffs
+        .into_iter()
+        .map(|(prefix, diff)| PrefixDiff {
+            prefix: prefix.to_string(),
+            ..diff
+        })
+        .filter(|diff| diff.keys_before != diff.keys_after || diff.memory_delta() != 0)
+        .collect();
+    diffs.sort_by_key(|diff| std::cmp::Reverse(diff.memory_delta().abs()));
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(key: &str, key_type: &str, memory: u64, ttl: i64) -> KeyStats {
+        KeyStats {
+            key: key.to_string(),
+            key_type: key_type.to_string(),
+            memory,
+            cardinality: 1,
+            encoding: if key_type == "string" {
+                "embstr".to_string()
+            } else {
+                "listpack".to_string()
+            },
+            ttl,
+        }
+    }
+
+    #[test]
+    fn test_key_prefix() {
+        assert_eq!(key_prefix("user:1:profile", ":", 1), "user");
+        assert_eq!(key_prefix("user:1:profile", ":", 2), "user:1");
+        assert_eq!(key_prefix("user:1:profile", ":", 5), "user:1");
+        assert_eq!(key_prefix("counter", ":", 2), "");
+        assert_eq!(key_prefix("a::b", "::", 1), "a");
+        assert_eq!(key_prefix("user:1", ":", 0), "");
+    }
+
+    #[test]
+    fn test_analyzer_aggregates_keys() {
+        let mut analyzer = MemoryAnalyzer::new(2, ":".to_string(), 1);
+        analyzer.add(stats("user:1", "hash", 300, -1));
+        analyzer.add(stats("user:2", "hash", 500, -1));
+        analyzer.add(stats("user:3", "hash", 100, 60));
+        analyzer.add(stats("session:1", "string", 50, 3600));
+        analyzer.add(stats("counter", "string", 20, -1));
+        let analysis = analyzer.finish();
+
+        assert_eq!(analysis.key_count, 5);
+        assert_eq!(analysis.total_memory, 970);
+        assert_eq!(analysis.types[0].key_type, "hash");
+        assert_eq!(analysis.types[0].keys, 3);
+        let biggest: Vec<&str> = analysis.types[0]
+            .biggest
+            .iter()
+            .map(|key| key.key.as_str())
+            .collect();
+        assert_eq!(biggest, vec!["user:2", "user:1"]);
+
+        assert_eq!(
+            analysis.prefixes[0],
+            PrefixStats {
+                prefix: "user".to_string(),
+                keys: 3,
+                memory: 900
+            }
+        );
+        assert_eq!(analysis.prefixes[2].prefix, "");
+        assert_eq!(
+            analysis.ttl,
+            TtlCoverage {
+                expiring_keys: 2,
+                expiring_memory: 150,
+                persistent_keys: 3,
+                persistent_memory: 820,
+            }
+        );
+        assert_eq!(analysis.encodings["listpack"], 3);
+    }
+
+    #[test]
+    fn test_diff_prefixes() {
+        let analysis = |prefixes: &[(&str, u64, u64)]| MemoryAnalysis {
+            prefixes: prefixes
+                .iter()
+                .map(|(prefix, keys, memory)| PrefixStats {
+                    prefix: prefix.to_string(),
+                    keys: *keys,
+                    memory: *memory,
+                })
+                .collect(),
+            ..MemoryAnalysis::default()
+        };
+        let before = analysis(&[("user", 10, 1000), ("cache", 5, 100), ("same", 1, 1)]);
+        let after = analysis(&[("user", 12, 1500), ("queue", 1, 2000), ("same", 1, 1)]);
+
+        let diffs = diff_prefixes(&before, &after);
+        let prefixes: Vec<(&str, i64)> = diffs
+            .iter()
+            .map(|diff| (diff.prefix.as_str(), diff.memory_delta()))
+            .collect();
+        assert_eq!(
+            prefixes,
+            vec![("queue", 2000), ("user", 500), ("cache", -100)]
+        );
+    }
+}
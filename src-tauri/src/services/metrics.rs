@@ -0,0 +1,317 @@
+use super::TaskConnection;
+use crate::{
+    models::{MetricSample, MetricsSettings},
+    utils::{parse_server_info, InfoSection, InfoValue, ServerInfo},
+};
+use redis::{aio::MultiplexedConnection, Client};
+use std::{
+    collections::VecDeque,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+use time::OffsetDateTime;
+use tokio::{
+    sync::Mutex,
+    task::JoinHandle,
+    time::{interval, Instant, MissedTickBehavior},
+};
+use uuid::Uuid;
+
+/// Event emitted with every sample taken for the opened server.
+pub const METRICS_SAMPLE_EVENT: &str = "metrics-sample";
+
+/// Counters and gauges read from a single `INFO` poll.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InfoCounters {
+    commands_processed: i64,
+    net_input_bytes: i64,
+    net_output_bytes: i64,
+    keyspace_hits: i64,
+    keyspace_misses: i64,
+    used_memory: i64,
+    connected_clients: i64,
+}
+
+fn integer(section: &InfoSection, key: &str) -> i64 {
+    match section.get(key) {
+        Some(InfoValue::Integer(value)) => *value,
+        _ => 0,
+    }
+}
+
+impl InfoCounters {
+    pub fn from_info(info: &ServerInfo) -> Self {
+        Self {
+            commands_processed: integer(&info.stats, "total_commands_processed"),
+            net_input_bytes: integer(&info.stats, "total_net_input_bytes"),
+            net_output_bytes: integer(&info.stats, "total_net_output_bytes"),
+            keyspace_hits: integer(&info.stats, "keyspace_hits"),
+            keyspace_misses: integer(&info.stats, "keyspace_misses"),
+            used_memory: integer(&info.memory, "used_memory"),
+            connected_clients: integer(&info.clients, "connected_clients"),
+        }
+    }
+}
+
+/// Computes the rates between two polls taken `elapsed` apart.
+/// Returns `None` when a counter went backwards, e.g. after a restart or `CONFIG RESETSTAT`.
+pub fn compute_sample(
+    previous: &InfoCounters,
+    current: &InfoCounters,
+    elapsed: Duration,
+) -> Option<MetricSample> {
+    let delta = |previous: i64, current: i64| current.checked_sub(previous).filter(|d| *d >= 0);
+    let commands = delta(previous.commands_processed, current.commands_processed)?;
+    let input = delta(previous.net_input_bytes, current.net_input_bytes)?;
+    let output = delta(previous.net_output_bytes, current.net_output_bytes)?;
+    let hits = delta(previous.keyspace_hits, current.keyspace_hits)?;
+    let misses = delta(previous.keyspace_misses, current.keyspace_misses)?;
+
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return None;
+    }
+
+    Some(MetricSample {
+        ops_per_sec: commands as f64 / seconds,
+        used_memory: current.used_memory,
+        connected_clients: current.connected_clients as u32,
+        hit_ratio: match hits + misses {
+            0 => None,
+            lookups => Some(hits as f64 / lookups as f64),
+        },
+        input_bytes_per_sec: input as f64 / seconds,
+        output_bytes_per_sec: output as f64 / seconds,
+        ..MetricSample::default()
+    })
+}
+
+/// Keeps the most recent samples, dropping the oldest once full.
+#[derive(Debug)]
+pub struct MetricsBuffer {
+    samples: VecDeque<MetricSample>,
+    capacity: usize,
+}
+
+impl MetricsBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, sample: MetricSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Returns the samples taken at or after `since`, oldest first.
+    pub fn since(&self, since: OffsetDateTime) -> Vec<MetricSample> {
+        self.samples
+            .iter()
+            .filter(|sample| sample.sampled_at >= since)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Polls `INFO` of a server in a background task until dropped.
+#[derive(Debug)]
+pub struct MetricsSampler {
+    buffer: Arc<Mutex<MetricsBuffer>>,
+    connection: TaskConnection,
+    handle: JoinHandle<()>,
+}
+
+impl MetricsSampler {
+    /// Starts polling right away, the first sample is emitted after the second poll
+    /// since rates need two readings. `no_evict` is the app's `CLIENT NO-EVICT` flag.
+    pub fn start<F>(
+        client: Client,
+        server_id: Uuid,
+        settings: &MetricsSettings,
+        no_evict: Arc<AtomicBool>,
+        on_sample: F,
+    ) -> Self
+    where
+        F: Fn(MetricSample) + Send + 'static,
+    {
+        let buffer = Arc::new(Mutex::new(MetricsBuffer::new(settings.capacity as usize)));
+        let period = Duration::from_secs(settings.interval_secs.max(1) as u64);
+
+        let connection = TaskConnection::new(no_evict);
+        let task_buffer = buffer.clone();
+        let task_connection = connection.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut previous: Option<(InfoCounters, Instant)> = None;
+
+            loop {
+                ticker.tick().await;
+                let counters = match poll_counters(&client, &task_connection).await {
+                    Some(counters) => counters,
+                    None => {
+                        previous = None;
+                        continue;
+                    }
+                };
+
+                let now = Instant::now();
+                let Some((last, last_at)) = previous.replace((counters, now)) else {
+                    continue;
+                };
+                let Some(sample) = compute_sample(&last, &counters, now - last_at) else {
+                    log::debug!(
+                        "Counters of server {} were reset, skipping sample",
+                        server_id
+                    );
+                    continue;
+                };
+
+                let sample = MetricSample {
+                    id: Uuid::now_v7(),
+                    server_id,
+                    ..sample
+                };
+                task_buffer.lock().await.push(sample.clone());
+                on_sample(sample);
+            }
+        });
+
+        Self {
+            buffer,
+            connection,
+            handle,
+        }
+    }
+
+    /// The connection the sampler polls on, `None` while it reconnects.
+    pub fn connection(&self) -> Option<MultiplexedConnection> {
+        self.connection.current()
+    }
+
+    /// Returns the buffered samples taken at or after `since`, oldest first.
+    pub async fn samples_since(&self, since: OffsetDateTime) -> Vec<MetricSample> {
+        self.buffer.lock().await.since(since)
+    }
+}
+
+impl Drop for MetricsSampler {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Reads the counters, reconnecting on the next poll when the connection failed.
+async fn poll_counters(client: &Client, connection: &TaskConnection) -> Option<InfoCounters> {
+    let result: redis::RedisResult<String> = redis::cmd("INFO")
+        .query_async(&mut connection.get(client).await?)
+        .await;
+    match result {
+        Ok(info) => Some(InfoCounters::from_info(&parse_server_info(&info))),
+        Err(e) => {
+            log::warn!("Failed to poll server metrics: {}", e);
+            connection.reset();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(commands: i64, hits: i64, misses: i64) -> InfoCounters {
+        InfoCounters {
+            commands_processed: commands,
+            net_input_bytes: commands * 10,
+            net_output_bytes: commands * 20,
+            keyspace_hits: hits,
+            keyspace_misses: misses,
+            used_memory: 1024,
+            connected_clients: 3,
+        }
+    }
+
+    #[test]
+    fn test_counters_from_info() {
+        let info = parse_server_info(
+            "# Clients\r\nconnected_clients:5\r\n# Memory\r\nused_memory:2048\r\n\
+            # Stats\r\ntotal_commands_processed:100\r\nkeyspace_hits:7\r\nkeyspace_misses:3\r\n",
+        );
+        let counters = InfoCounters::from_info(&info);
+
+        assert_eq!(counters.connected_clients, 5);
+        assert_eq!(counters.used_memory, 2048);
+        assert_eq!(counters.commands_processed, 100);
+        assert_eq!(counters.keyspace_hits, 7);
+        assert_eq!(counters.net_input_bytes, 0);
+    }
+
+    #[test]
+    fn test_compute_sample_rates() {
+        let sample = compute_sample(
+            &counters(100, 10, 10),
+            &counters(300, 40, 20),
+            Duration::from_secs(2),
+        )
+        .unwrap();
+
+        assert_eq!(sample.ops_per_sec, 100.0);
+        assert_eq!(sample.input_bytes_per_sec, 1000.0);
+        assert_eq!(sample.output_bytes_per_sec, 2000.0);
+        assert_eq!(sample.hit_ratio, Some(0.75));
+        assert_eq!(sample.used_memory, 1024);
+        assert_eq!(sample.connected_clients, 3);
+    }
+
+    #[test]
+    fn test_compute_sample_without_lookups_or_after_reset() {
+        let sample = compute_sample(
+            &counters(100, 5, 5),
+            &counters(100, 5, 5),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(sample.ops_per_sec, 0.0);
+        assert_eq!(sample.hit_ratio, None);
+
+        assert!(compute_sample(
+            &counters(100, 5, 5),
+            &counters(20, 0, 0),
+            Duration::from_secs(1)
+        )
+        .is_none());
+        assert!(compute_sample(&counters(1, 0, 0), &counters(2, 0, 0), Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_buffer_drops_oldest_samples() {
+        let now = OffsetDateTime::now_utc();
+        let mut buffer = MetricsBuffer::new(3);
+        for seconds in (0..5).rev() {
+            buffer.push(MetricSample {
+                used_memory: seconds,
+                sampled_at: now - time::Duration::seconds(seconds),
+                ..MetricSample::default()
+            });
+        }
+
+        let memory = |samples: Vec<MetricSample>| -> Vec<i64> {
+            samples.iter().map(|sample| sample.used_memory).collect()
+        };
+        assert_eq!(
+            memory(buffer.since(now - time::Duration::hours(1))),
+            vec![2, 1, 0]
+        );
+        assert_eq!(
+            memory(buffer.since(now - time::Duration::seconds(1))),
+            vec![1, 0]
+        );
+    }
+}
@@ -1,3 +1,29 @@
+pub mod connection_import;
+pub mod memory_analysis;
+pub mod metrics;
 pub mod redis;
+pub mod ssh_tunnel;
+pub mod ttl_analysis;
 
-pub use redis::test_connection;
+pub use connection_import::{parse_connections, ConnectionImportError, ConnectionSource};
+pub use memory_analysis::{
+    diff_prefixes, KeyStats, MemoryAnalysis, MemoryAnalyzer, PrefixDiff,
+    MEMORY_ANALYSIS_PROGRESS_EVENT,
+};
+pub use metrics::{MetricsSampler, METRICS_SAMPLE_EVENT};
+pub use redis::{
+    cluster_topology, complete_notifications, fetch_command_docs, get_connection,
+    get_console_connection, is_write_command, keyspace_clients, parse_acl_list, parse_acl_log,
+    parse_acl_user, parse_client_list, parse_config, parse_function_list, parse_numsub,
+    parse_slowlog, parse_slowlog_config, replication_overview, resp3_client, scan_options,
+    sentinel_overview, server_from_url, server_major_version, server_version_key, set_no_evict,
+    test_connection, AclListEntry, AclLogEntry, AclSetUserResult, AclUser, ClientInfo,
+    ClusterTopology, CommandArgument, CommandSpec, ConfigParameter, ConfigSetResult,
+    FunctionLibrary, KeyspaceEvent, KeyspaceWatcher, MonitorEvent, MonitorFilter, MonitorSession,
+    PubSubChannel, PubSubMessage, PubSubSession, RedisCluster, ReplicationOverview,
+    SentinelOverview, SentinelWatcher, ServerConnection, SlowlogCollector, SlowlogConfig,
+    SlowlogEntry, SubscriptionKind, TaskConnection, CLIENT_NAME, KEYSPACE_EVENT,
+    PUBSUB_MESSAGE_EVENT, SENTINEL_FAILOVER_EVENT,
+};
+pub use ssh_tunnel::SshTunnel;
+pub use ttl_analysis::{TtlAnalysis, TtlAnalyzer, TTL_ANALYSIS_PROGRESS_EVENT};
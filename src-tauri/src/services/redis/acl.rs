@@ -0,0 +1,288 @@
+use super::reply::{value_items, value_pairs, value_strings, value_to_string};
+use redis::Value as RedisValue;
+use serde::{Deserialize, Serialize};
+
+/// Extra command, key and channel permissions a user is granted on top of its root ones.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct AclSelector {
+    /// Command rules in order, e.g. `+@read` or `-keys`.
+    pub commands: Vec<String>,
+    /// Key patterns with their `~`, `%R~` or `%W~` prefix, e.g. `~user:*`.
+    pub keys: Vec<String>,
+    /// Channel patterns with their `&` prefix.
+    pub channels: Vec<String>,
+}
+
+/// A user as reported by `ACL GETUSER`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct AclUser {
+    pub username: String,
+    pub enabled: bool,
+    pub nopass: bool,
+    /// Every flag as sent, e.g. `on`, `nopass`, `sanitize-payload`.
+    pub flags: Vec<String>,
+    /// SHA-256 hashes in hex, the server never returns the passwords themselves.
+    pub password_hashes: Vec<String>,
+    /// The `+@category` and `-@category` rules out of the command rules.
+    pub categories: Vec<String>,
+    /// The root permissions, selectors add to them since Redis 7.
+    pub permissions: AclSelector,
+    pub selectors: Vec<AclSelector>,
+}
+
+/// The outcome of `ACL SETUSER`, either the user as stored or the server's rejection.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AclSetUserResult {
+    pub user: Option<AclUser>,
+    /// The error exactly as the server sent it, e.g. `ERR Error in ACL SETUSER modifier ...`.
+    pub error: Option<String>,
+}
+
+/// A line of `ACL LIST`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct AclListEntry {
+    pub username: String,
+    /// The rules recreating the user with `ACL SETUSER`.
+    pub rules: Vec<String>,
+}
+
+/// An entry of `ACL LOG`, a command or authentication the server denied.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AclLogEntry {
+    /// Number of similar denials grouped in the entry.
+    pub count: u64,
+    /// `command`, `key`, `channel` or `auth`.
+    pub reason: String,
+    /// `toplevel`, `multi`, `lua` or `module`.
+    pub context: String,
+    /// The denied command, key or channel.
+    pub object: String,
+    pub username: String,
+    pub age_seconds: f64,
+    pub client_info: String,
+    /// Missing before Redis 7.2.
+    pub entry_id: Option<i64>,
+}
+
+/// Reads rules sent as a single string on Redis 7 or as a list on Redis 6.
+fn value_rules(value: &RedisValue) -> Vec<String> {
+    match value {
+        RedisValue::Array(_) | RedisValue::Set(_) => value_strings(value),
+        other => value_to_string(other)
+            .map(|rules| rules.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_selector(value: &RedisValue) -> AclSelector {
+    let mut selector = AclSelector::default();
+    for (name, value) in value_pairs(value) {
+        match name.as_str() {
+            "commands" => selector.commands = value_rules(value),
+            "keys" => selector.keys = value_rules(value),
+            "channels" => selector.channels = value_rules(value),
+            _ => {}
+        }
+    }
+    selector
+}
+
+/// Parses the `ACL GETUSER` reply, `None` when the user does not exist.
+pub fn parse_acl_user(username: &str, value: &RedisValue) -> Option<AclUser> {
+    if matches!(value, RedisValue::Nil) {
+        return None;
+    }
+
+    let mut user = AclUser {
+        username: username.to_string(),
+        permissions: parse_selector(value),
+        ..AclUser::default()
+    };
+    for (name, value) in value_pairs(value) {
+        match name.as_str() {
+            "flags" => user.flags = value_strings(value),
+            "passwords" => user.password_hashes = value_strings(value),
+            "selectors" => user.selectors = value_items(value).iter().map(parse_selector).collect(),
+            _ => {}
+        }
+    }
+    user.enabled = user.flags.iter().any(|flag| flag == "on");
+    user.nopass = user.flags.iter().any(|flag| flag == "nopass");
+    user.categories = user
+        .permissions
+        .commands
+        .iter()
+        .filter(|rule| rule.starts_with("+@") || rule.starts_with("-@"))
+        .cloned()
+        .collect();
+    Some(user)
+}
+
+/// Parses the `ACL LIST` reply, one `user <name> <rules>` line per user.
+pub fn parse_acl_list(lines: &[String]) -> Vec<AclListEntry> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            if words.next() != Some("user") {
+                return None;
+            }
+            Some(AclListEntry {
+                username: words.next()?.to_string(),
+                rules: words.map(str::to_string).collect(),
+            })
+        })
+        .collect()
+}
+
+fn value_to_number(value: &RedisValue) -> Option<f64> {
+    match value {
+        RedisValue::Int(i) => Some(*i as f64),
+        RedisValue::Double(f) => Some(*f),
+        other => value_to_string(other).and_then(|s| s.parse().ok()),
+    }
+}
+
+/// Parses the `ACL LOG` reply, newest entry first as sent by the server.
+pub fn parse_acl_log(value: &RedisValue) -> Vec<AclLogEntry> {
+    value_items(value)
+        .iter()
+        .map(|entry| {
+            let mut log_entry = AclLogEntry::default();
+            for (name, value) in value_pairs(entry) {
+                let text = || value_to_string(value).unwrap_or_default();
+                match name.as_str() {
+                    "count" => log_entry.count = value_to_number(value).unwrap_or_default() as u64,
+                    "reason" => log_entry.reason = text(),
+                    "context" => log_entry.context = text(),
+                    "object" => log_entry.object = text(),
+                    "username" => log_entry.username = text(),
+                    "age-seconds" => {
+                        log_entry.age_seconds = value_to_number(value).unwrap_or_default()
+                    }
+                    "client-info" => log_entry.client_info = text(),
+                    "entry-id" => log_entry.entry_id = value_to_number(value).map(|id| id as i64),
+                    _ => {}
+                }
+            }
+            log_entry
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    fn bulks(items: &[&str]) -> RedisValue {
+        RedisValue::Array(items.iter().map(|s| bulk(s)).collect())
+    }
+
+    #[test]
+    fn test_parse_acl_user_with_selectors() {
+        let reply = RedisValue::Array(vec![
+            bulk("flags"),
+            bulks(&["on", "sanitize-payload"]),
+            bulk("passwords"),
+            bulks(&["5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8"]),
+            bulk("commands"),
+            bulk("+@read +@hash -keys"),
+            bulk("keys"),
+            bulk("~user:* %W~cache:*"),
+            bulk("channels"),
+            bulk(""),
+            bulk("selectors"),
+            RedisValue::Array(vec![RedisValue::Array(vec![
+                bulk("commands"),
+                bulk("+set"),
+                bulk("keys"),
+                bulk("~session:*"),
+                bulk("channels"),
+                bulk("&events"),
+            ])]),
+        ]);
+
+        let user = parse_acl_user("reader", &reply).unwrap();
+        assert!(user.enabled);
+        assert!(!user.nopass);
+        assert_eq!(user.password_hashes.len(), 1);
+        assert_eq!(user.categories, vec!["+@read", "+@hash"]);
+        assert_eq!(user.permissions.keys, vec!["~user:*", "%W~cache:*"]);
+        assert!(user.permissions.channels.is_empty());
+        assert_eq!(
+            user.selectors,
+            vec![AclSelector {
+                commands: vec!["+set".to_string()],
+                keys: vec!["~session:*".to_string()],
+                channels: vec!["&events".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_acl_user_from_redis_6() {
+        let reply = RedisValue::Array(vec![
+            bulk("flags"),
+            bulks(&["off", "nopass"]),
+            bulk("passwords"),
+            bulks(&[]),
+            bulk("commands"),
+            bulk("+@all"),
+            bulk("keys"),
+            bulks(&["*"]),
+            bulk("channels"),
+            bulks(&["*"]),
+        ]);
+
+        let user = parse_acl_user("legacy", &reply).unwrap();
+        assert!(!user.enabled);
+        assert!(user.nopass);
+        assert_eq!(user.permissions.keys, vec!["*"]);
+        assert!(parse_acl_user("missing", &RedisValue::Nil).is_none());
+    }
+
+    #[test]
+    fn test_parse_acl_list() {
+        let lines = vec![
+            "user default on nopass sanitize-payload ~* &* +@all".to_string(),
+            "user reader off #5e88 ~user:* resetchannels -@all +@read".to_string(),
+        ];
+
+        let entries = parse_acl_list(&lines);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].username, "reader");
+        assert_eq!(entries[1].rules[0], "off");
+    }
+
+    #[test]
+    fn test_parse_acl_log() {
+        let reply = RedisValue::Array(vec![RedisValue::Array(vec![
+            bulk("count"),
+            RedisValue::Int(3),
+            bulk("reason"),
+            bulk("command"),
+            bulk("context"),
+            bulk("toplevel"),
+            bulk("object"),
+            bulk("flushall"),
+            bulk("username"),
+            bulk("reader"),
+            bulk("age-seconds"),
+            bulk("4.096"),
+            bulk("client-info"),
+            bulk("id=3 addr=127.0.0.1:40212"),
+            bulk("entry-id"),
+            RedisValue::Int(0),
+        ])]);
+
+        let entries = parse_acl_log(&reply);
+        assert_eq!(entries[0].count, 3);
+        assert_eq!(entries[0].object, "flushall");
+        assert_eq!(entries[0].age_seconds, 4.096);
+        assert_eq!(entries[0].entry_id, Some(0));
+    }
+}
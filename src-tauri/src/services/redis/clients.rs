@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// A connection as reported by `CLIENT LIST`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ClientInfo {
+    pub id: u64,
+    pub addr: String,
+    pub name: String,
+    /// Seconds since the connection was opened.
+    pub age: u64,
+    /// Seconds since the last command.
+    pub idle: u64,
+    pub flags: String,
+    pub db: u32,
+    /// Subscribed channels.
+    pub sub: u32,
+    /// Subscribed patterns.
+    pub psub: u32,
+    /// Commands queued in a transaction, -1 outside of `MULTI`.
+    pub multi: i64,
+    /// Query buffer length in bytes.
+    pub qbuf: u64,
+    /// Output buffer memory in bytes.
+    pub omem: u64,
+    /// Last command run, e.g. `client|list`.
+    pub cmd: String,
+    /// ACL user, empty before Redis 6.
+    pub user: String,
+}
+
+/// Parses the `CLIENT LIST` reply, one `key=value` list per line.
+/// Fields added by newer servers are ignored and missing ones keep their default.
+pub fn parse_client_list(reply: &str) -> Vec<ClientInfo> {
+    reply
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut client = ClientInfo {
+                multi: -1,
+                ..ClientInfo::default()
+            };
+            for (key, value) in line.split_whitespace().filter_map(|f| f.split_once('=')) {
+                match key {
+                    "id" => client.id = value.parse().unwrap_or_default(),
+                    "addr" => client.addr = value.to_string(),
+                    "name" => client.name = value.to_string(),
+                    "age" => client.age = value.parse().unwrap_or_default(),
+                    "idle" => client.idle = value.parse().unwrap_or_default(),
+                    "flags" => client.flags = value.to_string(),
+                    "db" => client.db = value.parse().unwrap_or_default(),
+                    "sub" => client.sub = value.parse().unwrap_or_default(),
+                    "psub" => client.psub = value.parse().unwrap_or_default(),
+                    "multi" => client.multi = value.parse().unwrap_or(-1),
+                    "qbuf" => client.qbuf = value.parse().unwrap_or_default(),
+                    "omem" => client.omem = value.parse().unwrap_or_default(),
+                    "cmd" => client.cmd = value.to_string(),
+                    "user" => client.user = value.to_string(),
+                    _ => {}
+                }
+            }
+            client
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_client_list() {
+        let reply = "id=3 addr=172.17.0.1:40212 laddr=172.17.0.2:6379 fd=8 name=worker age=120 \
+            idle=2 flags=N db=1 sub=0 psub=2 ssub=0 multi=-1 watch=0 qbuf=26 qbuf-free=20448 \
+            argv-mem=10 multi-mem=0 rbs=1024 rbp=0 obl=0 oll=0 omem=0 tot-mem=22426 events=r \
+            cmd=client|list user=default redir=-1 resp=2 lib-name= lib-ver=\r\n\
+            id=5 addr=10.0.0.4:51000 fd=9 name= age=4 idle=4 flags=x db=0 sub=0 psub=0 multi=3 \
+            qbuf=0 qbuf-free=0 obl=0 oll=0 omem=1024 events=r cmd=set\n";
+
+        let clients = parse_client_list(reply);
+        assert_eq!(clients.len(), 2);
+        assert_eq!(
+            clients[0],
+            ClientInfo {
+                id: 3,
+                addr: "172.17.0.1:40212".to_string(),
+                name: "worker".to_string(),
+                age: 120,
+                idle: 2,
+                flags: "N".to_string(),
+                db: 1,
+                sub: 0,
+                psub: 2,
+                multi: -1,
+                qbuf: 26,
+                omem: 0,
+                cmd: "client|list".to_string(),
+                user: "default".to_string(),
+            }
+        );
+        // Redis 5 has no `user` field.
+        assert_eq!(clients[1].name, "");
+        assert_eq!(clients[1].multi, 3);
+        assert_eq!(clients[1].omem, 1024);
+        assert_eq!(clients[1].user, "");
+    }
+}
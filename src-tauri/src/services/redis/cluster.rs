@@ -0,0 +1,501 @@
+use super::{
+    connection_info, get_connection, node_client,
+    reply::{value_items, value_pairs, value_to_string},
+};
+use crate::{core::AppError, models::Server};
+use redis::{
+    aio::{ConnectionLike, MultiplexedConnection},
+    cluster::ClusterClient,
+    cluster_async::ClusterConnection,
+    Client, Cmd, Pipeline, RedisFuture, Value as RedisValue,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterRole {
+    Primary,
+    Replica,
+}
+
+/// An inclusive range of hash slots.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SlotRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl SlotRange {
+    pub fn count(&self) -> u32 {
+        (self.end - self.start) as u32 + 1
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ClusterNode {
+    pub id: String,
+    pub address: String,
+    pub port: u16,
+    pub role: ClusterRole,
+    /// The node a replica follows.
+    pub primary_id: Option<String>,
+    /// Served slots, empty for replicas.
+    pub slots: Vec<SlotRange>,
+    /// `online`, `failed` or `loading` as `CLUSTER SHARDS` reports it. Older servers get
+    /// `online`, `unreachable` or `failed` out of the flags and link of `CLUSTER NODES`.
+    pub health: String,
+    /// Missing from `CLUSTER NODES`.
+    pub replication_offset: Option<i64>,
+}
+
+impl ClusterNode {
+    fn is_online_primary(&self) -> bool {
+        self.role == ClusterRole::Primary && self.health == "online"
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ClusterTopology {
+    /// Primaries first, each followed by its replicas.
+    pub nodes: Vec<ClusterNode>,
+    /// Slots served by an online primary, all 16384 on a healthy cluster.
+    pub covered_slots: u32,
+}
+
+impl ClusterTopology {
+    fn new(mut nodes: Vec<ClusterNode>) -> Self {
+        let primary_of = |node: &ClusterNode| match node.role {
+            ClusterRole::Primary => node.id.clone(),
+            ClusterRole::Replica => node.primary_id.clone().unwrap_or_default(),
+        };
+        nodes.sort_by(|a, b| {
+            primary_of(a)
+                .cmp(&primary_of(b))
+                .then((a.role == ClusterRole::Replica).cmp(&(b.role == ClusterRole::Replica)))
+                .then(a.id.cmp(&b.id))
+        });
+        let covered_slots = nodes
+            .iter()
+            .filter(|node| node.is_online_primary())
+            .flat_map(|node| &node.slots)
+            .map(SlotRange::count)
+            .sum();
+
+        Self {
+            nodes,
+            covered_slots,
+        }
+    }
+
+    /// Returns a client for every online primary, the nodes a keyspace walk must visit.
+    /// They connect with the settings of `seed`, e.g. its password and TLS.
+    pub fn primary_clients(&self, seed: &Client) -> Result<Vec<Client>, AppError> {
+        self.nodes
+            .iter()
+            .filter(|node| node.is_online_primary())
+            .map(|node| node_client(seed, &node.address, node.port))
+            .collect()
+    }
+}
+
+/// Parses the `CLUSTER NODES` reply, one
+/// `<id> <ip:port@cport[,hostname]> <flags> <primary> <ping> <pong> <epoch> <link> <slots>...`
+/// line per node.
+pub fn parse_cluster_nodes(reply: &str) -> Vec<ClusterNode> {
+    reply
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 {
+                return None;
+            }
+            let endpoint = fields[1].split(['@', ',']).next().unwrap_or_default();
+            let (address, port) = endpoint.rsplit_once(':')?;
+            let flags: Vec<&str> = fields[2].split(',').collect();
+            let health = if flags.contains(&"fail") {
+                "failed"
+            } else if flags.contains(&"fail?") || fields[7] != "connected" {
+                "unreachable"
+            } else {
+                "online"
+            };
+
+            Some(ClusterNode {
+                id: fields[0].to_string(),
+                address: address.to_string(),
+                port: port.parse().unwrap_or_default(),
+                role: match flags.contains(&"slave") {
+                    true => ClusterRole::Replica,
+                    false => ClusterRole::Primary,
+                },
+                primary_id: Some(fields[3]).filter(|id| *id != "-").map(str::to_string),
+                // Slots being migrated are listed in brackets and still belong to this node.
+                slots: fields[8..]
+                    .iter()
+                    .filter(|slot| !slot.starts_with('['))
+                    .filter_map(|slot| {
+                        let (start, end) = slot.split_once('-').unwrap_or((slot, slot));
+                        Some(SlotRange {
+                            start: start.parse().ok()?,
+                            end: end.parse().ok()?,
+                        })
+                    })
+                    .collect(),
+                health: health.to_string(),
+                replication_offset: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses the `CLUSTER SHARDS` reply, available since Redis 7.
+pub fn parse_cluster_shards(value: &RedisValue) -> Vec<ClusterNode> {
+    let int = |value: &RedisValue| match value {
+        RedisValue::Int(i) => Some(*i),
+        other => value_to_string(other).and_then(|s| s.parse().ok()),
+    };
+
+    let mut nodes = vec![];
+    for shard in value_items(value) {
+        let shard = value_pairs(shard);
+        let field = |name: &str| shard.iter().find(|(k, _)| k == name).map(|(_, v)| *v);
+
+        let bounds: Vec<i64> = field("slots")
+            .map(|slots| value_items(slots).iter().filter_map(int).collect())
+            .unwrap_or_default();
+        let slots: Vec<SlotRange> = bounds
+            .chunks_exact(2)
+            .map(|range| SlotRange {
+                start: range[0] as u16,
+                end: range[1] as u16,
+            })
+            .collect();
+
+        let mut shard_nodes: Vec<ClusterNode> = field("nodes")
+            .map(value_items)
+            .unwrap_or_default()
+            .iter()
+            .map(|node| {
+                let node = value_pairs(node);
+                let text = |name: &str| {
+                    node.iter()
+                        .find(|(k, _)| k == name)
+                        .and_then(|(_, v)| value_to_string(v))
+                        .unwrap_or_default()
+                };
+                let number = |name: &str| {
+                    node.iter()
+                        .find(|(k, _)| k == name)
+                        .and_then(|(_, v)| int(v))
+                };
+                let role = match text("role").as_str() {
+                    "master" => ClusterRole::Primary,
+                    _ => ClusterRole::Replica,
+                };
+
+                ClusterNode {
+                    id: text("id"),
+                    address: text("ip"),
+                    port: number("port")
+                        .or_else(|| number("tls-port"))
+                        .unwrap_or_default() as u16,
+                    role,
+                    primary_id: None,
+                    slots: match role {
+                        ClusterRole::Primary => slots.clone(),
+                        ClusterRole::Replica => vec![],
+                    },
+                    health: text("health"),
+                    replication_offset: number("replication-offset"),
+                }
+            })
+            .collect();
+
+        let primary_id = shard_nodes
+            .iter()
+            .find(|node| node.role == ClusterRole::Primary)
+            .map(|node| node.id.clone());
+        for node in shard_nodes
+            .iter_mut()
+            .filter(|node| node.role == ClusterRole::Replica)
+        {
+            node.primary_id = primary_id.clone();
+        }
+        nodes.extend(shard_nodes);
+    }
+    nodes
+}
+
+/// Reads the cluster's nodes through any of them, with `CLUSTER SHARDS` when the server
+/// supports it and `CLUSTER NODES` otherwise.
+pub async fn cluster_topology(
+    connection: &mut MultiplexedConnection,
+) -> Result<ClusterTopology, AppError> {
+    let shards: Result<RedisValue, _> = redis::cmd("CLUSTER")
+        .arg("SHARDS")
+        .query_async(connection)
+        .await;
+    if let Ok(shards) = shards {
+        return Ok(ClusterTopology::new(parse_cluster_shards(&shards)));
+    }
+
+    let nodes: String = redis::cmd("CLUSTER")
+        .arg("NODES")
+        .query_async(connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to read the cluster nodes: {}", e);
+            AppError::RedisFailed
+        })?;
+    Ok(ClusterTopology::new(parse_cluster_nodes(&nodes)))
+}
+
+/// Returns the clients a keyspace walk visits, `client` itself or every online primary when
+/// it is the seed of a cluster.
+pub async fn keyspace_clients(client: &Client, cluster: bool) -> Result<Vec<Client>, AppError> {
+    if !cluster {
+        return Ok(vec![client.clone()]);
+    }
+    let mut connection = get_connection(client).await?;
+    cluster_topology(&mut connection)
+        .await?
+        .primary_clients(client)
+}
+
+/// A connection to a Redis Cluster seeded with the stored server, it routes every command
+/// to the node serving the key's slot and follows `MOVED` redirections. The connection is
+/// opened once and shared by its clones.
+#[derive(Clone)]
+pub struct RedisCluster {
+    connection: ClusterConnection,
+}
+
+impl fmt::Debug for RedisCluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisCluster").finish_non_exhaustive()
+    }
+}
+
+impl RedisCluster {
+    /// Discovers the cluster from the server and checks it answers `PING`.
+    pub async fn connect(server: &Server) -> Result<Self, AppError> {
+        let client = ClusterClient::new(vec![connection_info(server).await?]).map_err(|e| {
+            log::error!("Failed to create cluster client for {} - {}", server, e);
+            AppError::RedisFailed
+        })?;
+        let mut connection = client.get_async_connection().await.map_err(|e| {
+            log::error!("Failed to connect to cluster of {} - {}", server, e);
+            AppError::RedisFailed
+        })?;
+
+        redis::cmd("PING")
+            .query_async::<String>(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to ping cluster of {} - {}", server, e);
+                AppError::RedisFailed
+            })?;
+        Ok(Self { connection })
+    }
+
+    /// Returns a handle on the shared connection.
+    pub fn connection(&self) -> ClusterConnection {
+        self.connection.clone()
+    }
+}
+
+/// The connection commands of the opened server go through: the cluster connection in
+/// cluster mode, so keyed commands reach the node serving their slot and script cache
+/// commands reach every primary, or a connection to the server otherwise.
+pub enum ServerConnection {
+    Node(MultiplexedConnection),
+    Cluster(ClusterConnection),
+}
+
+impl ServerConnection {
+    pub async fn open(client: &Client, cluster: Option<&RedisCluster>) -> Result<Self, AppError> {
+        match cluster {
+            Some(cluster) => Ok(Self::Cluster(cluster.connection())),
+            None => Ok(Self::Node(get_connection(client).await?)),
+        }
+    }
+}
+
+impl ConnectionLike for ServerConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, RedisValue> {
+        match self {
+            Self::Node(connection) => connection.req_packed_command(cmd),
+            Self::Cluster(connection) => connection.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<RedisValue>> {
+        match self {
+            Self::Node(connection) => connection.req_packed_commands(cmd, offset, count),
+            Self::Cluster(connection) => connection.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            Self::Node(connection) => connection.get_db(),
+            Self::Cluster(connection) => connection.get_db(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::run_redis_cluster_container;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_cluster_nodes() {
+        let reply = "\
+07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,host-4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected
+67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1 127.0.0.1:30002@31002 master - 0 1426238316232 2 connected 5461-10922
+e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460 [5461->-67ed2db8d677e59ec4a4cefb06858cf2a1a89fa1]
+292f8b365bb7edb5e285caf0b7e6ddc7265d2f4f 127.0.0.1:30003@31003 master,fail - 1426238316232 1426238315000 3 disconnected 10923-16383
+";
+        let nodes = parse_cluster_nodes(reply);
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(nodes[0].role, ClusterRole::Replica);
+        assert_eq!(nodes[0].port, 30004);
+        assert_eq!(
+            nodes[0].primary_id.as_deref(),
+            Some("e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca")
+        );
+        assert_eq!(
+            nodes[2].slots,
+            vec![SlotRange {
+                start: 0,
+                end: 5460
+            }]
+        );
+        assert_eq!(nodes[3].health, "failed");
+
+        let topology = ClusterTopology::new(nodes);
+        assert_eq!(topology.covered_slots, 10923);
+        assert_eq!(
+            topology
+                .primary_clients(&Client::open("redis://127.0.0.1:30001").unwrap())
+                .unwrap()
+                .len(),
+            2
+        );
+        // The replica is listed right after its primary.
+        assert_eq!(
+            topology.nodes[2].id,
+            "e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca"
+        );
+        assert_eq!(topology.nodes[3].role, ClusterRole::Replica);
+    }
+
+    #[test]
+    fn test_parse_cluster_shards() {
+        let node = |id: &str, port: i64, role: &str| {
+            RedisValue::Array(vec![
+                bulk("id"),
+                bulk(id),
+                bulk("port"),
+                RedisValue::Int(port),
+                bulk("ip"),
+                bulk("10.0.0.1"),
+                bulk("role"),
+                bulk(role),
+                bulk("replication-offset"),
+                RedisValue::Int(72156),
+                bulk("health"),
+                bulk("online"),
+            ])
+        };
+        let reply = RedisValue::Array(vec![RedisValue::Array(vec![
+            bulk("slots"),
+            RedisValue::Array(vec![
+                RedisValue::Int(0),
+                RedisValue::Int(5460),
+                RedisValue::Int(10923),
+                RedisValue::Int(10923),
+            ]),
+            bulk("nodes"),
+            RedisValue::Array(vec![node("b", 6380, "replica"), node("a", 6379, "master")]),
+        ])]);
+
+        let nodes = parse_cluster_shards(&reply);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].primary_id.as_deref(), Some("a"));
+        assert!(nodes[0].slots.is_empty());
+        assert_eq!(nodes[1].slots.len(), 2);
+        assert_eq!(nodes[1].replication_offset, Some(72156));
+        assert_eq!(ClusterTopology::new(nodes).covered_slots, 5462);
+    }
+
+    #[tokio::test]
+    async fn test_keyspace_clients_outside_cluster() {
+        // Nothing is read from the server outside of cluster mode.
+        let client = Client::open("redis://127.0.0.1:1").unwrap();
+        let clients = keyspace_clients(&client, false).await.unwrap();
+
+        assert_eq!(clients.len(), 1);
+        assert_eq!(
+            clients[0].get_connection_info().addr(),
+            client.get_connection_info().addr()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cluster_routes_by_slot() {
+        let (host, port, container) = run_redis_cluster_container().await;
+        let mut server = Server::from_payload("Local Cluster".to_string(), host, port);
+        server.cluster = true;
+        let cluster = RedisCluster::connect(&server).await.unwrap();
+
+        let mut connection = ServerConnection::open(
+            &Client::open("redis://127.0.0.1:1").unwrap(),
+            Some(&cluster),
+        )
+        .await
+        .unwrap();
+        let _: () = redis::cmd("SET")
+            .arg("k")
+            .arg("v")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        let sha: String = redis::cmd("SCRIPT")
+            .arg("LOAD")
+            .arg("return 1")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        let exists: Vec<bool> = redis::cmd("SCRIPT")
+            .arg("EXISTS")
+            .arg(&sha)
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(exists, vec![true]);
+
+        let client = Client::open(format!("redis://127.0.0.1:{}", port)).unwrap();
+        let clients = keyspace_clients(&client, true).await.unwrap();
+        assert_eq!(clients.len(), 1);
+        let value: String = redis::cmd("GET")
+            .arg("k")
+            .query_async(&mut get_connection(&clients[0]).await.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(value, "v");
+
+        container.rm().await.unwrap();
+    }
+}
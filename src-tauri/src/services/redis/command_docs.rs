@@ -0,0 +1,533 @@
+use super::reply::{value_items, value_pairs, value_strings, value_to_string};
+use crate::core::AppError;
+use redis::{aio::MultiplexedConnection, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+
+/// Administrative commands that change the server state without carrying the `write` flag.
+/// Listed by name as well because servers before Redis 7 do not report subcommands.
+const STATE_CHANGING_COMMANDS: &[&str] = &[
+    "acl|deluser",
+    "acl|load",
+    "acl|save",
+    "acl|setuser",
+    "bgrewriteaof",
+    "bgsave",
+    "client|kill",
+    "cluster|addslots",
+    "cluster|delslots",
+    "cluster|failover",
+    "cluster|forget",
+    "cluster|meet",
+    "cluster|replicate",
+    "cluster|reset",
+    "cluster|setslot",
+    "config|resetstat",
+    "config|rewrite",
+    "config|set",
+    "debug",
+    "failover",
+    "function|kill",
+    "latency|reset",
+    "memory|purge",
+    "module|load",
+    "module|loadex",
+    "module|unload",
+    "replicaof",
+    "save",
+    "script|flush",
+    "script|kill",
+    "shutdown",
+    "slaveof",
+    "slowlog|reset",
+];
+
+/// A command as reported by the connected server through `COMMAND` and `COMMAND DOCS`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct CommandSpec {
+    /// Lowercase name, subcommands are reported as `container|subcommand`.
+    pub name: String,
+    /// Number of arguments including the command name, negative means "at least".
+    pub arity: i64,
+    pub flags: Vec<String>,
+    pub first_key: i64,
+    pub last_key: i64,
+    pub step: i64,
+    pub acl_categories: Vec<String>,
+    pub summary: Option<String>,
+    pub since: Option<String>,
+    pub group: Option<String>,
+    pub complexity: Option<String>,
+    pub arguments: Vec<CommandArgument>,
+    pub subcommands: Vec<CommandSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct CommandArgument {
+    pub name: String,
+    /// Argument type, e.g. `key`, `string`, `integer`, `pure-token`, `oneof` or `block`.
+    pub kind: String,
+    pub token: Option<String>,
+    pub optional: bool,
+    pub multiple: bool,
+    pub multiple_token: bool,
+    pub arguments: Vec<CommandArgument>,
+}
+
+impl CommandSpec {
+    /// Returns the name the way it is typed, e.g. `CONFIG GET` for `config|get`.
+    pub fn display_name(&self) -> String {
+        self.name.replace('|', " ").to_uppercase()
+    }
+
+    /// Returns the usage line, e.g. `GETEX key [EX seconds | PX milliseconds | PERSIST]`.
+    pub fn syntax(&self) -> String {
+        std::iter::once(self.display_name())
+            .chain(self.arguments.iter().map(CommandArgument::syntax))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Whether the argument at `position` (the command name being 0) is a key,
+    /// based on the legacy first key / last key / step triplet.
+    pub fn is_key_position(&self, position: i64, total: i64) -> bool {
+        if self.first_key <= 0 || position < self.first_key {
+            return false;
+        }
+
+        let last_key = match self.last_key {
+            last if last < 0 => total + last,
+            last => last,
+        };
+        let step = self.step.max(1);
+
+        (last_key < 0 || position <= last_key.max(self.first_key))
+            && (position - self.first_key) % step == 0
+    }
+}
+
+/// Resolves the command of an argument list, descending into the subcommand of containers
+/// such as `CONFIG GET`.
+fn find_command_spec<'a>(commands: &'a [CommandSpec], args: &[Vec<u8>]) -> Option<&'a CommandSpec> {
+    let name = String::from_utf8_lossy(args.first()?).to_lowercase();
+    let command = commands.iter().find(|command| command.name == name)?;
+
+    let subcommand = args.get(1).and_then(|sub| {
+        let full_name = format!("{}|{}", name, String::from_utf8_lossy(sub).to_lowercase());
+        command.subcommands.iter().find(|s| s.name == full_name)
+    });
+    Some(subcommand.unwrap_or(command))
+}
+
+/// Whether an argument list modifies data or the server state according to the flags the
+/// server reports. Commands the server does not know are considered writes.
+pub fn is_write_command(commands: &[CommandSpec], args: &[Vec<u8>]) -> bool {
+    let Some(name) = args.first() else {
+        return false;
+    };
+    let name = String::from_utf8_lossy(name).to_lowercase();
+    let subcommand = args
+        .get(1)
+        .map(|sub| format!("{}|{}", name, String::from_utf8_lossy(sub).to_lowercase()));
+
+    if STATE_CHANGING_COMMANDS.contains(&name.as_str())
+        || subcommand.is_some_and(|sub| STATE_CHANGING_COMMANDS.contains(&sub.as_str()))
+    {
+        return true;
+    }
+
+    // Redis 6 spells the flag `may-replicate`, later versions `may_replicate`.
+    find_command_spec(commands, args).is_none_or(|command| {
+        command
+            .flags
+            .iter()
+            .any(|flag| matches!(flag.replace('-', "_").as_str(), "write" | "may_replicate"))
+    })
+}
+
+impl CommandArgument {
+    pub fn syntax(&self) -> String {
+        let mut syntax = match self.kind.as_str() {
+            "pure-token" => self.token.clone().unwrap_or_else(|| self.name.clone()),
+            "oneof" => self
+                .arguments
+                .iter()
+                .map(CommandArgument::syntax)
+                .collect::<Vec<String>>()
+                .join(" | "),
+            "block" => self
+                .arguments
+                .iter()
+                .map(CommandArgument::syntax)
+                .collect::<Vec<String>>()
+                .join(" "),
+            _ => self.name.clone(),
+        };
+
+        if self.kind != "pure-token" {
+            if let Some(token) = &self.token {
+                syntax = format!("{} {}", token, syntax);
+            }
+        }
+
+        if self.multiple {
+            syntax = format!("{} [{} ...]", syntax, syntax);
+        }
+
+        if self.optional {
+            format!("[{}]", syntax)
+        } else if self.kind == "oneof" {
+            format!("<{}>", syntax)
+        } else {
+            syntax
+        }
+    }
+}
+
+/// Parses one entry of the `COMMAND` reply. Redis 6 replies with 7 fields, Redis 7+ with 10.
+pub fn parse_command_info(value: &RedisValue) -> Option<CommandSpec> {
+    let fields = value_items(value);
+    let int = |index: usize| match fields.get(index) {
+        Some(RedisValue::Int(i)) => *i,
+        _ => 0,
+    };
+
+    Some(CommandSpec {
+        name: value_to_string(fields.first()?)?.to_lowercase(),
+        arity: int(1),
+        flags: fields.get(2).map(value_strings).unwrap_or_default(),
+        first_key: int(3),
+        last_key: int(4),
+        step: int(5),
+        acl_categories: fields.get(6).map(value_strings).unwrap_or_default(),
+        subcommands: fields
+            .get(9)
+            .map(|subcommands| {
+                value_items(subcommands)
+                    .iter()
+                    .filter_map(parse_command_info)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..CommandSpec::default()
+    })
+}
+
+fn parse_argument(value: &RedisValue) -> CommandArgument {
+    let mut argument = CommandArgument::default();
+    for (field, value) in value_pairs(value) {
+        match field.as_str() {
+            "name" => argument.name = value_to_string(value).unwrap_or_default(),
+            "type" => argument.kind = value_to_string(value).unwrap_or_default(),
+            "token" => argument.token = value_to_string(value),
+            "flags" => {
+                let flags = value_strings(value);
+                argument.optional = flags.iter().any(|f| f == "optional");
+                argument.multiple = flags.iter().any(|f| f == "multiple");
+                argument.multiple_token = flags.iter().any(|f| f == "multiple_token");
+            }
+            "arguments" => {
+                argument.arguments = value_items(value).iter().map(parse_argument).collect()
+            }
+            _ => {}
+        }
+    }
+    argument
+}
+
+/// Merges a `COMMAND DOCS` entry into a command parsed from `COMMAND`.
+pub fn apply_command_docs(spec: &mut CommandSpec, docs: &RedisValue) {
+    for (field, value) in value_pairs(docs) {
+        match field.as_str() {
+            "summary" => spec.summary = value_to_string(value),
+            "since" => spec.since = value_to_string(value),
+            "group" => spec.group = value_to_string(value),
+            "complexity" => spec.complexity = value_to_string(value),
+            "arguments" => spec.arguments = value_items(value).iter().map(parse_argument).collect(),
+            "subcommands" => {
+                for (name, sub_docs) in value_pairs(value) {
+                    let name = name.to_lowercase();
+                    if let Some(sub) = spec.subcommands.iter_mut().find(|s| s.name == name) {
+                        apply_command_docs(sub, sub_docs);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Fetches the commands the connected server supports, including module commands.
+/// Servers without `COMMAND DOCS` (before Redis 7) are reported without docs.
+pub async fn fetch_command_docs(
+    connection: &mut MultiplexedConnection,
+) -> Result<Vec<CommandSpec>, AppError> {
+    let info: RedisValue = redis::cmd("COMMAND")
+        .query_async(connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to retrieve COMMAND: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    let mut specs: Vec<CommandSpec> = value_items(&info)
+        .iter()
+        .filter_map(parse_command_info)
+        .collect();
+
+    match redis::cmd("COMMAND")
+        .arg("DOCS")
+        .query_async::<RedisValue>(connection)
+        .await
+    {
+        Ok(docs) => {
+            for (name, command_docs) in value_pairs(&docs) {
+                let name = name.to_lowercase();
+                if let Some(spec) = specs.iter_mut().find(|s| s.name == name) {
+                    apply_command_docs(spec, command_docs);
+                }
+            }
+        }
+        Err(e) => log::warn!("COMMAND DOCS is not available, docs are skipped: {}", e),
+    }
+
+    specs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(specs)
+}
+
+/// Identifies the server flavour, version and loaded modules, e.g. `valkey-8.0.1+search@20612`,
+/// so docs are cached once per distinct command set.
+pub async fn server_version_key(
+    connection: &mut MultiplexedConnection,
+) -> Result<String, AppError> {
+    // Redis before 7 only accepts a single section per INFO.
+    let (server, modules): (String, String) = redis::pipe()
+        .cmd("INFO")
+        .arg("server")
+        .cmd("INFO")
+        .arg("modules")
+        .query_async(connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to retrieve INFO server: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(version_key_from_info(&format!("{}{}", server, modules)))
+}
+
+fn version_key_from_info(info: &str) -> String {
+    let field = |name: &str| {
+        info.lines()
+            .find_map(|line| line.trim().strip_prefix(&format!("{}:", name)))
+            .map(str::to_string)
+    };
+
+    let server_name = field("server_name").unwrap_or_else(|| "redis".to_string());
+    let version = field(&format!("{}_version", server_name))
+        .or_else(|| field("redis_version"))
+        .unwrap_or_default();
+
+    let mut modules: Vec<String> = info
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("module:"))
+        .map(|module| {
+            let attribute = |key: &str| {
+                module
+                    .split(',')
+                    .find_map(|pair| pair.strip_prefix(&format!("{}=", key)))
+                    .unwrap_or_default()
+            };
+            format!("+{}@{}", attribute("name"), attribute("ver"))
+        })
+        .collect();
+    modules.sort();
+
+    format!("{}-{}{}", server_name, version, modules.concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::run_redis_version_container;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    fn getex_info() -> RedisValue {
+        RedisValue::Array(vec![
+            bulk("getex"),
+            RedisValue::Int(-2),
+            RedisValue::Array(vec![RedisValue::SimpleString("write".to_string())]),
+            RedisValue::Int(1),
+            RedisValue::Int(1),
+            RedisValue::Int(1),
+            RedisValue::Array(vec![bulk("@write"), bulk("@string")]),
+        ])
+    }
+
+    fn getex_docs() -> RedisValue {
+        let pure_token = |name: &str, token: &str| {
+            RedisValue::Array(vec![
+                bulk("name"),
+                bulk(name),
+                bulk("type"),
+                bulk("pure-token"),
+                bulk("token"),
+                bulk(token),
+            ])
+        };
+
+        RedisValue::Array(vec![
+            bulk("summary"),
+            bulk("Returns the string value of a key after setting its expiration time."),
+            bulk("since"),
+            bulk("6.2.0"),
+            bulk("group"),
+            bulk("string"),
+            bulk("arguments"),
+            RedisValue::Array(vec![
+                RedisValue::Array(vec![bulk("name"), bulk("key"), bulk("type"), bulk("key")]),
+                RedisValue::Array(vec![
+                    bulk("name"),
+                    bulk("expiration"),
+                    bulk("type"),
+                    bulk("oneof"),
+                    bulk("flags"),
+                    RedisValue::Array(vec![RedisValue::SimpleString("optional".to_string())]),
+                    bulk("arguments"),
+                    RedisValue::Array(vec![
+                        RedisValue::Array(vec![
+                            bulk("name"),
+                            bulk("seconds"),
+                            bulk("type"),
+                            bulk("integer"),
+                            bulk("token"),
+                            bulk("EX"),
+                        ]),
+                        pure_token("persist", "PERSIST"),
+                    ]),
+                ]),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_parse_command_info_and_docs() {
+        let mut spec = parse_command_info(&getex_info()).unwrap();
+        assert_eq!(spec.name, "getex");
+        assert_eq!(spec.arity, -2);
+        assert_eq!(spec.flags, vec!["write".to_string()]);
+        assert_eq!((spec.first_key, spec.last_key, spec.step), (1, 1, 1));
+        assert!(spec.subcommands.is_empty());
+
+        apply_command_docs(&mut spec, &getex_docs());
+        assert_eq!(spec.since.as_deref(), Some("6.2.0"));
+        assert_eq!(spec.arguments.len(), 2);
+        assert!(spec.arguments[1].optional);
+        assert_eq!(spec.syntax(), "GETEX key [EX seconds | PERSIST]");
+    }
+
+    #[test]
+    fn test_argument_syntax() {
+        let key = CommandArgument {
+            name: "key".to_string(),
+            kind: "key".to_string(),
+            multiple: true,
+            ..CommandArgument::default()
+        };
+        assert_eq!(key.syntax(), "key [key ...]");
+
+        let condition = CommandArgument {
+            name: "condition".to_string(),
+            kind: "oneof".to_string(),
+            arguments: vec![
+                CommandArgument {
+                    name: "nx".to_string(),
+                    kind: "pure-token".to_string(),
+                    token: Some("NX".to_string()),
+                    ..CommandArgument::default()
+                },
+                CommandArgument {
+                    name: "xx".to_string(),
+                    kind: "pure-token".to_string(),
+                    token: Some("XX".to_string()),
+                    ..CommandArgument::default()
+                },
+            ],
+            ..CommandArgument::default()
+        };
+        assert_eq!(condition.syntax(), "<NX | XX>");
+    }
+
+    #[test]
+    fn test_is_key_position() {
+        let mset = CommandSpec {
+            name: "mset".to_string(),
+            first_key: 1,
+            last_key: -1,
+            step: 2,
+            ..CommandSpec::default()
+        };
+        assert!(mset.is_key_position(1, 5));
+        assert!(!mset.is_key_position(2, 5));
+        assert!(mset.is_key_position(3, 5));
+
+        let ping = CommandSpec::default();
+        assert!(!ping.is_key_position(1, 2));
+    }
+
+    #[test]
+    fn test_is_write_command() {
+        let spec = |name: &str, flags: &[&str], subcommands: Vec<CommandSpec>| CommandSpec {
+            name: name.to_string(),
+            flags: flags.iter().map(|f| f.to_string()).collect(),
+            subcommands,
+            ..CommandSpec::default()
+        };
+        let commands = vec![
+            spec("get", &["readonly", "fast"], vec![]),
+            spec("set", &["write", "denyoom"], vec![]),
+            spec("eval", &["noscript", "may-replicate"], vec![]),
+            spec(
+                "object",
+                &[],
+                vec![spec("object|encoding", &["readonly"], vec![])],
+            ),
+            spec("config", &["admin"], vec![]),
+        ];
+        let args = |line: &str| -> Vec<Vec<u8>> {
+            line.split(' ').map(|arg| arg.as_bytes().to_vec()).collect()
+        };
+
+        assert!(!is_write_command(&commands, &args("GET k")));
+        assert!(!is_write_command(&commands, &args("object ENCODING k")));
+        assert!(is_write_command(&commands, &args("SET k v")));
+        assert!(is_write_command(&commands, &args("EVAL return 0")));
+        assert!(is_write_command(&commands, &args("CONFIG SET maxmemory 1")));
+        assert!(!is_write_command(&commands, &args("CONFIG GET maxmemory")));
+        assert!(is_write_command(&commands, &args("UNKNOWN k")));
+    }
+
+    #[test]
+    fn test_version_key_from_info() {
+        let redis = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n# Modules\r\n";
+        assert_eq!(version_key_from_info(redis), "redis-7.2.4");
+
+        let valkey = "# Server\r\nredis_version:7.2.4\r\nserver_name:valkey\r\nvalkey_version:8.0.1\r\n\
+            # Modules\r\nmodule:name=search,ver=20612,api=1,filters=0,usedby=[],using=[],options=[]\r\n";
+        assert_eq!(version_key_from_info(valkey), "valkey-8.0.1+search@20612");
+    }
+
+    #[tokio::test]
+    async fn test_server_version_key_on_redis_6() {
+        let (host, port, container) = run_redis_version_container("6.2-alpine", 6379).await;
+        let client = redis::Client::open(format!("redis://{}:{}", host, port)).unwrap();
+        let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+
+        let key = server_version_key(&mut connection).await.unwrap();
+        assert!(key.starts_with("redis-6.2."));
+
+        container.rm().await.unwrap();
+    }
+}
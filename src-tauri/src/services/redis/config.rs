@@ -0,0 +1,189 @@
+use super::reply::{value_pairs, value_to_string};
+use crate::core::AppError;
+use redis::{aio::MultiplexedConnection, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Defaults shared by every supported major version, as `CONFIG GET` reports them
+/// on a server started without a config file.
+const COMMON_DEFAULTS: &[(&str, &str)] = &[
+    ("activedefrag", "no"),
+    ("active-expire-effort", "1"),
+    ("appendfsync", "everysec"),
+    ("appendonly", "no"),
+    ("client-query-buffer-limit", "1073741824"),
+    ("databases", "16"),
+    ("dynamic-hz", "yes"),
+    ("hz", "10"),
+    ("io-threads", "1"),
+    ("latency-monitor-threshold", "0"),
+    ("lazyfree-lazy-eviction", "no"),
+    ("lazyfree-lazy-expire", "no"),
+    ("lazyfree-lazy-server-del", "no"),
+    ("lazyfree-lazy-user-del", "no"),
+    ("list-compress-depth", "0"),
+    ("maxclients", "10000"),
+    ("maxmemory", "0"),
+    ("maxmemory-policy", "noeviction"),
+    ("maxmemory-samples", "5"),
+    ("notify-keyspace-events", ""),
+    ("proto-max-bulk-len", "536870912"),
+    ("protected-mode", "yes"),
+    ("rdbcompression", "yes"),
+    ("repl-backlog-size", "1048576"),
+    ("set-max-intset-entries", "512"),
+    ("slowlog-log-slower-than", "10000"),
+    ("slowlog-max-len", "128"),
+    ("stream-node-max-bytes", "4096"),
+    ("stream-node-max-entries", "100"),
+    ("tcp-backlog", "511"),
+    ("tcp-keepalive", "300"),
+    ("timeout", "0"),
+];
+
+const REDIS_6_DEFAULTS: &[(&str, &str)] = &[
+    ("hash-max-ziplist-entries", "512"),
+    ("hash-max-ziplist-value", "64"),
+    ("list-max-ziplist-size", "-2"),
+    ("lua-time-limit", "5000"),
+    ("save", "900 1 300 10 60 10000"),
+    ("zset-max-ziplist-entries", "128"),
+    ("zset-max-ziplist-value", "64"),
+];
+
+/// Redis 7 renamed the ziplist settings to listpack and changed the save points.
+const REDIS_7_DEFAULTS: &[(&str, &str)] = &[
+    ("appenddirname", "appendonlydir"),
+    ("busy-reply-threshold", "5000"),
+    ("enable-debug-command", "no"),
+    ("hash-max-listpack-entries", "512"),
+    ("hash-max-listpack-value", "64"),
+    ("latency-tracking", "yes"),
+    ("lazyfree-lazy-user-flush", "no"),
+    ("list-max-listpack-size", "-2"),
+    ("save", "3600 1 300 100 60 10000"),
+    ("zset-max-listpack-entries", "128"),
+    ("zset-max-listpack-value", "64"),
+];
+
+/// A configuration parameter compared with the documented default of the server's version.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ConfigParameter {
+    pub value: String,
+    /// `None` when the default is not in the table, e.g. paths or module settings.
+    pub default: Option<String>,
+    pub modified: bool,
+}
+
+/// The outcome of setting a single parameter.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ConfigSetResult {
+    pub name: String,
+    pub value: String,
+    /// The error exactly as the server sent it, e.g. `ERR CONFIG SET failed ...`.
+    pub error: Option<String>,
+}
+
+/// Returns the documented defaults of a Redis major version, versions older than 6
+/// have none and newer ones use the latest table.
+pub fn documented_defaults(major_version: u32) -> HashMap<&'static str, &'static str> {
+    let specific = match major_version {
+        0..=5 => return HashMap::new(),
+        6 => REDIS_6_DEFAULTS,
+        _ => REDIS_7_DEFAULTS,
+    };
+    COMMON_DEFAULTS.iter().chain(specific).copied().collect()
+}
+
+/// Parses the `CONFIG GET` reply and flags the parameters that differ from their default.
+pub fn parse_config(value: &RedisValue, major_version: u32) -> BTreeMap<String, ConfigParameter> {
+    let defaults = documented_defaults(major_version);
+    value_pairs(value)
+        .into_iter()
+        .map(|(name, value)| {
+            let value = value_to_string(value).unwrap_or_default();
+            let default = defaults.get(name.as_str()).map(|d| d.to_string());
+            let modified = default.as_ref().is_some_and(|d| *d != value);
+            (
+                name,
+                ConfigParameter {
+                    value,
+                    default,
+                    modified,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Reads the major version from `INFO server`, compatible forks report the Redis
+/// version they follow in `redis_version`.
+pub async fn server_major_version(connection: &mut MultiplexedConnection) -> Result<u32, AppError> {
+    let info: String = redis::cmd("INFO")
+        .arg("server")
+        .query_async(connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to retrieve INFO server: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(major_version_from_info(&info))
+}
+
+fn major_version_from_info(info: &str) -> u32 {
+    info.lines()
+        .find_map(|line| line.trim().strip_prefix("redis_version:"))
+        .and_then(|version| version.split('.').next())
+        .and_then(|major| major.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_major_version_from_info() {
+        let info = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n";
+        assert_eq!(major_version_from_info(info), 7);
+        assert_eq!(major_version_from_info("# Server\r\n"), 0);
+    }
+
+    #[test]
+    fn test_documented_defaults_per_version() {
+        assert_eq!(documented_defaults(6)["save"], "900 1 300 10 60 10000");
+        assert_eq!(documented_defaults(8)["save"], "3600 1 300 100 60 10000");
+        assert!(!documented_defaults(6).contains_key("hash-max-listpack-entries"));
+        assert!(documented_defaults(5).is_empty());
+    }
+
+    #[test]
+    fn test_parse_config_flags_modified_parameters() {
+        let reply = RedisValue::Array(vec![
+            bulk("hz"),
+            bulk("10"),
+            bulk("maxmemory-policy"),
+            bulk("allkeys-lru"),
+            bulk("dir"),
+            bulk("/data"),
+        ]);
+        let config = parse_config(&reply, 7);
+
+        assert!(!config["hz"].modified);
+        assert_eq!(
+            config["maxmemory-policy"],
+            ConfigParameter {
+                value: "allkeys-lru".to_string(),
+                default: Some("noeviction".to_string()),
+                modified: true,
+            }
+        );
+        assert_eq!(config["dir"].default, None);
+        assert!(!config["dir"].modified);
+    }
+}
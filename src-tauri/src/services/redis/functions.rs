@@ -0,0 +1,127 @@
+use super::reply::{value_items, value_pairs, value_strings, value_to_string};
+use redis::Value as RedisValue;
+use serde::{Deserialize, Serialize};
+
+/// A function library as reported by `FUNCTION LIST`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct FunctionLibrary {
+    pub library_name: String,
+    pub engine: String,
+    pub functions: Vec<FunctionInfo>,
+    /// Source of the library, only present when listed `WITHCODE`.
+    pub library_code: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub description: Option<String>,
+    /// Flags declared on registration, e.g. `no-writes` or `allow-oom`.
+    pub flags: Vec<String>,
+}
+
+fn parse_function(value: &RedisValue) -> FunctionInfo {
+    let mut function = FunctionInfo::default();
+    for (field, value) in value_pairs(value) {
+        match field.as_str() {
+            "name" => function.name = value_to_string(value).unwrap_or_default(),
+            "description" => function.description = value_to_string(value),
+            "flags" => function.flags = value_strings(value),
+            _ => {}
+        }
+    }
+    function
+}
+
+/// Parses the `FUNCTION LIST` reply.
+pub fn parse_function_list(value: &RedisValue) -> Vec<FunctionLibrary> {
+    value_items(value)
+        .iter()
+        .map(|library_value| {
+            let mut library = FunctionLibrary::default();
+            for (field, value) in value_pairs(library_value) {
+                match field.as_str() {
+                    "library_name" => {
+                        library.library_name = value_to_string(value).unwrap_or_default()
+                    }
+                    "engine" => library.engine = value_to_string(value).unwrap_or_default(),
+                    "functions" => {
+                        library.functions = value_items(value).iter().map(parse_function).collect()
+                    }
+                    "library_code" => library.library_code = value_to_string(value),
+                    _ => {}
+                }
+            }
+            library
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_resp2_function_list() {
+        let reply = RedisValue::Array(vec![RedisValue::Array(vec![
+            bulk("library_name"),
+            bulk("mylib"),
+            bulk("engine"),
+            bulk("LUA"),
+            bulk("functions"),
+            RedisValue::Array(vec![RedisValue::Array(vec![
+                bulk("name"),
+                bulk("myfunc"),
+                bulk("description"),
+                RedisValue::Nil,
+                bulk("flags"),
+                RedisValue::Array(vec![bulk("no-writes")]),
+            ])]),
+        ])]);
+
+        assert_eq!(
+            parse_function_list(&reply),
+            vec![FunctionLibrary {
+                library_name: "mylib".to_string(),
+                engine: "LUA".to_string(),
+                functions: vec![FunctionInfo {
+                    name: "myfunc".to_string(),
+                    description: None,
+                    flags: vec!["no-writes".to_string()],
+                }],
+                library_code: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_resp3_function_list() {
+        let reply = RedisValue::Array(vec![RedisValue::Map(vec![
+            (bulk("library_name"), bulk("mylib")),
+            (bulk("engine"), bulk("LUA")),
+            (
+                bulk("functions"),
+                RedisValue::Array(vec![RedisValue::Map(vec![
+                    (bulk("name"), bulk("myfunc")),
+                    (bulk("description"), bulk("Says hi")),
+                    (bulk("flags"), RedisValue::Set(vec![])),
+                ])]),
+            ),
+            (bulk("library_code"), bulk("#!lua name=mylib")),
+        ])]);
+
+        let libraries = parse_function_list(&reply);
+        assert_eq!(
+            libraries[0].functions[0].description.as_deref(),
+            Some("Says hi")
+        );
+        assert_eq!(
+            libraries[0].library_code.as_deref(),
+            Some("#!lua name=mylib")
+        );
+    }
+}
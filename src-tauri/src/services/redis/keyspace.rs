@@ -0,0 +1,186 @@
+use super::pubsub::{PubSubMessage, PubSubSession, SubscriptionKind};
+use crate::core::AppError;
+use redis::{aio::MultiplexedConnection, Client};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Event emitted for every change notified on the watched database.
+pub const KEYSPACE_EVENT: &str = "keyspace-event";
+
+/// Value `notify-keyspace-events` is completed to when notifications are off: both channel
+/// classes for every event type.
+const KEYSPACE_NOTIFICATIONS: &str = "KEA";
+
+/// Event classes the watcher needs: generic, string, list, set, hash, sorted set and
+/// expired events. `A` stands for all of them.
+const REQUIRED_EVENT_CLASSES: &str = "g$lshzxe";
+
+/// Returns `notify-keyspace-events` completed with what the watcher needs, or `None` when
+/// nothing is missing. Flags already set are kept, missing event classes are appended and
+/// `K` is added when no channel class is on, e.g. `Ex` becomes `Exg$lshze`.
+pub fn complete_notifications(notifications: &str) -> Option<String> {
+    if notifications.is_empty() {
+        return Some(KEYSPACE_NOTIFICATIONS.to_string());
+    }
+    let mut completed = notifications.to_string();
+    if !notifications.contains('K') && !notifications.contains('E') {
+        completed.push('K');
+    }
+    if !notifications.contains('A') {
+        completed.extend(
+            REQUIRED_EVENT_CLASSES
+                .chars()
+                .filter(|class| !notifications.contains(*class)),
+        );
+    }
+    (completed != notifications).then_some(completed)
+}
+
+/// A change of a key reported by a keyspace notification.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct KeyspaceEvent {
+    pub db: i64,
+    pub key: String,
+    /// Notification name such as `set`, `del`, `expired` or `hset`.
+    pub event: String,
+    /// Whether the key is the one last opened with `retrieve_key`.
+    pub current: bool,
+}
+
+/// Reads the key and event out of a `__keyspace@<db>__:<key>` or `__keyevent@<db>__:<event>`
+/// message.
+pub fn parse_keyspace_message(message: &PubSubMessage) -> Option<KeyspaceEvent> {
+    let (class, rest) = message.channel.strip_prefix("__")?.split_once('@')?;
+    let (db, name) = rest.split_once("__:")?;
+    let (key, event) = match class {
+        "keyspace" => (name.to_string(), message.payload.clone()),
+        "keyevent" => (message.payload.clone(), name.to_string()),
+        _ => return None,
+    };
+
+    Some(KeyspaceEvent {
+        db: db.parse().ok()?,
+        key,
+        event,
+        current: false,
+    })
+}
+
+/// Subscribes to the keyspace notifications of one database on a dedicated connection
+/// until dropped.
+#[derive(Debug)]
+pub struct KeyspaceWatcher {
+    current_key: Arc<Mutex<Option<String>>>,
+    session: PubSubSession,
+}
+
+impl KeyspaceWatcher {
+    /// `notifications` is the server's `notify-keyspace-events`. Every change is sent on
+    /// both channels when both classes are enabled, so only the keyspace one is used then.
+    pub async fn start<F>(
+        client: &Client,
+        notifications: &str,
+        no_evict: bool,
+        on_event: F,
+    ) -> Result<Self, AppError>
+    where
+        F: Fn(KeyspaceEvent) + Send + Sync + 'static,
+    {
+        let db = client.get_connection_info().redis_settings().db();
+        let keyspace_class = if notifications.contains('K') {
+            "keyspace"
+        } else {
+            "keyevent"
+        };
+
+        let current_key: Arc<Mutex<Option<String>>> = Arc::default();
+        let task_current_key = current_key.clone();
+        let session = PubSubSession::start(client, no_evict, move |message| {
+            if !message
+                .channel
+                .starts_with(&format!("__{}@", keyspace_class))
+            {
+                return;
+            }
+            let Some(event) = parse_keyspace_message(&message) else {
+                return;
+            };
+            let current = task_current_key
+                .lock()
+                .is_ok_and(|key| key.as_deref() == Some(event.key.as_str()));
+            on_event(KeyspaceEvent { current, ..event });
+        })
+        .await?;
+
+        session
+            .subscribe(
+                SubscriptionKind::Pattern,
+                &[
+                    format!("__keyspace@{}__:*", db),
+                    format!("__keyevent@{}__:*", db),
+                ],
+            )
+            .await?;
+
+        Ok(Self {
+            current_key,
+            session,
+        })
+    }
+
+    /// The connection the notifications are received on.
+    pub fn connection(&self) -> MultiplexedConnection {
+        self.session.connection()
+    }
+
+    /// Sets the key whose events are flagged as `current`.
+    pub fn set_current_key(&self, key: Option<String>) {
+        if let Ok(mut current_key) = self.current_key.lock() {
+            *current_key = key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(channel: &str, payload: &str) -> PubSubMessage {
+        PubSubMessage {
+            kind: SubscriptionKind::Pattern,
+            channel: channel.to_string(),
+            pattern: None,
+            payload: payload.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_complete_notifications() {
+        assert_eq!(complete_notifications("KEA"), None);
+        assert_eq!(complete_notifications("Eg$lshzxe"), None);
+        assert_eq!(complete_notifications(""), Some("KEA".to_string()));
+        assert_eq!(complete_notifications("Ex"), Some("Exg$lshze".to_string()));
+        assert_eq!(complete_notifications("g"), Some("gK$lshzxe".to_string()));
+        assert_eq!(complete_notifications("A"), Some("AK".to_string()));
+    }
+
+    #[test]
+    fn test_parse_keyspace_message() {
+        let expected = KeyspaceEvent {
+            db: 2,
+            key: "user:1".to_string(),
+            event: "hset".to_string(),
+            current: false,
+        };
+
+        assert_eq!(
+            parse_keyspace_message(&message("__keyspace@2__:user:1", "hset")),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            parse_keyspace_message(&message("__keyevent@2__:hset", "user:1")),
+            Some(expected)
+        );
+        assert!(parse_keyspace_message(&message("orders", "created")).is_none());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    core::AppError,
+    utils::{glob_match, split_command_args},
+};
+use futures_util::StreamExt;
+use redis::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Events forwarded per second when the caller does not set a limit.
+const DEFAULT_MAX_EVENTS_PER_SEC: u32 = 100;
+
+/// A command reported by `MONITOR`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct MonitorEvent {
+    /// Unix time in seconds, with microseconds.
+    pub timestamp: f64,
+    pub db: u32,
+    /// Address of the client, `lua` for scripts or `unix:<path>` for socket clients.
+    pub client: String,
+    pub args: Vec<String>,
+    /// Number of matching events skipped by the rate limit since the previous event.
+    pub dropped: u64,
+}
+
+/// Which commands are forwarded. Filters left empty match every command.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct MonitorFilter {
+    /// Command name, compared case-insensitively.
+    pub command: Option<String>,
+    /// Glob pattern one of the command's arguments has to match.
+    pub key_pattern: Option<String>,
+    pub client_addr: Option<String>,
+    /// Matching events forwarded per second at most, `0` disables the limit.
+    pub max_events_per_sec: u32,
+}
+
+impl Default for MonitorFilter {
+    fn default() -> Self {
+        Self {
+            command: None,
+            key_pattern: None,
+            client_addr: None,
+            max_events_per_sec: DEFAULT_MAX_EVENTS_PER_SEC,
+        }
+    }
+}
+
+impl MonitorFilter {
+    pub fn matches(&self, event: &MonitorEvent) -> bool {
+        let command = self.command.as_deref().filter(|c| !c.is_empty());
+        if let Some(command) = command {
+            if !event
+                .args
+                .first()
+                .is_some_and(|name| name.eq_ignore_ascii_case(command))
+            {
+                return false;
+            }
+        }
+
+        let client_addr = self.client_addr.as_deref().filter(|a| !a.is_empty());
+        if client_addr.is_some_and(|addr| event.client != addr) {
+            return false;
+        }
+
+        // Without command docs the key positions are unknown, so any argument may match.
+        let key_pattern = self.key_pattern.as_deref().filter(|p| !p.is_empty());
+        if let Some(pattern) = key_pattern {
+            return event
+                .args
+                .iter()
+                .skip(1)
+                .any(|arg| glob_match(pattern.as_bytes(), arg.as_bytes()));
+        }
+
+        true
+    }
+}
+
+/// Parses a `MONITOR` line such as `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`.
+pub fn parse_monitor_line(line: &str) -> Option<MonitorEvent> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let rest = rest.strip_prefix('[')?;
+    let (source, command) = rest.split_once("] ")?;
+    let (db, client) = source.split_once(' ')?;
+
+    Some(MonitorEvent {
+        timestamp: timestamp.parse().ok()?,
+        db: db.parse().ok()?,
+        client: client.to_string(),
+        args: split_command_args(command)?
+            .iter()
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect(),
+        dropped: 0,
+    })
+}
+
+/// Lets through at most `limit` events per one second window.
+#[derive(Debug)]
+struct RateLimiter {
+    limit: u32,
+    window_start: Instant,
+    admitted: u32,
+    dropped: u64,
+}
+
+impl RateLimiter {
+    fn new(limit: u32, now: Instant) -> Self {
+        Self {
+            limit,
+            window_start: now,
+            admitted: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Returns the number of events dropped since the last admitted one,
+    /// or `None` when this event has to be dropped too.
+    fn admit(&mut self, now: Instant) -> Option<u64> {
+        if self.limit == 0 {
+            return Some(0);
+        }
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.admitted = 0;
+        }
+        if self.admitted >= self.limit {
+            self.dropped += 1;
+            return None;
+        }
+        self.admitted += 1;
+        Some(std::mem::take(&mut self.dropped))
+    }
+}
+
+/// Streams `MONITOR` output of a server to `on_event` from a dedicated connection
+/// until dropped or until `on_event` returns `false`.
+#[derive(Debug)]
+pub struct MonitorSession {
+    handle: JoinHandle<()>,
+}
+
+impl MonitorSession {
+    pub async fn start<F>(
+        client: &Client,
+        filter: MonitorFilter,
+        on_event: F,
+    ) -> Result<Self, AppError>
+    where
+        F: Fn(MonitorEvent) -> bool + Send + 'static,
+    {
+        let monitor = client.get_async_monitor().await.map_err(|e| {
+            log::error!("Failed to start MONITOR: {}", e);
+            AppError::RedisFailed
+        })?;
+
+        let handle = tokio::spawn(async move {
+            let mut stream = monitor.into_on_message::<String>();
+            let mut limiter = RateLimiter::new(filter.max_events_per_sec, Instant::now());
+
+            while let Some(line) = stream.next().await {
+                let Some(event) = parse_monitor_line(&line) else {
+                    log::debug!("Skipping unexpected MONITOR line: {}", line);
+                    continue;
+                };
+                if !filter.matches(&event) {
+                    continue;
+                }
+                let Some(dropped) = limiter.admit(Instant::now()) else {
+                    continue;
+                };
+                if !on_event(MonitorEvent { dropped, ..event }) {
+                    break;
+                }
+            }
+            log::info!("MONITOR stream ended");
+        });
+
+        Ok(Self { handle })
+    }
+}
+
+impl Drop for MonitorSession {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(client: &str, args: &[&str]) -> MonitorEvent {
+        MonitorEvent {
+            client: client.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            ..MonitorEvent::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_monitor_line() {
+        let event = parse_monitor_line(
+            r#"1718030492.107412 [3 127.0.0.1:60866] "SET" "user:1" "a \"b\"\n""#,
+        )
+        .unwrap();
+        assert_eq!(event.timestamp, 1718030492.107412);
+        assert_eq!(event.db, 3);
+        assert_eq!(event.client, "127.0.0.1:60866");
+        assert_eq!(event.args, vec!["SET", "user:1", "a \"b\"\n"]);
+
+        let event = parse_monitor_line(r#"1718030492.2 [0 lua] "get" "counter""#).unwrap();
+        assert_eq!(event.client, "lua");
+        let event = parse_monitor_line(r#"1718030492.3 [0 unix:/tmp/redis.sock] "PING""#).unwrap();
+        assert_eq!(event.client, "unix:/tmp/redis.sock");
+
+        assert!(parse_monitor_line("OK").is_none());
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let set = event("127.0.0.1:1000", &["SET", "user:1", "value"]);
+        let get = event("127.0.0.1:2000", &["GET", "session:1"]);
+
+        assert!(MonitorFilter::default().matches(&set));
+
+        let filter = MonitorFilter {
+            command: Some("set".to_string()),
+            ..MonitorFilter::default()
+        };
+        assert!(filter.matches(&set));
+        assert!(!filter.matches(&get));
+
+        let filter = MonitorFilter {
+            key_pattern: Some("user:*".to_string()),
+            ..MonitorFilter::default()
+        };
+        assert!(filter.matches(&set));
+        assert!(!filter.matches(&get));
+
+        let filter = MonitorFilter {
+            client_addr: Some("127.0.0.1:2000".to_string()),
+            ..MonitorFilter::default()
+        };
+        assert!(!filter.matches(&set));
+        assert!(filter.matches(&get));
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(2, start);
+
+        assert_eq!(limiter.admit(start), Some(0));
+        assert_eq!(limiter.admit(start), Some(0));
+        assert_eq!(limiter.admit(start), None);
+        assert_eq!(limiter.admit(start + Duration::from_millis(500)), None);
+        assert_eq!(limiter.admit(start + Duration::from_secs(1)), Some(2));
+
+        let mut unlimited = RateLimiter::new(0, start);
+        assert!((0..1000).all(|_| unlimited.admit(start) == Some(0)));
+    }
+}
@@ -0,0 +1,243 @@
+use super::{
+    reply::{value_pairs, value_to_string},
+    resp3_client, set_no_evict, CLIENT_NAME,
+};
+use crate::core::AppError;
+use redis::{
+    aio::MultiplexedConnection, AsyncConnectionConfig, Client, PushInfo, PushKind,
+    Value as RedisValue,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Event emitted with every message received on a subscription.
+pub const PUBSUB_MESSAGE_EVENT: &str = "pubsub-message";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionKind {
+    /// `SUBSCRIBE`
+    Channel,
+    /// `PSUBSCRIBE`
+    Pattern,
+    /// `SSUBSCRIBE`, needs Redis 7.0.
+    Shard,
+}
+
+impl SubscriptionKind {
+    fn commands(&self) -> (&'static str, &'static str) {
+        match self {
+            SubscriptionKind::Channel => ("SUBSCRIBE", "UNSUBSCRIBE"),
+            SubscriptionKind::Pattern => ("PSUBSCRIBE", "PUNSUBSCRIBE"),
+            SubscriptionKind::Shard => ("SSUBSCRIBE", "SUNSUBSCRIBE"),
+        }
+    }
+}
+
+/// A message received on a subscription.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PubSubMessage {
+    pub kind: SubscriptionKind,
+    pub channel: String,
+    /// The pattern the channel matched for `Pattern` subscriptions.
+    pub pattern: Option<String>,
+    pub payload: String,
+}
+
+/// A channel with the number of clients subscribed to it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PubSubChannel {
+    pub name: String,
+    pub subscribers: u64,
+}
+
+/// Reads a message out of a RESP3 push, other pushes such as subscription replies are skipped.
+pub fn parse_pubsub_push(push: &PushInfo) -> Option<PubSubMessage> {
+    let text = |index: usize| push.data.get(index).and_then(value_to_string);
+    match push.kind {
+        PushKind::Message | PushKind::SMessage => Some(PubSubMessage {
+            kind: match push.kind {
+                PushKind::SMessage => SubscriptionKind::Shard,
+                _ => SubscriptionKind::Channel,
+            },
+            channel: text(0)?,
+            pattern: None,
+            payload: text(1)?,
+        }),
+        PushKind::PMessage => Some(PubSubMessage {
+            kind: SubscriptionKind::Pattern,
+            channel: text(1)?,
+            pattern: Some(text(0)?),
+            payload: text(2)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Parses the `PUBSUB NUMSUB` and `PUBSUB SHARDNUMSUB` replies.
+pub fn parse_numsub(value: &RedisValue) -> Vec<PubSubChannel> {
+    value_pairs(value)
+        .into_iter()
+        .map(|(name, count)| PubSubChannel {
+            name,
+            subscribers: match count {
+                RedisValue::Int(count) => (*count).max(0) as u64,
+                _ => 0,
+            },
+        })
+        .collect()
+}
+
+/// A dedicated RESP3 connection holding the app's subscriptions. Messages are pushed to
+/// `on_message` as they arrive, dropping the session closes the connection.
+#[derive(Clone)]
+pub struct PubSubSession {
+    connection: MultiplexedConnection,
+}
+
+impl std::fmt::Debug for PubSubSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PubSubSession").finish_non_exhaustive()
+    }
+}
+
+impl PubSubSession {
+    /// `no_evict` turns on `CLIENT NO-EVICT` for the connection.
+    pub async fn start<F>(client: &Client, no_evict: bool, on_message: F) -> Result<Self, AppError>
+    where
+        F: Fn(PubSubMessage) + Send + Sync + 'static,
+    {
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(Some(Duration::from_secs(6)))
+            .set_push_sender(move |push: PushInfo| {
+                if let Some(message) = parse_pubsub_push(&push) {
+                    on_message(message);
+                }
+                Ok::<(), ()>(())
+            });
+        let mut connection = resp3_client(client)?
+            .get_multiplexed_async_connection_with_config(&config)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to open the pub/sub connection: {}", e);
+                AppError::RedisFailed
+            })?;
+
+        if let Err(e) = redis::cmd("CLIENT")
+            .arg("SETNAME")
+            .arg(CLIENT_NAME.as_str())
+            .exec_async(&mut connection)
+            .await
+        {
+            log::warn!("Failed to identify the pub/sub connection: {}", e);
+        }
+        if no_evict {
+            if let Err(e) = set_no_evict(&mut connection, true).await {
+                log::warn!("Failed to turn on CLIENT NO-EVICT: {}", e);
+            }
+        }
+
+        Ok(Self { connection })
+    }
+
+    /// The RESP3 connection the subscriptions are on, it still accepts regular commands.
+    pub fn connection(&self) -> MultiplexedConnection {
+        self.connection.clone()
+    }
+
+    pub async fn subscribe(
+        &self,
+        kind: SubscriptionKind,
+        channels: &[String],
+    ) -> Result<(), AppError> {
+        let (command, _) = kind.commands();
+        self.send(command, channels).await
+    }
+
+    /// Unsubscribes from the given channels, or from every channel of `kind` when empty.
+    pub async fn unsubscribe(
+        &self,
+        kind: SubscriptionKind,
+        channels: &[String],
+    ) -> Result<(), AppError> {
+        let (_, command) = kind.commands();
+        self.send(command, channels).await
+    }
+
+    async fn send(&self, command: &str, channels: &[String]) -> Result<(), AppError> {
+        let mut connection = self.connection.clone();
+        redis::cmd(command)
+            .arg(channels)
+            .exec_async(&mut connection)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to run {} {:?}: {}", command, channels, e);
+                AppError::RedisFailed
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_pubsub_push() {
+        let message = parse_pubsub_push(&PushInfo {
+            kind: PushKind::PMessage,
+            data: vec![bulk("orders:*"), bulk("orders:eu"), bulk("created")],
+        })
+        .unwrap();
+        assert_eq!(
+            message,
+            PubSubMessage {
+                kind: SubscriptionKind::Pattern,
+                channel: "orders:eu".to_string(),
+                pattern: Some("orders:*".to_string()),
+                payload: "created".to_string(),
+            }
+        );
+
+        let message = parse_pubsub_push(&PushInfo {
+            kind: PushKind::SMessage,
+            data: vec![bulk("orders:{1}"), bulk("paid")],
+        })
+        .unwrap();
+        assert_eq!(message.kind, SubscriptionKind::Shard);
+        assert_eq!(message.pattern, None);
+
+        assert!(parse_pubsub_push(&PushInfo {
+            kind: PushKind::Subscribe,
+            data: vec![bulk("orders"), RedisValue::Int(1)],
+        })
+        .is_none());
+    }
+
+    #[test]
+    fn test_parse_numsub() {
+        let reply = RedisValue::Array(vec![
+            bulk("orders"),
+            RedisValue::Int(2),
+            bulk("events"),
+            RedisValue::Int(0),
+        ]);
+
+        assert_eq!(
+            parse_numsub(&reply),
+            vec![
+                PubSubChannel {
+                    name: "orders".to_string(),
+                    subscribers: 2
+                },
+                PubSubChannel {
+                    name: "events".to_string(),
+                    subscribers: 0
+                },
+            ]
+        );
+    }
+}
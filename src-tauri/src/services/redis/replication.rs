@@ -0,0 +1,290 @@
+use super::{
+    get_connection, node_client,
+    reply::{value_items, value_to_string},
+};
+use crate::{
+    core::AppError,
+    utils::{parse_server_info, InfoSection, InfoValue},
+};
+use redis::{aio::MultiplexedConnection, Client, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+
+/// A replica as listed by its primary, completed with its own view when it could be reached.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ReplicaStatus {
+    pub address: String,
+    pub port: u16,
+    /// `online`, `wait_bgsave` or `send_bulk` as the primary reports it.
+    pub state: String,
+    /// The offset the replica last acknowledged to the primary.
+    pub acknowledged_offset: i64,
+    /// Seconds since that acknowledgement.
+    pub lag_seconds: Option<i64>,
+    /// `up` or `down` as the replica reports its link, `None` when it could not be reached.
+    pub link_status: Option<String>,
+    /// The offset the replica reports itself, `None` when it could not be reached.
+    pub replica_offset: Option<i64>,
+    /// Bytes of the replication stream the replica has not processed yet.
+    pub lag_bytes: i64,
+    pub caught_up: bool,
+}
+
+/// The primary a replica follows, as `ROLE` and `INFO replication` report it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct PrimaryLink {
+    pub address: String,
+    pub port: u16,
+    /// `connect`, `connecting`, `sync` or `connected`.
+    pub state: String,
+    /// `up` or `down`.
+    pub link_status: String,
+    /// Seconds since the last interaction with the primary.
+    pub last_io_seconds: Option<i64>,
+    pub sync_in_progress: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+pub struct ReplicationOverview {
+    /// `master`, `slave` or `sentinel` as `ROLE` reports it.
+    pub role: String,
+    /// The primary's own offset, or the processed offset on a replica.
+    pub offset: i64,
+    /// Set on replicas.
+    pub primary: Option<PrimaryLink>,
+    /// Set on primaries.
+    pub replicas: Vec<ReplicaStatus>,
+    /// Every `INFO replication` field as sent.
+    pub info: InfoSection,
+}
+
+fn value_to_i64(value: &RedisValue) -> Option<i64> {
+    match value {
+        RedisValue::Int(i) => Some(*i),
+        other => value_to_string(other).and_then(|s| s.parse().ok()),
+    }
+}
+
+fn info_integer(info: &InfoSection, name: &str) -> Option<i64> {
+    match info.get(name)? {
+        InfoValue::Integer(i) => Some(*i),
+        _ => None,
+    }
+}
+
+fn info_text(info: &InfoSection, name: &str) -> Option<String> {
+    match info.get(name)? {
+        InfoValue::Text(text) => Some(text.clone()),
+        InfoValue::Integer(i) => Some(i.to_string()),
+        _ => None,
+    }
+}
+
+impl ReplicaStatus {
+    /// Compares the replica with the primary's offset, preferring the replica's own offset
+    /// over the last acknowledgement.
+    fn measure_lag(&mut self, primary_offset: i64) {
+        let offset = self.replica_offset.unwrap_or(self.acknowledged_offset);
+        self.lag_bytes = (primary_offset - offset).max(0);
+        self.caught_up = self.state == "online"
+            && self.lag_bytes == 0
+            && self.link_status.as_deref().is_none_or(|link| link == "up");
+    }
+}
+
+/// Parses the `ROLE` reply together with `INFO replication`. `ROLE` lists the replicas
+/// and the offsets, `INFO` adds their state and lag.
+pub fn parse_replication(role: &RedisValue, info: &str) -> ReplicationOverview {
+    let info = parse_server_info(info).replication;
+    let items = value_items(role);
+    let mut overview = ReplicationOverview {
+        role: items.first().and_then(value_to_string).unwrap_or_default(),
+        ..ReplicationOverview::default()
+    };
+
+    match overview.role.as_str() {
+        // master, <offset>, [[ip, port, offset], ...]
+        "master" => {
+            overview.offset = items.get(1).and_then(value_to_i64).unwrap_or_default();
+            let listed: Vec<InfoSection> = info
+                .iter()
+                .filter(|(name, _)| name.starts_with("slave"))
+                .filter_map(|(_, value)| match value {
+                    InfoValue::Nested(fields) => Some(fields.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            for replica in items.get(2).map(value_items).unwrap_or_default() {
+                let fields = value_items(replica);
+                let mut status = ReplicaStatus {
+                    address: fields.first().and_then(value_to_string).unwrap_or_default(),
+                    port: fields.get(1).and_then(value_to_i64).unwrap_or_default() as u16,
+                    acknowledged_offset: fields.get(2).and_then(value_to_i64).unwrap_or_default(),
+                    ..ReplicaStatus::default()
+                };
+                if let Some(fields) = listed.iter().find(|fields| {
+                    info_text(fields, "ip").as_deref() == Some(status.address.as_str())
+                        && info_integer(fields, "port") == Some(status.port as i64)
+                }) {
+                    status.state = info_text(fields, "state").unwrap_or_default();
+                    status.lag_seconds = info_integer(fields, "lag");
+                }
+                status.measure_lag(overview.offset);
+                overview.replicas.push(status);
+            }
+        }
+        // slave, <primary ip>, <primary port>, <state>, <offset>
+        "slave" => {
+            overview.offset = items.get(4).and_then(value_to_i64).unwrap_or_default();
+            overview.primary = Some(PrimaryLink {
+                address: items.get(1).and_then(value_to_string).unwrap_or_default(),
+                port: items.get(2).and_then(value_to_i64).unwrap_or_default() as u16,
+                state: items.get(3).and_then(value_to_string).unwrap_or_default(),
+                link_status: info_text(&info, "master_link_status").unwrap_or_default(),
+                last_io_seconds: info_integer(&info, "master_last_io_seconds_ago")
+                    .filter(|seconds| *seconds >= 0),
+                sync_in_progress: info_integer(&info, "master_sync_in_progress") == Some(1),
+            });
+        }
+        _ => {}
+    }
+
+    overview.info = info;
+    overview
+}
+
+async fn read_replication(
+    connection: &mut MultiplexedConnection,
+) -> Result<ReplicationOverview, AppError> {
+    let (role, info): (RedisValue, String) = redis::pipe()
+        .cmd("ROLE")
+        .cmd("INFO")
+        .arg("replication")
+        .query_async(connection)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to read the replication state: {}", e);
+            AppError::RedisFailed
+        })?;
+
+    Ok(parse_replication(&role, &info))
+}
+
+/// Asks a replica for its own replication state, `None` when it cannot be reached,
+/// e.g. when the primary lists it with an address only reachable inside its network.
+async fn follow_replica(seed: &Client, replica: &ReplicaStatus) -> Option<ReplicationOverview> {
+    let client = node_client(seed, &replica.address, replica.port).ok()?;
+    let result = match get_connection(&client).await {
+        Ok(mut connection) => read_replication(&mut connection).await,
+        Err(e) => Err(e),
+    };
+    if result.is_err() {
+        log::warn!(
+            "Replica {}:{} could not be reached",
+            replica.address,
+            replica.port
+        );
+    }
+    result.ok()
+}
+
+/// Reads the replication state of the connected node. On a primary every listed replica
+/// is asked for its own offset and link so the lag reflects what it actually processed,
+/// connecting with the credentials and TLS of `client`.
+pub async fn replication_overview(client: &Client) -> Result<ReplicationOverview, AppError> {
+    let mut connection = get_connection(client).await?;
+    let mut overview = read_replication(&mut connection).await?;
+
+    for replica in &mut overview.replicas {
+        if let Some(own) = follow_replica(client, replica).await {
+            replica.replica_offset = Some(own.offset);
+            replica.link_status = own.primary.map(|primary| primary.link_status);
+            replica.measure_lag(overview.offset);
+        }
+    }
+
+    Ok(overview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_replication_of_primary() {
+        let role = RedisValue::Array(vec![
+            bulk("master"),
+            RedisValue::Int(3129659),
+            RedisValue::Array(vec![
+                RedisValue::Array(vec![bulk("10.0.0.2"), bulk("6379"), bulk("3129659")]),
+                RedisValue::Array(vec![bulk("10.0.0.3"), bulk("6379"), bulk("3129242")]),
+            ]),
+        ]);
+        let info = "# Replication\r\nrole:master\r\nconnected_slaves:2\r\n\
+            slave0:ip=10.0.0.2,port=6379,state=online,offset=3129659,lag=0\r\n\
+            slave1:ip=10.0.0.3,port=6379,state=online,offset=3129242,lag=1\r\n\
+            master_repl_offset:3129659\r\n";
+
+        let overview = parse_replication(&role, info);
+        assert_eq!(overview.role, "master");
+        assert_eq!(overview.offset, 3129659);
+        assert!(overview.primary.is_none());
+        assert_eq!(overview.replicas.len(), 2);
+        assert!(overview.replicas[0].caught_up);
+        assert_eq!(overview.replicas[1].lag_bytes, 417);
+        assert_eq!(overview.replicas[1].lag_seconds, Some(1));
+        assert!(!overview.replicas[1].caught_up);
+        assert_eq!(
+            overview.info.get("connected_slaves"),
+            Some(&InfoValue::Integer(2))
+        );
+    }
+
+    #[test]
+    fn test_parse_replication_of_replica() {
+        let role = RedisValue::Array(vec![
+            bulk("slave"),
+            bulk("10.0.0.1"),
+            RedisValue::Int(6379),
+            bulk("connected"),
+            RedisValue::Int(3167038),
+        ]);
+        let info = "# Replication\r\nrole:slave\r\nmaster_host:10.0.0.1\r\nmaster_port:6379\r\n\
+            master_link_status:up\r\nmaster_last_io_seconds_ago:-1\r\nmaster_sync_in_progress:0\r\n";
+
+        let overview = parse_replication(&role, info);
+        assert_eq!(overview.offset, 3167038);
+        assert_eq!(
+            overview.primary,
+            Some(PrimaryLink {
+                address: "10.0.0.1".to_string(),
+                port: 6379,
+                state: "connected".to_string(),
+                link_status: "up".to_string(),
+                last_io_seconds: None,
+                sync_in_progress: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_measure_lag_prefers_replica_offset() {
+        let mut replica = ReplicaStatus {
+            state: "online".to_string(),
+            acknowledged_offset: 90,
+            replica_offset: Some(100),
+            link_status: Some("up".to_string()),
+            ..ReplicaStatus::default()
+        };
+        replica.measure_lag(100);
+        assert!(replica.caught_up);
+
+        replica.link_status = Some("down".to_string());
+        replica.measure_lag(100);
+        assert!(!replica.caught_up);
+    }
+}
@@ -0,0 +1,42 @@
+use redis::Value as RedisValue;
+
+/// Reads a simple, bulk or verbatim string reply.
+pub(super) fn value_to_string(value: &RedisValue) -> Option<String> {
+    match value {
+        RedisValue::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        RedisValue::SimpleString(s) => Some(s.clone()),
+        RedisValue::VerbatimString { text, .. } => Some(text.clone()),
+        RedisValue::Okay => Some("OK".to_string()),
+        _ => None,
+    }
+}
+
+/// Reads the elements of an array or set reply, anything else has none.
+pub(super) fn value_items(value: &RedisValue) -> &[RedisValue] {
+    match value {
+        RedisValue::Array(items) | RedisValue::Set(items) => items,
+        _ => &[],
+    }
+}
+
+pub(super) fn value_strings(value: &RedisValue) -> Vec<String> {
+    value_items(value)
+        .iter()
+        .filter_map(value_to_string)
+        .collect()
+}
+
+/// Reads a RESP3 map or a RESP2 flat `[key, value, key, value]` array as pairs.
+pub(super) fn value_pairs(value: &RedisValue) -> Vec<(String, &RedisValue)> {
+    match value {
+        RedisValue::Map(pairs) => pairs
+            .iter()
+            .filter_map(|(k, v)| value_to_string(k).map(|k| (k, v)))
+            .collect(),
+        RedisValue::Array(items) => items
+            .chunks_exact(2)
+            .filter_map(|pair| value_to_string(&pair[0]).map(|k| (k, &pair[1])))
+            .collect(),
+        _ => vec![],
+    }
+}
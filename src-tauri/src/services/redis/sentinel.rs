@@ -0,0 +1,372 @@
+use super::{
+    reply::{value_items, value_pairs, value_to_string},
+    tcp_addr,
+};
+use crate::{
+    core::AppError,
+    models::{SentinelAddress, SentinelSettings},
+};
+use futures_util::StreamExt;
+use redis::{AsyncConnectionConfig, Client, IntoConnectionInfo, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+use tokio::task::JoinHandle;
+
+/// Event emitted after the app followed a failover to a new master.
+pub const SENTINEL_FAILOVER_EVENT: &str = "sentinel-failover";
+
+/// Channel sentinels announce a promoted master on.
+const SWITCH_MASTER_CHANNEL: &str = "+switch-master";
+
+/// How long the watcher waits before asking the sentinels again once none answers.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Flags a node is reported with when the sentinels consider it unreachable.
+const UNHEALTHY_FLAGS: [&str; 3] = ["s_down", "o_down", "disconnected"];
+
+/// A master, replica or sentinel as reported by `SENTINEL MASTER`, `REPLICAS` or `SENTINELS`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct SentinelNode {
+    /// The master name, `ip:port` for replicas or the run id for sentinels.
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    /// E.g. `master`, `slave,s_down` or `sentinel`.
+    pub flags: Vec<String>,
+    pub healthy: bool,
+    /// Every field as sent, e.g. `quorum`, `master-link-status` or `slave-repl-offset`.
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct SentinelOverview {
+    /// The sentinel that answered, as `address:port`.
+    pub sentinel: String,
+    pub master: SentinelNode,
+    pub replicas: Vec<SentinelNode>,
+    /// The other sentinels monitoring the master.
+    pub sentinels: Vec<SentinelNode>,
+}
+
+/// Opens a client to one of the sentinels with the sentinels' own credentials and TLS.
+fn sentinel_client(
+    settings: &SentinelSettings,
+    sentinel: &SentinelAddress,
+) -> Result<Client, AppError> {
+    let failed = |e: redis::RedisError| {
+        log::error!(
+            "Failed to create Sentinel client for {}:{} - {}",
+            sentinel.address,
+            sentinel.port,
+            e
+        );
+        AppError::RedisFailed
+    };
+    let info = tcp_addr(sentinel.address.clone(), sentinel.port, settings.tls)
+        .into_connection_info()
+        .map_err(failed)?;
+
+    let mut redis_settings = info.redis_settings().clone();
+    if let Some(username) = &settings.username {
+        redis_settings = redis_settings.set_username(username);
+    }
+    if let Some(password) = &settings.password {
+        redis_settings = redis_settings.set_password(password);
+    }
+    Client::open(info.set_redis_settings(redis_settings)).map_err(failed)
+}
+
+/// Runs a command on the first sentinel that answers it, returning that sentinel too.
+async fn query_sentinels<'a>(
+    settings: &'a SentinelSettings,
+    cmd: &redis::Cmd,
+) -> Result<(&'a SentinelAddress, RedisValue), AppError> {
+    let config = AsyncConnectionConfig::new().set_connection_timeout(Some(Duration::from_secs(6)));
+    for sentinel in &settings.sentinels {
+        let Ok(client) = sentinel_client(settings, sentinel) else {
+            continue;
+        };
+        let reply = match client
+            .get_multiplexed_async_connection_with_config(&config)
+            .await
+        {
+            Ok(mut connection) => cmd.query_async(&mut connection).await,
+            Err(e) => Err(e),
+        };
+        match reply {
+            Ok(value) => return Ok((sentinel, value)),
+            Err(e) => log::warn!(
+                "Sentinel {}:{} did not answer: {}",
+                sentinel.address,
+                sentinel.port,
+                e
+            ),
+        }
+    }
+
+    log::error!("No sentinel of {} answered", settings.master_name);
+    Err(AppError::RedisFailed)
+}
+
+/// Asks the sentinels for the address of the current master.
+pub async fn resolve_master(settings: &SentinelSettings) -> Result<(String, u16), AppError> {
+    let mut cmd = redis::cmd("SENTINEL");
+    cmd.arg("GET-MASTER-ADDR-BY-NAME")
+        .arg(&settings.master_name);
+    let (_, reply) = query_sentinels(settings, &cmd).await?;
+
+    let address: Vec<String> = value_items(&reply)
+        .iter()
+        .filter_map(value_to_string)
+        .collect();
+    match address.as_slice() {
+        [host, port] => port.parse().map(|port| (host.clone(), port)).map_err(|_| {
+            log::error!("Invalid master port from Sentinel: {}", port);
+            AppError::RedisFailed
+        }),
+        _ => {
+            log::error!("Sentinels do not know master {}", settings.master_name);
+            Err(AppError::RedisFailed)
+        }
+    }
+}
+
+/// Parses a `SENTINEL MASTER` node or one entry of `SENTINEL REPLICAS`/`SENTINELS`.
+pub fn parse_sentinel_node(value: &RedisValue) -> SentinelNode {
+    let fields: BTreeMap<String, String> = value_pairs(value)
+        .into_iter()
+        .filter_map(|(name, value)| value_to_string(value).map(|value| (name, value)))
+        .collect();
+    let field = |name: &str| fields.get(name).cloned().unwrap_or_default();
+    let flags: Vec<String> = field("flags")
+        .split(',')
+        .filter(|flag| !flag.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    SentinelNode {
+        name: field("name"),
+        address: field("ip"),
+        port: field("port").parse().unwrap_or_default(),
+        healthy: !flags
+            .iter()
+            .any(|flag| UNHEALTHY_FLAGS.contains(&flag.as_str())),
+        flags,
+        fields,
+    }
+}
+
+/// Reads the master, its replicas and the sentinels monitoring it from the first
+/// sentinel that answers.
+pub async fn sentinel_overview(settings: &SentinelSettings) -> Result<SentinelOverview, AppError> {
+    let query = |subcommand: &str| {
+        let mut cmd = redis::cmd("SENTINEL");
+        cmd.arg(subcommand).arg(&settings.master_name);
+        cmd
+    };
+
+    let (sentinel, master) = query_sentinels(settings, &query("MASTER")).await?;
+    // Asked of the same sentinel so the three replies describe the same view.
+    let single = SentinelSettings {
+        sentinels: vec![sentinel.clone()],
+        ..settings.clone()
+    };
+    // REPLICAS was added in Redis 5, SLAVES is kept for older sentinels.
+    let replicas = match query_sentinels(&single, &query("REPLICAS")).await {
+        Ok((_, replicas)) => replicas,
+        Err(_) => query_sentinels(&single, &query("SLAVES")).await?.1,
+    };
+    let (_, sentinels) = query_sentinels(&single, &query("SENTINELS")).await?;
+
+    Ok(SentinelOverview {
+        sentinel: format!("{}:{}", sentinel.address, sentinel.port),
+        master: parse_sentinel_node(&master),
+        replicas: value_items(&replicas)
+            .iter()
+            .map(parse_sentinel_node)
+            .collect(),
+        sentinels: value_items(&sentinels)
+            .iter()
+            .map(parse_sentinel_node)
+            .collect(),
+    })
+}
+
+/// Reads the new master out of a `+switch-master` message,
+/// `<master name> <old ip> <old port> <new ip> <new port>`.
+fn parse_switch_master(payload: &str, master_name: &str) -> Option<(String, u16)> {
+    let parts: Vec<&str> = payload.split_whitespace().collect();
+    match parts.as_slice() {
+        [name, _, _, ip, port] if *name == master_name => {
+            Some((ip.to_string(), port.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+/// Listens to the sentinels for a failover of the master and reports the new master to
+/// `on_switch`, moving on to the next sentinel whenever the current one goes away.
+#[derive(Debug)]
+pub struct SentinelWatcher {
+    handle: JoinHandle<()>,
+}
+
+impl SentinelWatcher {
+    pub fn start<F>(settings: SentinelSettings, on_switch: F) -> Self
+    where
+        F: Fn(String, u16) + Send + Sync + 'static,
+    {
+        let handle = tokio::spawn(async move {
+            loop {
+                for sentinel in &settings.sentinels {
+                    if let Err(e) = watch_sentinel(&settings, sentinel, &on_switch).await {
+                        log::warn!(
+                            "Lost Sentinel {}:{} - {:?}",
+                            sentinel.address,
+                            sentinel.port,
+                            e
+                        );
+                    }
+                }
+                tokio::time::sleep(RETRY_INTERVAL).await;
+            }
+        });
+
+        Self { handle }
+    }
+}
+
+async fn watch_sentinel<F>(
+    settings: &SentinelSettings,
+    sentinel: &SentinelAddress,
+    on_switch: &F,
+) -> Result<(), AppError>
+where
+    F: Fn(String, u16),
+{
+    let master_name = settings.master_name.as_str();
+    let mut pubsub = sentinel_client(settings, sentinel)?
+        .get_async_pubsub()
+        .await
+        .map_err(|e| {
+            log::error!("Failed to connect to Sentinel: {}", e);
+            AppError::RedisFailed
+        })?;
+    pubsub.subscribe(SWITCH_MASTER_CHANNEL).await.map_err(|e| {
+        log::error!("Failed to subscribe to {}: {}", SWITCH_MASTER_CHANNEL, e);
+        AppError::RedisFailed
+    })?;
+
+    let mut messages = pubsub.into_on_message();
+    while let Some(message) = messages.next().await {
+        let Ok(payload) = message.get_payload::<String>() else {
+            continue;
+        };
+        if let Some((address, port)) = parse_switch_master(&payload, master_name) {
+            log::info!("Master {} moved to {}:{}", master_name, address, port);
+            on_switch(address, port);
+        }
+    }
+    Ok(())
+}
+
+impl Drop for SentinelWatcher {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::run_redis_sentinel_container;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_sentinel_node() {
+        let reply = RedisValue::Array(vec![
+            bulk("name"),
+            bulk("10.0.0.2:6379"),
+            bulk("ip"),
+            bulk("10.0.0.2"),
+            bulk("port"),
+            bulk("6379"),
+            bulk("flags"),
+            bulk("slave,s_down"),
+            bulk("master-link-status"),
+            bulk("err"),
+        ]);
+
+        let node = parse_sentinel_node(&reply);
+        assert_eq!(node.address, "10.0.0.2");
+        assert_eq!(node.port, 6379);
+        assert_eq!(node.flags, vec!["slave", "s_down"]);
+        assert!(!node.healthy);
+        assert_eq!(node.fields["master-link-status"], "err");
+    }
+
+    #[test]
+    fn test_sentinel_client_uses_sentinel_settings() {
+        let settings = SentinelSettings {
+            master_name: "mymaster".to_string(),
+            sentinels: vec![SentinelAddress {
+                address: "sentinel.local".to_string(),
+                port: 26379,
+            }],
+            username: Some("watcher".to_string()),
+            password: Some("sentinel-secret".to_string()),
+            tls: true,
+        };
+        let client = sentinel_client(&settings, &settings.sentinels[0]).unwrap();
+        let info = client.get_connection_info();
+
+        assert!(matches!(
+            info.addr(),
+            redis::ConnectionAddr::TcpTls { host, port: 26379, .. } if host == "sentinel.local"
+        ));
+        assert_eq!(info.redis_settings().username(), Some("watcher"));
+        assert_eq!(info.redis_settings().password(), Some("sentinel-secret"));
+        // Sentinels have no databases, the master's is never selected on them.
+        assert_eq!(info.redis_settings().db(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_master_with_sentinel_credentials() {
+        let (host, port, container) = run_redis_sentinel_container("sentinel-secret").await;
+        let mut settings = SentinelSettings {
+            master_name: "mymaster".to_string(),
+            sentinels: vec![SentinelAddress {
+                address: host,
+                port,
+            }],
+            username: None,
+            password: Some("sentinel-secret".to_string()),
+            tls: false,
+        };
+
+        let master = resolve_master(&settings).await.unwrap();
+        assert_eq!(master, ("127.0.0.1".to_string(), 6379));
+
+        // The sentinels reject any other password, e.g. the master's.
+        settings.password = Some("master-secret".to_string());
+        assert_eq!(resolve_master(&settings).await, Err(AppError::RedisFailed));
+
+        container.rm().await.unwrap();
+    }
+
+    #[test]
+    fn test_parse_switch_master() {
+        assert_eq!(
+            parse_switch_master("mymaster 10.0.0.1 6379 10.0.0.2 6380", "mymaster"),
+            Some(("10.0.0.2".to_string(), 6380))
+        );
+        assert_eq!(
+            parse_switch_master("other 10.0.0.1 6379 10.0.0.2 6380", "mymaster"),
+            None
+        );
+        assert_eq!(parse_switch_master("mymaster", "mymaster"), None);
+    }
+}
@@ -0,0 +1,266 @@
+use super::{
+    reply::{value_items, value_pairs, value_strings, value_to_string},
+    TaskConnection,
+};
+use crate::models::SlowlogRecord;
+use redis::{aio::MultiplexedConnection, Client, Value as RedisValue};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
+};
+use tokio::{
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+use uuid::Uuid;
+
+/// How often the slow log of the opened server is copied to the database.
+const CAPTURE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An entry of `SLOWLOG GET`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct SlowlogEntry {
+    pub id: i64,
+    /// Unix time in seconds the command was logged at.
+    pub timestamp: i64,
+    pub duration_us: i64,
+    pub args: Vec<String>,
+    /// Empty on servers older than 4.0, which do not report the client.
+    pub client_addr: String,
+    pub client_name: String,
+}
+
+/// The thresholds deciding which commands end up in the slow log.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SlowlogConfig {
+    /// `slowlog-log-slower-than` in microseconds, negative disables the log.
+    pub log_slower_than: i64,
+    /// `slowlog-max-len`
+    pub max_len: u32,
+}
+
+fn value_to_int(value: &RedisValue) -> i64 {
+    match value {
+        RedisValue::Int(i) => *i,
+        other => value_to_string(other)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+    }
+}
+
+/// Parses the `SLOWLOG GET` reply, newest entry first as sent by the server.
+pub fn parse_slowlog(value: &RedisValue) -> Vec<SlowlogEntry> {
+    value_items(value)
+        .iter()
+        .map(|entry| {
+            let fields = value_items(entry);
+            let field = |index: usize| fields.get(index);
+            SlowlogEntry {
+                id: field(0).map(value_to_int).unwrap_or_default(),
+                timestamp: field(1).map(value_to_int).unwrap_or_default(),
+                duration_us: field(2).map(value_to_int).unwrap_or_default(),
+                args: field(3).map(value_strings).unwrap_or_default(),
+                client_addr: field(4).and_then(value_to_string).unwrap_or_default(),
+                client_name: field(5).and_then(value_to_string).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Parses the `CONFIG GET slowlog-*` reply.
+pub fn parse_slowlog_config(value: &RedisValue) -> SlowlogConfig {
+    let mut config = SlowlogConfig::default();
+    for (name, value) in value_pairs(value) {
+        match name.as_str() {
+            "slowlog-log-slower-than" => config.log_slower_than = value_to_int(value),
+            "slowlog-max-len" => config.max_len = value_to_int(value).max(0) as u32,
+            _ => {}
+        }
+    }
+    config
+}
+
+/// Returns the entries logged after `last_id`. Entry ids start over when the server
+/// restarts, so every entry is new again once the newest id is lower than `last_id`.
+fn new_entries(entries: Vec<SlowlogEntry>, last_id: Option<i64>) -> Vec<SlowlogEntry> {
+    let newest = entries.iter().map(|entry| entry.id).max();
+    match (last_id, newest) {
+        (Some(last_id), Some(newest)) if newest >= last_id => entries
+            .into_iter()
+            .filter(|entry| entry.id > last_id)
+            .collect(),
+        _ => entries,
+    }
+}
+
+/// Copies the slow log of a server to `on_capture` in a background task until dropped,
+/// so entries survive the server's ring buffer rolling over.
+#[derive(Debug)]
+pub struct SlowlogCollector {
+    connection: TaskConnection,
+    handle: JoinHandle<()>,
+}
+
+impl SlowlogCollector {
+    /// `no_evict` is the app's `CLIENT NO-EVICT` flag.
+    pub fn start<F>(
+        client: Client,
+        server_id: Uuid,
+        no_evict: Arc<AtomicBool>,
+        on_capture: F,
+    ) -> Self
+    where
+        F: Fn(Vec<SlowlogRecord>) + Send + 'static,
+    {
+        let connection = TaskConnection::new(no_evict);
+        let task_connection = connection.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = interval(CAPTURE_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut last_id: Option<i64> = None;
+
+            loop {
+                ticker.tick().await;
+                let Some(mut connection) = task_connection.get(&client).await else {
+                    continue;
+                };
+                let reply: RedisValue = match redis::cmd("SLOWLOG")
+                    .arg("GET")
+                    .arg(-1)
+                    .query_async(&mut connection)
+                    .await
+                {
+                    Ok(reply) => reply,
+                    Err(e) => {
+                        log::warn!("Failed to capture the slow log: {}", e);
+                        task_connection.reset();
+                        continue;
+                    }
+                };
+
+                let entries = new_entries(parse_slowlog(&reply), last_id);
+                if entries.is_empty() {
+                    continue;
+                }
+                last_id = entries.iter().map(|entry| entry.id).max();
+                on_capture(
+                    entries
+                        .iter()
+                        .map(|entry| SlowlogRecord::from_entry(server_id, entry))
+                        .collect(),
+                );
+            }
+        });
+
+        Self { connection, handle }
+    }
+
+    /// The connection the slow log is captured on, `None` while it reconnects.
+    pub fn connection(&self) -> Option<MultiplexedConnection> {
+        self.connection.current()
+    }
+}
+
+impl Drop for SlowlogCollector {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> RedisValue {
+        RedisValue::BulkString(s.as_bytes().to_vec())
+    }
+
+    fn entry(id: i64) -> SlowlogEntry {
+        SlowlogEntry {
+            id,
+            ..SlowlogEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_slowlog() {
+        let reply = RedisValue::Array(vec![
+            RedisValue::Array(vec![
+                RedisValue::Int(14),
+                RedisValue::Int(1718030492),
+                RedisValue::Int(15230),
+                RedisValue::Array(vec![bulk("KEYS"), bulk("*")]),
+                bulk("127.0.0.1:52110"),
+                bulk("worker"),
+            ]),
+            // Servers older than 4.0 only send the first four fields.
+            RedisValue::Array(vec![
+                RedisValue::Int(13),
+                RedisValue::Int(1718030400),
+                RedisValue::Int(10021),
+                RedisValue::Array(vec![bulk("DEBUG"), bulk("SLEEP"), bulk("0.01")]),
+            ]),
+        ]);
+
+        let entries = parse_slowlog(&reply);
+        assert_eq!(
+            entries[0],
+            SlowlogEntry {
+                id: 14,
+                timestamp: 1718030492,
+                duration_us: 15230,
+                args: vec!["KEYS".to_string(), "*".to_string()],
+                client_addr: "127.0.0.1:52110".to_string(),
+                client_name: "worker".to_string(),
+            }
+        );
+        assert_eq!(entries[1].args.len(), 3);
+        assert_eq!(entries[1].client_addr, "");
+    }
+
+    #[test]
+    fn test_parse_slowlog_config() {
+        let resp2 = RedisValue::Array(vec![
+            bulk("slowlog-max-len"),
+            bulk("128"),
+            bulk("slowlog-log-slower-than"),
+            bulk("10000"),
+        ]);
+        let resp3 = RedisValue::Map(vec![
+            (bulk("slowlog-log-slower-than"), bulk("-1")),
+            (bulk("slowlog-max-len"), bulk("1024")),
+        ]);
+
+        assert_eq!(
+            parse_slowlog_config(&resp2),
+            SlowlogConfig {
+                log_slower_than: 10000,
+                max_len: 128
+            }
+        );
+        assert_eq!(
+            parse_slowlog_config(&resp3),
+            SlowlogConfig {
+                log_slower_than: -1,
+                max_len: 1024
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_entries() {
+        let ids = |entries: Vec<SlowlogEntry>| -> Vec<i64> {
+            entries.iter().map(|entry| entry.id).collect()
+        };
+
+        assert_eq!(ids(new_entries(vec![entry(2), entry(1)], None)), vec![2, 1]);
+        assert_eq!(
+            ids(new_entries(vec![entry(4), entry(3), entry(2)], Some(2))),
+            vec![4, 3]
+        );
+        assert!(new_entries(vec![entry(2), entry(1)], Some(2)).is_empty());
+        // The server restarted and numbers its entries from zero again.
+        assert_eq!(ids(new_entries(vec![entry(0)], Some(7))), vec![0]);
+    }
+}
@@ -0,0 +1,221 @@
+use super::memory_analysis::key_prefix;
+use crate::utils::format_ttl_to_human_readable;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Event emitted after every batch of a TTL analysis.
+pub const TTL_ANALYSIS_PROGRESS_EVENT: &str = "ttl-analysis-progress";
+
+/// Exclusive upper bounds of the expiring buckets in seconds, the last bucket holds the rest.
+const BUCKET_BOUNDS: [i64; 6] = [60, 3600, 86400, 7 * 86400, 30 * 86400, 360 * 86400];
+
+/// Share of persistent keys above which a prefix is flagged.
+const MOSTLY_PERSISTENT_RATIO: f64 = 0.5;
+
+/// Prefixes beyond this many, ranked by key count, are left out of an analysis.
+const MAX_PREFIXES: usize = 1000;
+
+const HOUR: i64 = 3600;
+const DAY: i64 = 86400;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TtlBucket {
+    /// `-` for keys without a TTL, like in the key list.
+    pub label: String,
+    pub keys: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PrefixTtl {
+    pub prefix: String,
+    pub keys: u64,
+    pub buckets: Vec<TtlBucket>,
+    /// Share of the prefix's keys without a TTL.
+    pub persistent_ratio: f64,
+    pub mostly_persistent: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TtlAnalysis {
+    /// Number of keys read, the same key may be read twice when sampling randomly.
+    pub sampled: u64,
+    /// Number of keys the sample stands for: `DBSIZE` when sampling randomly,
+    /// the scanned keys otherwise.
+    pub population: u64,
+    pub buckets: Vec<TtlBucket>,
+    /// Sorted by key count, biggest first, at most `MAX_PREFIXES`.
+    pub prefixes: Vec<PrefixTtl>,
+    /// Keys expected to expire within an hour, scaled up to `population`.
+    pub expiring_within_hour: u64,
+    /// Keys expected to expire within a day, scaled up to `population`.
+    pub expiring_within_day: u64,
+}
+
+/// Returns the labels of the buckets, the persistent one first.
+pub fn bucket_labels() -> Vec<String> {
+    let mut labels = vec![format_ttl_to_human_readable(&-1)];
+    labels.extend(
+        BUCKET_BOUNDS
+            .iter()
+            .map(|bound| format!("< {}", format_ttl_to_human_readable(bound))),
+    );
+    labels.push(format!(
+        ">= {}",
+        format_ttl_to_human_readable(&BUCKET_BOUNDS[BUCKET_BOUNDS.len() - 1])
+    ));
+    labels
+}
+
+/// Returns the index of the bucket a TTL in seconds falls in, negative TTLs never expire.
+fn bucket_index(ttl: i64) -> usize {
+    if ttl < 0 {
+        return 0;
+    }
+    1 + BUCKET_BOUNDS
+        .iter()
+        .position(|bound| ttl < *bound)
+        .unwrap_or(BUCKET_BOUNDS.len())
+}
+
+fn to_buckets(labels: &[String], counts: &[u64]) -> Vec<TtlBucket> {
+    labels
+        .iter()
+        .zip(counts)
+        .map(|(label, keys)| TtlBucket {
+            label: label.clone(),
+            keys: *keys,
+        })
+        .collect()
+}
+
+/// Histograms key TTLs overall and per prefix as they are read.
+#[derive(Debug)]
+pub struct TtlAnalyzer {
+    separator: String,
+    prefix_depth: usize,
+    sampled: u64,
+    counts: Vec<u64>,
+    within_hour: u64,
+    within_day: u64,
+    prefixes: HashMap<String, Vec<u64>>,
+}
+
+impl TtlAnalyzer {
+    pub fn new(separator: String, prefix_depth: usize) -> Self {
+        Self {
+            separator,
+            prefix_depth,
+            sampled: 0,
+            counts: vec![0; BUCKET_BOUNDS.len() + 2],
+            within_hour: 0,
+            within_day: 0,
+            prefixes: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, key: &str, ttl: i64) {
+        let index = bucket_index(ttl);
+        self.sampled += 1;
+        self.counts[index] += 1;
+        if (0..=HOUR).contains(&ttl) {
+            self.within_hour += 1;
+        }
+        if (0..=DAY).contains(&ttl) {
+            self.within_day += 1;
+        }
+
+        let prefix = key_prefix(key, &self.separator, self.prefix_depth);
+        let counts = self
+            .prefixes
+            .entry(prefix.to_string())
+            .or_insert_with(|| vec![0; BUCKET_BOUNDS.len() + 2]);
+        counts[index] += 1;
+    }
+
+    pub fn finish(self, population: u64) -> TtlAnalysis {
+        let labels = bucket_labels();
+        let population = population.max(self.sampled);
+        let scale = match self.sampled {
+            0 => 0.0,
+            sampled => population as f64 / sampled as f64,
+        };
+
+        let mut prefixes: Vec<PrefixTtl> = self
+            .prefixes
+            .into_iter()
+            .map(|(prefix, counts)| {
+                let keys: u64 = counts.iter().sum();
+                let persistent_ratio = counts[0] as f64 / keys as f64;
+                PrefixTtl {
+                    prefix,
+                    keys,
+                    buckets: to_buckets(&labels, &counts),
+                    persistent_ratio,
+                    mostly_persistent: persistent_ratio > MOSTLY_PERSISTENT_RATIO,
+                }
+            })
+            .collect();
+        prefixes.sort_by(|a, b| b.keys.cmp(&a.keys).then(a.prefix.cmp(&b.prefix)));
+        prefixes.truncate(MAX_PREFIXES);
+
+        TtlAnalysis {
+            sampled: self.sampled,
+            population,
+            buckets: to_buckets(&labels, &self.counts),
+            prefixes,
+            expiring_within_hour: (self.within_hour as f64 * scale).round() as u64,
+            expiring_within_day: (self.within_day as f64 * scale).round() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_labels() {
+        assert_eq!(
+            bucket_labels(),
+            vec!["-", "< 1min", "< 1h", "< 1d", "< 7d", "< 1mon", "< 1y", ">= 1y"]
+        );
+        assert_eq!(bucket_index(-1), 0);
+        assert_eq!(bucket_index(59), 1);
+        assert_eq!(bucket_index(60), 2);
+        assert_eq!(bucket_index(400 * 86400), 7);
+    }
+
+    #[test]
+    fn test_analyzer_histograms_prefixes() {
+        let mut analyzer = TtlAnalyzer::new(":".to_string(), 1);
+        analyzer.add("session:1", 30);
+        analyzer.add("session:2", 1800);
+        analyzer.add("session:3", 7200);
+        analyzer.add("user:1", -1);
+        analyzer.add("user:2", -1);
+        analyzer.add("user:3", 90000);
+        let analysis = analyzer.finish(60);
+
+        assert_eq!(analysis.sampled, 6);
+        assert_eq!(analysis.buckets[0].keys, 2);
+        assert_eq!(analysis.buckets[1].keys, 1);
+        // Two of six sampled keys expire within an hour, scaled up to 60 keys.
+        assert_eq!(analysis.expiring_within_hour, 20);
+        assert_eq!(analysis.expiring_within_day, 30);
+
+        let users = analysis
+            .prefixes
+            .iter()
+            .find(|p| p.prefix == "user")
+            .unwrap();
+        assert!(users.mostly_persistent);
+        assert_eq!(users.buckets[0].keys, 2);
+        let sessions = analysis
+            .prefixes
+            .iter()
+            .find(|p| p.prefix == "session")
+            .unwrap();
+        assert!(!sessions.mostly_persistent);
+        assert_eq!(sessions.persistent_ratio, 0.0);
+    }
+}
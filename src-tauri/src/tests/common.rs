@@ -11,7 +11,16 @@ use testcontainers::{
 /// # Returns
 /// * `(String, u16, ContainerAsync<GenericImage>)` - A tuple containing the host, port, and container instance.
 pub async fn run_redis_container(port: u16) -> (String, u16, ContainerAsync<GenericImage>) {
-    let container = GenericImage::new("redis", "8.0.2-alpine")
+    run_redis_version_container("8.0.2-alpine", port).await
+}
+
+/// Runs a Redis container of a specific image tag, e.g. `6.2-alpine` for the behaviour of
+/// older servers.
+pub async fn run_redis_version_container(
+    tag: &str,
+    port: u16,
+) -> (String, u16, ContainerAsync<GenericImage>) {
+    let container = GenericImage::new("redis", tag)
         .with_exposed_port(port.tcp())
         .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
         .with_network("bridge")
@@ -26,3 +35,82 @@ pub async fn run_redis_container(port: u16) -> (String, u16, ContainerAsync<Gene
         container,
     )
 }
+
+/// Runs a single-node Redis Cluster serving every slot. The node listens on the same port
+/// inside and outside the container and announces `127.0.0.1`, so the slot map sends the
+/// cluster client back to the mapped port.
+/// Returns the host, port, and container instance.
+pub async fn run_redis_cluster_container() -> (String, u16, ContainerAsync<GenericImage>) {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("Failed to find a free port")
+        .port();
+    let container = GenericImage::new("redis", "8.0.2-alpine")
+        .with_exposed_port(port.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+        .with_mapped_port(port, port.tcp())
+        .with_cmd([
+            "redis-server".to_string(),
+            "--port".to_string(),
+            port.to_string(),
+            "--cluster-enabled".to_string(),
+            "yes".to_string(),
+            "--cluster-announce-ip".to_string(),
+            "127.0.0.1".to_string(),
+        ])
+        .start()
+        .await
+        .expect("Failed to start Redis Cluster");
+
+    let client = redis::Client::open(format!("redis://127.0.0.1:{}", port)).unwrap();
+    let mut connection = client.get_multiplexed_async_connection().await.unwrap();
+    let _: () = redis::cmd("CLUSTER")
+        .arg("ADDSLOTSRANGE")
+        .arg(0)
+        .arg(16383)
+        .query_async(&mut connection)
+        .await
+        .unwrap();
+    // The node only serves the slots once it considers the cluster healthy.
+    loop {
+        let info: String = redis::cmd("CLUSTER")
+            .arg("INFO")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        if info.contains("cluster_state:ok") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    ("127.0.0.1".to_string(), port, container)
+}
+
+/// Runs a Redis Sentinel monitoring `mymaster` at `127.0.0.1:6379` and requiring `password`,
+/// the master does not need to exist for the Sentinel to report its address.
+/// Returns the host, port, and container instance.
+pub async fn run_redis_sentinel_container(
+    password: &str,
+) -> (String, u16, ContainerAsync<GenericImage>) {
+    let script = format!(
+        "printf 'sentinel monitor mymaster 127.0.0.1 6379 1\\n' > /tmp/sentinel.conf && \
+         exec redis-sentinel /tmp/sentinel.conf --requirepass {}",
+        password
+    );
+    let container = GenericImage::new("redis", "8.0.2-alpine")
+        .with_exposed_port(26379.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("+monitor master"))
+        .with_entrypoint("sh")
+        .with_cmd(["-c".to_string(), script])
+        .with_network("bridge")
+        .start()
+        .await
+        .expect("Failed to start Redis Sentinel");
+
+    (
+        container.get_host().await.unwrap().to_string(),
+        container.get_host_port_ipv4(26379).await.unwrap(),
+        container,
+    )
+}
@@ -1,11 +1,21 @@
 mod db_path;
 mod format_ttl_to_human_readable;
+mod glob_match;
 mod logger;
+mod parse_server_info;
 mod redis_to_json_value;
+mod redis_to_resp_value;
+mod split_command_args;
 // mod updater;
 
 pub use db_path::get_db_base_dir;
 pub use format_ttl_to_human_readable::format_ttl_to_human_readable;
+pub use glob_match::glob_match;
 pub use logger::init_logger;
+pub use parse_server_info::{parse_server_info, InfoSection, InfoValue, ServerInfo};
 pub use redis_to_json_value::redis_to_json;
+pub use redis_to_resp_value::{
+    redis_error_to_resp, redis_to_resp, server_error_message, RespValue,
+};
+pub use split_command_args::split_command_args;
 // pub use updater::update;
@@ -0,0 +1,310 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Fields that look numeric but are identifiers, e.g. `redis_git_sha1:00000000`.
+const TEXT_FIELDS: [&str; 5] = [
+    "redis_git_sha1",
+    "redis_build_id",
+    "run_id",
+    "master_replid",
+    "master_replid2",
+];
+
+/// A single `INFO` value. Lists such as `db0:keys=1,expires=0` become `Nested`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum InfoValue {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    Nested(InfoSection),
+}
+
+pub type InfoSection = BTreeMap<String, InfoValue>;
+
+/// The reply of `INFO everything`, split into the sections the UI knows about.
+/// `commandstats` and `errorstats` are keyed without their `cmdstat_`/`errorstat_` prefix.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ServerInfo {
+    pub server: InfoSection,
+    pub clients: InfoSection,
+    pub memory: InfoSection,
+    pub persistence: InfoSection,
+    pub stats: InfoSection,
+    pub replication: InfoSection,
+    pub cpu: InfoSection,
+    pub keyspace: InfoSection,
+    pub modules: Vec<InfoSection>,
+    pub commandstats: InfoSection,
+    pub errorstats: InfoSection,
+    /// Sections added by newer servers or modules, e.g. `latencystats` or `cluster`.
+    pub other: BTreeMap<String, InfoSection>,
+}
+
+fn parse_scalar(key: &str, value: &str) -> InfoValue {
+    if TEXT_FIELDS.contains(&key) {
+        return InfoValue::Text(value.to_string());
+    }
+    if let Ok(integer) = value.parse::<i64>() {
+        return InfoValue::Integer(integer);
+    }
+    // `f64::from_str` also accepts words like `inf` or `nan`, which are never meant as numbers here.
+    let looks_numeric = value
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+        && value.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+    match value.parse::<f64>() {
+        Ok(float) if looks_numeric && float.is_finite() => InfoValue::Float(float),
+        _ => InfoValue::Text(value.to_string()),
+    }
+}
+
+/// Parses `key=value,key=value` lists. Brackets are kept together so module
+/// lines like `usedby=[a,b]` do not split inside the list.
+fn parse_nested(value: &str) -> InfoSection {
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                fields.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&value[start..]);
+
+    fields
+        .into_iter()
+        .filter_map(|field| field.split_once('='))
+        .map(|(key, value)| (key.to_string(), parse_scalar(key, value)))
+        .collect()
+}
+
+fn parse_value(key: &str, value: &str) -> InfoValue {
+    match value.contains('=') {
+        true => InfoValue::Nested(parse_nested(value)),
+        false => parse_scalar(key, value),
+    }
+}
+
+/// Parses the text reply of `INFO`. Lines outside of a `# Section` header and
+/// lines without a `:` separator are ignored.
+pub fn parse_server_info(info: &str) -> ServerInfo {
+    let mut result = ServerInfo::default();
+    let mut section = String::new();
+
+    for line in info.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('#') {
+            section = name.trim().to_lowercase();
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        match section.as_str() {
+            "modules" => result.modules.push(parse_nested(value)),
+            "commandstats" => {
+                let name = key.strip_prefix("cmdstat_").unwrap_or(key);
+                result
+                    .commandstats
+                    .insert(name.to_string(), parse_value(key, value));
+            }
+            "errorstats" => {
+                let name = key.strip_prefix("errorstat_").unwrap_or(key);
+                result
+                    .errorstats
+                    .insert(name.to_string(), parse_value(key, value));
+            }
+            "" => {}
+            _ => {
+                let value = parse_value(key, value);
+                match section_mut(&mut result, &section) {
+                    Some(fields) => fields,
+                    None => result.other.entry(section.clone()).or_default(),
+                }
+                .insert(key.to_string(), value);
+            }
+        }
+    }
+
+    result
+}
+
+fn section_mut<'a>(info: &'a mut ServerInfo, name: &str) -> Option<&'a mut InfoSection> {
+    match name {
+        "server" => Some(&mut info.server),
+        "clients" => Some(&mut info.clients),
+        "memory" => Some(&mut info.memory),
+        "persistence" => Some(&mut info.persistence),
+        "stats" => Some(&mut info.stats),
+        "replication" => Some(&mut info.replication),
+        "cpu" => Some(&mut info.cpu),
+        "keyspace" => Some(&mut info.keyspace),
+        "commandstats" => Some(&mut info.commandstats),
+        "errorstats" => Some(&mut info.errorstats),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REDIS_6_2: &str = include_str!("../tests/fixtures/info/redis-6.2.txt");
+    const REDIS_7_2: &str = include_str!("../tests/fixtures/info/redis-7.2.txt");
+    const VALKEY_8_0: &str = include_str!("../tests/fixtures/info/valkey-8.0.txt");
+
+    fn nested(value: &InfoValue) -> &InfoSection {
+        match value {
+            InfoValue::Nested(fields) => fields,
+            other => panic!("expected nested value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_redis_6_2() {
+        let info = parse_server_info(REDIS_6_2);
+
+        assert_eq!(
+            info.server["redis_version"],
+            InfoValue::Text("6.2.14".to_string())
+        );
+        assert_eq!(
+            info.server["redis_git_sha1"],
+            InfoValue::Text("00000000".to_string())
+        );
+        assert_eq!(info.server["config_file"], InfoValue::Text(String::new()));
+        assert_eq!(info.clients["connected_clients"], InfoValue::Integer(2));
+        assert_eq!(
+            info.memory["used_memory_peak_perc"],
+            InfoValue::Text("93.74%".to_string())
+        );
+        assert_eq!(
+            info.memory["mem_fragmentation_ratio"],
+            InfoValue::Float(7.89)
+        );
+        assert_eq!(info.cpu["used_cpu_sys_children"], InfoValue::Float(0.0));
+        assert_eq!(info.stats["keyspace_hits"], InfoValue::Integer(41));
+        assert!(info.modules.is_empty());
+
+        let slave = nested(&info.replication["slave0"]);
+        assert_eq!(slave["ip"], InfoValue::Text("172.17.0.3".to_string()));
+        assert_eq!(slave["offset"], InfoValue::Integer(4096));
+
+        let db0 = nested(&info.keyspace["db0"]);
+        assert_eq!(db0["keys"], InfoValue::Integer(12));
+        assert_eq!(db0["expires"], InfoValue::Integer(2));
+        assert_eq!(db0["avg_ttl"], InfoValue::Integer(86234));
+        assert_eq!(info.keyspace.len(), 2);
+
+        let get = nested(&info.commandstats["get"]);
+        assert_eq!(get["calls"], InfoValue::Integer(48));
+        assert_eq!(get["usec_per_call"], InfoValue::Float(3.38));
+        assert_eq!(
+            nested(&info.errorstats["WRONGTYPE"])["count"],
+            InfoValue::Integer(1)
+        );
+        assert_eq!(
+            info.other["cluster"]["cluster_enabled"],
+            InfoValue::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_parse_redis_7_2() {
+        let info = parse_server_info(REDIS_7_2);
+
+        assert_eq!(
+            info.memory["maxmemory_policy"],
+            InfoValue::Text("allkeys-lru".to_string())
+        );
+        assert_eq!(
+            nested(&info.server["listener0"])["port"],
+            InfoValue::Integer(6379)
+        );
+
+        assert_eq!(info.modules.len(), 2);
+        assert_eq!(
+            info.modules[0]["name"],
+            InfoValue::Text("search".to_string())
+        );
+        assert_eq!(info.modules[0]["ver"], InfoValue::Integer(20813));
+        assert_eq!(
+            info.modules[1]["usedby"],
+            InfoValue::Text("[search]".to_string())
+        );
+
+        let config_get = nested(&info.commandstats["config|get"]);
+        assert_eq!(config_get["calls"], InfoValue::Integer(4));
+        assert_eq!(
+            nested(&info.commandstats["evalsha"])["failed_calls"],
+            InfoValue::Integer(1)
+        );
+        assert_eq!(
+            nested(&info.keyspace["db0"])["subexpiry"],
+            InfoValue::Integer(0)
+        );
+
+        let latency = &info.other["latencystats"];
+        assert_eq!(
+            nested(&latency["latency_percentiles_usec_get"])["p99.9"],
+            InfoValue::Float(12.031)
+        );
+    }
+
+    #[test]
+    fn test_parse_valkey_8_0() {
+        let info = parse_server_info(VALKEY_8_0);
+
+        assert_eq!(
+            info.server["server_name"],
+            InfoValue::Text("valkey".to_string())
+        );
+        assert_eq!(
+            info.server["valkey_version"],
+            InfoValue::Text("8.0.1".to_string())
+        );
+        assert_eq!(
+            info.replication["role"],
+            InfoValue::Text("slave".to_string())
+        );
+        assert_eq!(info.replication["master_port"], InfoValue::Integer(6379));
+        assert!(info.errorstats.is_empty());
+        assert_eq!(
+            nested(&info.commandstats["info"])["usec_per_call"],
+            InfoValue::Float(152.5)
+        );
+    }
+
+    #[test]
+    fn test_parse_crlf_and_stray_lines() {
+        let info = parse_server_info(
+            "orphan:1\r\n# Stats\r\ninstantaneous_input_kbps:0.02\r\nnot a field\r\n\
+            evicted_keys:-1\r\nratio:inf\r\n",
+        );
+
+        assert_eq!(info.stats.len(), 3);
+        assert_eq!(
+            info.stats["instantaneous_input_kbps"],
+            InfoValue::Float(0.02)
+        );
+        assert_eq!(info.stats["evicted_keys"], InfoValue::Integer(-1));
+        assert_eq!(info.stats["ratio"], InfoValue::Text("inf".to_string()));
+        assert!(info.other.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_as_plain_json() {
+        let info = parse_server_info("# Keyspace\ndb0:keys=3,expires=0,avg_ttl=0\n");
+        let json = serde_json::to_value(&info).unwrap();
+
+        assert_eq!(json["keyspace"]["db0"]["keys"], 3);
+        assert_eq!(json["modules"], serde_json::json!([]));
+    }
+}
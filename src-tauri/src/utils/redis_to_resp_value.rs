@@ -0,0 +1,176 @@
+use redis::{RedisError, Value as RedisValue};
+use serde::Serialize;
+
+/// A reply tree that keeps every RESP type apart, unlike `redis_to_json`
+/// which flattens replies into plain JSON values.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RespValue {
+    Nil,
+    Integer(i64),
+    Double(f64),
+    Boolean(bool),
+    BigNumber(String),
+    SimpleString(String),
+    BulkString(String),
+    Verbatim {
+        format: String,
+        text: String,
+    },
+    Error(String),
+    Array(Vec<RespValue>),
+    Set(Vec<RespValue>),
+    Map(Vec<(RespValue, RespValue)>),
+    Push {
+        kind: String,
+        data: Vec<RespValue>,
+    },
+    Attribute {
+        data: Box<RespValue>,
+        attributes: Vec<(RespValue, RespValue)>,
+    },
+    /// Reply types added to the client library after this tree was written.
+    Unsupported(String),
+}
+
+/// Renders binary-safe bytes as text, escaping non UTF-8 content as `\xHH` like `redis-cli`.
+pub fn bytes_to_display(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes
+            .iter()
+            .map(|byte| match byte {
+                b'\\' => "\\\\".to_string(),
+                0x20..=0x7e => (*byte as char).to_string(),
+                _ => format!("\\x{:02x}", byte),
+            })
+            .collect(),
+    }
+}
+
+/// Formats a server error the way Redis sends it, e.g. `WRONGTYPE Operation against a key...`.
+pub fn server_error_message(code: &str, detail: Option<&str>) -> String {
+    match detail {
+        Some(detail) => format!("{} {}", code, detail),
+        None => code.to_string(),
+    }
+}
+
+/// Converts an error reply into an error node, returning `None` for client-side failures
+/// such as dropped connections or timeouts.
+pub fn redis_error_to_resp(error: &RedisError) -> Option<RespValue> {
+    error
+        .code()
+        .map(|code| RespValue::Error(server_error_message(code, error.detail())))
+}
+
+fn pairs_to_resp(pairs: Vec<(RedisValue, RedisValue)>) -> Vec<(RespValue, RespValue)> {
+    pairs
+        .into_iter()
+        .map(|(k, v)| (redis_to_resp(k), redis_to_resp(v)))
+        .collect()
+}
+
+pub fn redis_to_resp(value: RedisValue) -> RespValue {
+    match value {
+        RedisValue::Nil => RespValue::Nil,
+        RedisValue::Int(i) => RespValue::Integer(i),
+        RedisValue::Double(f) => RespValue::Double(f),
+        RedisValue::Boolean(b) => RespValue::Boolean(b),
+        RedisValue::BigNumber(n) => RespValue::BigNumber(n.to_string()),
+        RedisValue::Okay => RespValue::SimpleString("OK".to_string()),
+        RedisValue::SimpleString(s) => RespValue::SimpleString(s),
+        RedisValue::BulkString(bytes) => RespValue::BulkString(bytes_to_display(&bytes)),
+        RedisValue::VerbatimString { format, text } => RespValue::Verbatim {
+            format: format.to_string(),
+            text,
+        },
+        RedisValue::ServerError(error) => {
+            RespValue::Error(server_error_message(error.code(), error.details()))
+        }
+        RedisValue::Array(items) => {
+            RespValue::Array(items.into_iter().map(redis_to_resp).collect())
+        }
+        RedisValue::Set(items) => RespValue::Set(items.into_iter().map(redis_to_resp).collect()),
+        RedisValue::Map(map) => RespValue::Map(pairs_to_resp(map)),
+        RedisValue::Push { kind, data } => RespValue::Push {
+            kind: kind.to_string(),
+            data: data.into_iter().map(redis_to_resp).collect(),
+        },
+        RedisValue::Attribute { data, attributes } => RespValue::Attribute {
+            data: Box::new(redis_to_resp(*data)),
+            attributes: pairs_to_resp(attributes),
+        },
+        _ => RespValue::Unsupported(format!("{:?}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use redis::PushKind;
+    use serde_json::json;
+
+    #[test]
+    fn test_scalar_replies_keep_their_type() {
+        assert_eq!(redis_to_resp(RedisValue::Nil), RespValue::Nil);
+        assert_eq!(redis_to_resp(RedisValue::Int(7)), RespValue::Integer(7));
+        assert_eq!(
+            redis_to_resp(RedisValue::Okay),
+            RespValue::SimpleString("OK".to_string())
+        );
+        assert_eq!(
+            redis_to_resp(RedisValue::BulkString(b"OK".to_vec())),
+            RespValue::BulkString("OK".to_string())
+        );
+        assert_eq!(
+            redis_to_resp(RedisValue::BulkString(vec![b'a', 0xff])),
+            RespValue::BulkString("a\\xff".to_string())
+        );
+    }
+
+    #[test]
+    fn test_aggregate_replies_keep_their_type() {
+        let map = RedisValue::Map(vec![(
+            RedisValue::SimpleString("server".to_string()),
+            RedisValue::Set(vec![RedisValue::Int(1)]),
+        )]);
+        assert_eq!(
+            redis_to_resp(map),
+            RespValue::Map(vec![(
+                RespValue::SimpleString("server".to_string()),
+                RespValue::Set(vec![RespValue::Integer(1)]),
+            )])
+        );
+
+        let push = RedisValue::Push {
+            kind: PushKind::Message,
+            data: vec![RedisValue::BulkString(b"news".to_vec())],
+        };
+        assert_eq!(
+            redis_to_resp(push),
+            RespValue::Push {
+                kind: "message".to_string(),
+                data: vec![RespValue::BulkString("news".to_string())],
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialized_shape() {
+        let value = RespValue::Array(vec![RespValue::Nil, RespValue::Integer(1)]);
+        assert_eq!(
+            serde_json::to_value(value).unwrap(),
+            json!({ "type": "array", "value": [{ "type": "nil" }, { "type": "integer", "value": 1 }] })
+        );
+    }
+
+    #[test]
+    fn test_server_error_message() {
+        assert_eq!(
+            server_error_message("WRONGTYPE", Some("Operation against a key")),
+            "WRONGTYPE Operation against a key"
+        );
+        assert_eq!(server_error_message("ERR", None), "ERR");
+    }
+}
@@ -0,0 +1,166 @@
+/// Splits a raw command line into arguments the same way `redis-cli` does (`sdssplitargs`).
+///
+/// Double quoted arguments support `\n`, `\r`, `\t`, `\b`, `\a` and `\xHH` escapes,
+/// single quoted arguments only support `\'`. A closing quote must be followed by
+/// whitespace or the end of the line.
+///
+/// # Returns
+/// * `None` if quotes are unbalanced or a closing quote is followed by another character.
+pub fn split_command_args(line: &str) -> Option<Vec<Vec<u8>>> {
+    let bytes = line.as_bytes();
+    let mut args = vec![];
+    let mut i = 0;
+
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return Some(args);
+        }
+
+        let mut current = vec![];
+        let mut in_double_quotes = false;
+        let mut in_single_quotes = false;
+
+        loop {
+            if in_double_quotes {
+                match bytes.get(i) {
+                    None => return None,
+                    Some(b'\\')
+                        if bytes.get(i + 1) == Some(&b'x')
+                            && hex_value(bytes.get(i + 2)).is_some()
+                            && hex_value(bytes.get(i + 3)).is_some() =>
+                    {
+                        let high = hex_value(bytes.get(i + 2)).unwrap();
+                        let low = hex_value(bytes.get(i + 3)).unwrap();
+                        current.push(high * 16 + low);
+                        i += 3;
+                    }
+                    Some(b'\\') if i + 1 < bytes.len() => {
+                        i += 1;
+                        current.push(match bytes[i] {
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'b' => 0x08,
+                            b'a' => 0x07,
+                            other => other,
+                        });
+                    }
+                    Some(b'"') => {
+                        if bytes.get(i + 1).is_some_and(|b| !b.is_ascii_whitespace()) {
+                            return None;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    Some(byte) => current.push(*byte),
+                }
+            } else if in_single_quotes {
+                match bytes.get(i) {
+                    None => return None,
+                    Some(b'\\') if bytes.get(i + 1) == Some(&b'\'') => {
+                        i += 1;
+                        current.push(b'\'');
+                    }
+                    Some(b'\'') => {
+                        if bytes.get(i + 1).is_some_and(|b| !b.is_ascii_whitespace()) {
+                            return None;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    Some(byte) => current.push(*byte),
+                }
+            } else {
+                match bytes.get(i) {
+                    None => break,
+                    Some(byte) if byte.is_ascii_whitespace() => break,
+                    Some(b'"') => in_double_quotes = true,
+                    Some(b'\'') => in_single_quotes = true,
+                    Some(byte) => current.push(*byte),
+                }
+            }
+            i += 1;
+        }
+
+        args.push(current);
+    }
+}
+
+fn hex_value(byte: Option<&u8>) -> Option<u8> {
+    match byte {
+        Some(b) if b.is_ascii_digit() => Some(b - b'0'),
+        Some(b) if (b'a'..=b'f').contains(b) => Some(b - b'a' + 10),
+        Some(b) if (b'A'..=b'F').contains(b) => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(line: &str) -> Option<Vec<String>> {
+        split_command_args(line).map(|args| {
+            args.into_iter()
+                .map(|arg| String::from_utf8_lossy(&arg).into_owned())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_split_plain_arguments() {
+        assert_eq!(
+            split("  SET  foo   bar "),
+            Some(vec!["SET".into(), "foo".into(), "bar".into()])
+        );
+        assert_eq!(split(""), Some(vec![]));
+        assert_eq!(split("   "), Some(vec![]));
+    }
+
+    #[test]
+    fn test_split_quoted_arguments() {
+        assert_eq!(
+            split(r#"SET "hello world" 'it\'s'"#),
+            Some(vec!["SET".into(), "hello world".into(), "it's".into()])
+        );
+        assert_eq!(
+            split(r#"SET k "a\"b""#),
+            Some(vec!["SET".into(), "k".into(), "a\"b".into()])
+        );
+        assert_eq!(
+            split(r#"SET k 'a\nb'"#),
+            Some(vec!["SET".into(), "k".into(), "a\\nb".into()])
+        );
+        assert_eq!(
+            split(r#"SET k """#),
+            Some(vec!["SET".into(), "k".into(), "".into()])
+        );
+    }
+
+    #[test]
+    fn test_split_escape_sequences() {
+        assert_eq!(
+            split_command_args(r#"SET k "\x41\xff\n\t""#),
+            Some(vec![
+                b"SET".to_vec(),
+                b"k".to_vec(),
+                vec![b'A', 0xff, b'\n', b'\t']
+            ])
+        );
+        // An incomplete hex sequence is kept as a literal `x`.
+        assert_eq!(
+            split(r#"SET k "\x4""#),
+            Some(vec!["SET".into(), "k".into(), "x4".into()])
+        );
+    }
+
+    #[test]
+    fn test_split_invalid_quotes() {
+        assert_eq!(split(r#"SET k "unterminated"#), None);
+        assert_eq!(split("SET k 'unterminated"), None);
+        assert_eq!(split(r#"SET k "closed"trailing"#), None);
+    }
+}
@@ -21,12 +21,170 @@ export const COMMANDS = {
 	CLOSE_SERVER: "close_server",
 	/** Retrieve details of a specific key from the server. */
 	RETRIEVE_KEY: "retrieve_key",
+	/** Apply an action to every key matching a pattern, or preview it with a dry run. */
+	RUN_BULK_JOB: "run_bulk_job",
+	/** Cancel a running long-running job by its ID. */
+	CANCEL_JOB: "cancel_job",
+	/** Run a raw command line on the open connection and return the typed reply. */
+	EXECUTE_COMMAND: "execute_command",
+	/** Search and paginate the console history of a server. */
+	GET_COMMAND_HISTORY: "get_command_history",
+	/** Remove the console history of a server. */
+	CLEAR_COMMAND_HISTORY: "clear_command_history",
+	/** Retrieve how long console history is kept. */
+	GET_HISTORY_RETENTION: "get_history_retention",
+	/** Change how long console history is kept. */
+	SET_HISTORY_RETENTION: "set_history_retention",
+	/** Pin a named command snippet with `{{parameter}}` placeholders. */
+	SAVE_SNIPPET: "save_snippet",
+	/** Retrieve the snippets pinned for a server. */
+	GET_SNIPPETS: "get_snippets",
+	/** Delete a pinned snippet. */
+	DELETE_SNIPPET: "delete_snippet",
+	/** Run a pinned snippet with values for its parameters. */
+	EXECUTE_SNIPPET: "execute_snippet",
+	/** Retrieve the commands and docs supported by the open server. */
+	GET_COMMAND_DOCS: "get_command_docs",
+	/** Suggest command names, argument tokens or key names for a partial command line. */
+	COMPLETE_COMMAND: "complete_command",
+	/** Issue a single-use confirmation token for a guarded command. */
+	CONFIRM_COMMAND: "confirm_command",
+	/** Retrieve the commands that require a confirmation token. */
+	GET_COMMAND_GUARD: "get_command_guard",
+	/** Change the commands that require a confirmation token. */
+	SET_COMMAND_GUARD: "set_command_guard",
+	/** Turn read-only mode of a server on or off. */
+	SET_SERVER_READ_ONLY: "set_server_read_only",
+	/** Add a Lua script to a server's library or update an existing one. */
+	SAVE_SCRIPT: "save_script",
+	/** Retrieve the Lua scripts kept for a server. */
+	GET_SCRIPTS: "get_scripts",
+	/** Delete a Lua script from the library. */
+	DELETE_SCRIPT: "delete_script",
+	/** Run a Lua script with separate `KEYS` and `ARGV` arrays. */
+	EVAL_SCRIPT: "eval_script",
+	/** Load a Lua script into the server's script cache. */
+	LOAD_SCRIPT: "load_script",
+	/** Check which SHA1 digests are in the server's script cache. */
+	SCRIPT_EXISTS: "script_exists",
+	/** Empty the server's script cache. */
+	FLUSH_SCRIPTS: "flush_scripts",
+	/** List function libraries with their functions and flags. */
+	LIST_FUNCTIONS: "list_functions",
+	/** Load or replace a function library from source. */
+	LOAD_FUNCTION: "load_function",
+	/** Delete a function library. */
+	DELETE_FUNCTION: "delete_function",
+	/** Write every function library to a file. */
+	DUMP_FUNCTIONS: "dump_functions",
+	/** Restore function libraries from a file written by `dump_functions`. */
+	RESTORE_FUNCTIONS: "restore_functions",
+	/** Invoke a function with `FCALL` or `FCALL_RO`. */
+	FCALL: "fcall",
+	/** Retrieve `INFO everything` parsed into typed sections. */
+	SERVER_INFO: "server_info",
+	/** Retrieve the metric samples of the opened server for a range in seconds. */
+	GET_METRICS: "get_metrics",
+	/** Retrieve the metrics sampler settings. */
+	GET_METRICS_SETTINGS: "get_metrics_settings",
+	/** Update the metrics sampler settings. */
+	SET_METRICS_SETTINGS: "set_metrics_settings",
+	/** Retrieve the newest slow log entries. */
+	GET_SLOWLOG: "get_slowlog",
+	/** Count the entries in the slow log. */
+	SLOWLOG_LEN: "slowlog_len",
+	/** Empty the slow log. */
+	RESET_SLOWLOG: "reset_slowlog",
+	/** Retrieve the slow log threshold and length. */
+	GET_SLOWLOG_CONFIG: "get_slowlog_config",
+	/** Update the slow log threshold and length. */
+	SET_SLOWLOG_CONFIG: "set_slowlog_config",
+	/** Retrieve the slow log entries captured for a server. */
+	GET_SLOWLOG_HISTORY: "get_slowlog_history",
+	/** Lists the server's connections with their flags, idle time and last command */
+	CLIENT_LIST: "client_list",
+	/** Closes connections by id, address, user or type */
+	KILL_CLIENTS: "kill_clients",
+	/** Suspends clients for a number of milliseconds */
+	PAUSE_CLIENTS: "pause_clients",
+	/** Resumes paused clients */
+	UNPAUSE_CLIENTS: "unpause_clients",
+	/** Protects the app's own connections from client eviction */
+	SET_CLIENT_NO_EVICT: "set_client_no_evict",
+	/** Streams MONITOR output through a channel with filters and a rate limit */
+	START_MONITOR: "start_monitor",
+	/** Stops the running MONITOR stream */
+	STOP_MONITOR: "stop_monitor",
+	/** Subscribes to channels, patterns or shard channels, messages are emitted as events */
+	SUBSCRIBE_CHANNELS: "subscribe_channels",
+	/** Unsubscribes from channels, patterns or shard channels */
+	UNSUBSCRIBE_CHANNELS: "unsubscribe_channels",
+	/** Publishes a message to a channel */
+	PUBLISH_MESSAGE: "publish_message",
+	/** Lists active channels with their subscriber counts */
+	LIST_PUBSUB_CHANNELS: "list_pubsub_channels",
+	/** Watches keyspace notifications of the current database, optionally enabling them */
+	WATCH_KEYSPACE: "watch_keyspace",
+	/** Stops watching keyspace notifications */
+	UNWATCH_KEYSPACE: "unwatch_keyspace",
+	/** Scans the keyspace for memory usage by type, prefix, TTL and encoding, with progress events */
+	RUN_MEMORY_ANALYSIS: "run_memory_analysis",
+	/** Lists the stored memory reports of a server */
+	GET_MEMORY_REPORTS: "get_memory_reports",
+	/** Compares two stored memory reports */
+	DIFF_MEMORY_REPORTS: "diff_memory_reports",
+	/** Histograms key TTLs per prefix from random samples or a full scan, with progress events */
+	RUN_TTL_ANALYSIS: "run_ttl_analysis",
+	/** Reads the server config flagged against the documented defaults */
+	GET_CONFIG: "get_config",
+	/** Sets one or many config parameters */
+	SET_CONFIG: "set_config",
+	/** Resets the INFO statistics */
+	RESET_CONFIG_STATS: "reset_config_stats",
+	/** Writes the running config back to the config file */
+	REWRITE_CONFIG: "rewrite_config",
+	/** Lists the ACL users with their rules */
+	LIST_ACL_USERS: "list_acl_users",
+	/** Reads an ACL user's flags, password hashes and permissions */
+	GET_ACL_USER: "get_acl_user",
+	/** Creates or updates an ACL user */
+	SET_ACL_USER: "set_acl_user",
+	/** Deletes ACL users */
+	DELETE_ACL_USERS: "delete_acl_users",
+	/** Returns the user the app is authenticated as */
+	ACL_WHOAMI: "acl_whoami",
+	/** Lists the ACL categories or the commands of one */
+	LIST_ACL_CATEGORIES: "list_acl_categories",
+	/** Reads the denied commands and authentications */
+	GET_ACL_LOG: "get_acl_log",
+	/** Checks whether a user could run a command */
+	ACL_DRY_RUN: "acl_dry_run",
+	/** Reads the master, replicas and sentinels of a Sentinel-managed server */
+	SENTINEL_OVERVIEW: "sentinel_overview",
+	/** Reads the cluster nodes, slot ranges, health and replication */
+	CLUSTER_TOPOLOGY: "cluster_topology",
+	/** Reads the node role, offsets and replica lag */
+	REPLICATION_OVERVIEW: "replication_overview",
+	/** Follows another primary or promotes the node to primary */
+	REPLICA_OF: "replica_of",
+	/** Adds a server from a redis://, rediss:// or redis+unix:// URL */
+	ADD_SERVER_FROM_URL: "add_server_from_url",
+	/** Writes saved servers to a versioned JSON bundle, secrets optional */
+	EXPORT_SERVERS: "export_servers",
+	/** Imports a server bundle, skipping servers that are already saved */
+	IMPORT_SERVERS: "import_servers",
+	/** Imports connections exported by RedisInsight, ARDM or a host port [password] list */
+	IMPORT_CONNECTIONS: "import_connections",
 };
 
 /**
  * @enum APP_ERROR_CODES
  * @description Application-specific error codes for handling various error scenarios.
  * @property {number} REDIS_FAILED - Error code for Redis interaction failures.
+ * @property {number} INVALID_COMMAND - Error code for command lines that cannot be parsed or run.
+ * @property {number} WRITE_BLOCKED - Error code for write commands sent to a read-only server.
+ * @property {number} CONFIRMATION_REQUIRED - Error code for guarded commands sent without a valid confirmation token.
+ * @property {number} FILE_FAILED - Error code for files that cannot be read or written.
  * @property {number} DATABASE_NOT_READY - Error code for database not being ready.
  * @property {number} DATABASE_QUERY_FAILED - Error code for generic database query failures.
  */
@@ -35,6 +193,30 @@ export enum APP_ERROR_CODES {
 	 * Failure to interact with Redis server, e.g., connection failed, command failed
 	 */
 	REDIS_FAILED = 910,
+	/**
+	 * The command line could not be parsed, e.g., unbalanced quotes or unsupported command
+	 */
+	INVALID_COMMAND = 920,
+	/**
+	 * The connection URL could not be parsed, e.g., unknown scheme or invalid port
+	 */
+	INVALID_URL = 921,
+	/**
+	 * The command writes data but the server is opened in read-only mode
+	 */
+	WRITE_BLOCKED = 930,
+	/**
+	 * The command is on the dangerous command guard list and was sent without a valid confirmation token
+	 */
+	CONFIRMATION_REQUIRED = 931,
+	/**
+	 * Failure to read or write a file picked by the user, e.g., missing file or permission denied
+	 */
+	FILE_FAILED = 940,
+	/**
+	 * Failure to open the SSH tunnel, e.g., bastion unreachable, authentication rejected or unknown host key
+	 */
+	SSH_FAILED = 950,
 	/**
 	 * Database is not ready, e.g., connection not established
 	 */
@@ -10,6 +10,26 @@ export class ServerService {
 				errorMessage =
 					"Failed to interact with the Redis server. Please check your connection settings.";
 				break;
+			case APP_ERROR_CODES.INVALID_COMMAND:
+				errorMessage =
+					"The command could not be parsed. Please check its quotes and arguments.";
+				break;
+			case APP_ERROR_CODES.INVALID_URL:
+				errorMessage =
+					"The connection URL could not be parsed. Please check its scheme, host and port.";
+				break;
+			case APP_ERROR_CODES.WRITE_BLOCKED:
+				errorMessage =
+					"This server is in read-only mode. Write commands are not allowed.";
+				break;
+			case APP_ERROR_CODES.CONFIRMATION_REQUIRED:
+				errorMessage =
+					"This command is potentially dangerous and must be confirmed before it runs.";
+				break;
+			case APP_ERROR_CODES.FILE_FAILED:
+				errorMessage =
+					"The file could not be read or written. Please check its path and permissions.";
+				break;
 			case APP_ERROR_CODES.DATABASE_NOT_READY:
 				errorMessage =
 					"The database is not ready. Please ensure the connection is established.";
@@ -17,6 +17,15 @@ export function useServerFactory() {
 			server: {
 				...serverFormFields,
 				id: reandomString(20),
+				connection_kind: { kind: "tcp" },
+				username: null,
+				password: null,
+				database: 0,
+				tls: false,
+				read_only: false,
+				sentinel: null,
+				cluster: false,
+				ssh_tunnel: null,
 				created_at: new Date(),
 				updated_at: new Date(),
 			} as TServer,
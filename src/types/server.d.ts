@@ -1,8 +1,37 @@
+type TConnectionKind = { kind: "tcp" } | { kind: "unix_socket"; path: string };
+
+interface TSentinelSettings {
+	master_name: string;
+	sentinels: { address: string; port: number }[];
+	username: string | null;
+	password: string | null;
+	tls: boolean;
+}
+
+interface TSshTunnelSettings {
+	host: string;
+	port: number;
+	user: string;
+	password: string | null;
+	private_key_path: string | null;
+	passphrase: string | null;
+	known_hosts_path: string | null;
+}
+
 interface TServer {
 	id: string;
 	name: string;
 	address: string;
 	port: number;
+	connection_kind: TConnectionKind;
+	username: string | null;
+	password: string | null;
+	database: number;
+	tls: boolean;
+	read_only: boolean;
+	sentinel: TSentinelSettings | null;
+	cluster: boolean;
+	ssh_tunnel: TSshTunnelSettings | null;
 	created_at: Date;
 	updated_at: Date;
 }
@@ -11,4 +40,13 @@ interface TServerFromBackend extends TServer {
 	created_at: string;
 	updated_at: string;
 }
+type TConnectionSource = "redis_insight" | "ardm" | "text_list";
+
+interface TServerImportResult {
+	name: string;
+	server: TServerFromBackend | null;
+	duplicate_of: string | null;
+	error: string | null;
+}
+
 type TServerFormFields = Pick<TServer, "name" | "address" | "port">;